@@ -154,6 +154,8 @@ fn main() {
     }
 
     let menu_channel = MenuEvent::receiver();
+    // submenus being opened or closed and items being highlighted
+    let navigation_channel = MenuEvent::navigation_receiver();
     let mut window_cursor_position = PhysicalPosition { x: 0., y: 0. };
     let mut use_window_pos = false;
 
@@ -215,6 +217,10 @@ fn main() {
             }
             println!("{event:?}");
         }
+
+        if let Ok(event) = navigation_channel.try_recv() {
+            println!("{event:?}");
+        }
     })
 }
 
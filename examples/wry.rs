@@ -277,6 +277,8 @@ fn main() -> wry::Result<()> {
         .build()?;
 
     let menu_channel = MenuEvent::receiver();
+    // submenus being opened or closed and items being highlighted
+    let navigation_channel = MenuEvent::navigation_receiver();
 
     event_loop.run(move |event, _, control_flow| {
         *control_flow = ControlFlow::Wait;
@@ -300,6 +302,10 @@ fn main() -> wry::Result<()> {
             }
             println!("{event:?}");
         }
+
+        if let Ok(event) = navigation_channel.try_recv() {
+            println!("{event:?}");
+        }
     })
 }
 
@@ -1,7 +1,10 @@
 use crate::icon::Icon;
 
 /// Application metadata for the [`PredefinedMenuItem::about`](crate::PredefinedMenuItem::about).
+///
+/// When serialized with the `serde` feature, the [`AboutMetadata::icon`] is skipped.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AboutMetadata {
     /// Sets the application name.
     pub name: Option<String>,
@@ -56,6 +59,7 @@ pub struct AboutMetadata {
     /// ## Platform-specific
     ///
     /// - **Windows:** Unsupported.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub icon: Option<Icon>,
 }
 
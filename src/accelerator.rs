@@ -26,9 +26,39 @@
 //! # assert!("shift+KeyQ+alt".parse::<Accelerator>().is_err());
 //! ```
 //!
+//! To bind a shortcut to the character a key produces on the user's keyboard layout,
+//! instead of a physical key, use [`Accelerator::from_char`](crate::accelerator::Accelerator::from_char)
+//! or the `char:` prefix, so `CmdOrCtrl+char:z` is bound to the key that types `z`, even on an AZERTY keyboard.
+//! ```no_run
+//! # use muda::accelerator::{Accelerator, Modifiers};
+//! let undo: Accelerator = "CmdOrCtrl+char:z".parse().unwrap();
+//! let undo_with_char = Accelerator::from_char(Some(Modifiers::CONTROL), 'z');
+//! ```
+//!
+//! Accelerators can be turned back into a string that can be parsed again using [`ToString`],
+//! or into a label for humans using [`Accelerator::display_label`](crate::accelerator::Accelerator::display_label).
+//! ```no_run
+//! # use muda::accelerator::{Accelerator, AcceleratorStyle};
+//! let accelerator: Accelerator = "ctrl+shift+KeyK".parse().unwrap();
+//! assert_eq!(accelerator.to_string(), "shift+control+KeyK");
+//! assert_eq!(accelerator.display_label(AcceleratorStyle::Windows), "Ctrl+Shift+K");
+//! assert_eq!(accelerator.display_label(AcceleratorStyle::MacOS), "⌃⇧K");
+//! assert_eq!(accelerator.display_label(AcceleratorStyle::Gtk), "Shift+Ctrl+K");
+//! ```
+//!
+//! Multi-stroke shortcuts (chords) like `Ctrl+K Ctrl+C` are described by a [`KeySequence`](crate::accelerator::KeySequence)
+//! and can be matched against key events using a [`KeySequenceMatcher`](crate::accelerator::KeySequenceMatcher).
+//! ```no_run
+//! # use muda::accelerator::KeySequence;
+//! let sequence: KeySequence = "Ctrl+KeyK Ctrl+KeyC".parse().unwrap();
+//! assert_eq!(sequence.strokes().len(), 2);
+//! ```
+//!
+
+pub use keyboard_types::{Code, Key, Modifiers};
+use std::{borrow::Borrow, fmt, hash::Hash, str::FromStr};
 
-pub use keyboard_types::{Code, Modifiers};
-use std::{borrow::Borrow, hash::Hash, str::FromStr};
+use crate::MenuId;
 
 #[cfg(target_os = "macos")]
 pub const CMD_OR_CTRL: Modifiers = Modifiers::SUPER;
@@ -47,15 +77,38 @@ pub enum AcceleratorParseError {
 
 /// A keyboard shortcut that consists of an optional combination
 /// of modifier keys (provided by [`Modifiers`](crate::accelerator::Modifiers)) and
-/// one key ([`Code`](crate::accelerator::Code)).
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+/// one key ([`Code`](crate::accelerator::Code)) or one character, see [`Accelerator::from_char`].
+#[derive(Debug, Copy, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Accelerator {
     pub(crate) mods: Modifiers,
+    /// [`Code::Unidentified`] for character based accelerators.
     pub(crate) key: Code,
+    pub(crate) character: Option<char>,
+    /// Whether [`CMD_OR_CTRL`] was parsed from `CmdOrCtrl`, so it is formatted back that way.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) cmd_or_ctrl: bool,
     id: u32,
 }
 
+// `cmd_or_ctrl` only affects how the accelerator is formatted,
+// `CmdOrCtrl+KeyQ` is the same accelerator as `Ctrl+KeyQ` on Windows and Linux.
+impl PartialEq for Accelerator {
+    fn eq(&self, other: &Self) -> bool {
+        self.mods == other.mods && self.key == other.key && self.character == other.character
+    }
+}
+
+impl Eq for Accelerator {}
+
+impl Hash for Accelerator {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.mods.hash(state);
+        self.key.hash(state);
+        self.character.hash(state);
+    }
+}
+
 impl Accelerator {
     /// Creates a new accelerator to define keyboard shortcuts throughout your application.
     /// Only [`Modifiers::ALT`], [`Modifiers::SHIFT`], [`Modifiers::CONTROL`], and [`Modifiers::SUPER`]
@@ -66,12 +119,62 @@ impl Accelerator {
             mods.insert(Modifiers::SUPER);
         }
 
-        let id = Self::generate_hash(mods, key);
+        let id = Self::generate_hash(mods, key, None);
+
+        Self {
+            mods,
+            key,
+            character: None,
+            cmd_or_ctrl: false,
+            id,
+        }
+    }
+
+    /// Creates a new accelerator that is bound to the key producing `character`
+    /// on the keyboard layout that is active when the accelerator is registered,
+    /// rather than to a physical key.
+    ///
+    /// Letters are case insensitive, use [`Modifiers::SHIFT`] to require the shift key.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows**: The character is resolved to a key when the accelerator is added to a menu,
+    ///   using the keyboard layout of the current thread.
+    /// - **Linux**: The character is converted to a gdk keyval which gtk resolves using the active keyboard layout.
+    pub fn from_char(mods: Option<Modifiers>, character: char) -> Self {
+        let mut mods = mods.unwrap_or_else(Modifiers::empty);
+        if mods.contains(Modifiers::META) {
+            mods.remove(Modifiers::META);
+            mods.insert(Modifiers::SUPER);
+        }
+
+        let mut lowercase = character.to_lowercase();
+        let character = match (lowercase.next(), lowercase.next()) {
+            (Some(c), None) => c,
+            _ => character,
+        };
+
+        let key = Code::Unidentified;
+        let id = Self::generate_hash(mods, key, Some(character));
 
-        Self { mods, key, id }
+        Self {
+            mods,
+            key,
+            character: Some(character),
+            cmd_or_ctrl: false,
+            id,
+        }
     }
 
-    fn generate_hash(mods: Modifiers, key: Code) -> u32 {
+    fn generate_hash(mods: Modifiers, key: Code, character: Option<char>) -> u32 {
+        let accelerator_str = Self::format(mods, key, character);
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        accelerator_str.hash(&mut hasher);
+        std::hash::Hasher::finish(&hasher) as u32
+    }
+
+    fn format(mods: Modifiers, key: Code, character: Option<char>) -> String {
         let mut accelerator_str = String::new();
         if mods.contains(Modifiers::SHIFT) {
             accelerator_str.push_str("shift+")
@@ -85,11 +188,15 @@ impl Accelerator {
         if mods.contains(Modifiers::SUPER) {
             accelerator_str.push_str("super+")
         }
-        accelerator_str.push_str(&key.to_string());
-
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        accelerator_str.hash(&mut hasher);
-        std::hash::Hasher::finish(&hasher) as u32
+        match character {
+            Some('+') => accelerator_str.push_str("char:plus"),
+            Some(character) => {
+                accelerator_str.push_str("char:");
+                accelerator_str.push(character);
+            }
+            None => accelerator_str.push_str(&key.to_string()),
+        }
+        accelerator_str
     }
 
     /// Returns the id associated with this accelerator
@@ -98,13 +205,229 @@ impl Accelerator {
         self.id
     }
 
+    /// Returns the character of this accelerator if it was created using [`Accelerator::from_char`].
+    pub fn character(&self) -> Option<char> {
+        self.character
+    }
+
     /// Returns `true` if this [`Code`] and [`Modifiers`] matches this `Accelerator`.
+    ///
+    /// Character based accelerators never match a [`Code`], use [`Accelerator::matches_key`] for them
+    /// or [`KeySequenceMatcher::process_key`] to match both kinds of accelerators.
     pub fn matches(&self, modifiers: impl Borrow<Modifiers>, key: impl Borrow<Code>) -> bool {
         // Should be a const but const bit_or doesn't work here.
         let base_mods = Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::SUPER;
         let modifiers = modifiers.borrow();
         let key = key.borrow();
-        self.mods == *modifiers & base_mods && self.key == *key
+        self.character.is_none() && self.mods == *modifiers & base_mods && self.key == *key
+    }
+
+    /// Returns `true` if this [`Key`] and [`Modifiers`] matches this character based `Accelerator`.
+    ///
+    /// Letters are compared case insensitively. For other characters, [`Modifiers::SHIFT`] is ignored
+    /// since the keyboard layout decides whether the shift key is needed to type them.
+    ///
+    /// Accelerators created from a [`Code`] never match a [`Key`], use [`Accelerator::matches`] for them.
+    pub fn matches_key(&self, modifiers: impl Borrow<Modifiers>, key: impl Borrow<Key>) -> bool {
+        let character = match self.character {
+            Some(character) => character,
+            None => return false,
+        };
+
+        let mut base_mods =
+            Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::SUPER;
+        if !character.is_alphabetic() {
+            base_mods.remove(Modifiers::SHIFT);
+        }
+
+        let pressed = match key.borrow() {
+            Key::Character(pressed) => pressed,
+            _ => return false,
+        };
+        let mut pressed_chars = pressed.chars();
+        let same_character = match (pressed_chars.next(), pressed_chars.next()) {
+            (Some(pressed), None) => pressed.to_lowercase().eq(character.to_lowercase()),
+            _ => false,
+        };
+
+        same_character && self.mods & base_mods == *modifiers.borrow() & base_mods
+    }
+
+    /// Returns a label for this accelerator in the given style, for example `Ctrl+Shift+K`,
+    /// to be shown to users in tooltips, settings pages or command palettes.
+    ///
+    /// Unlike [`ToString::to_string`], the returned label can't be parsed back into an accelerator.
+    pub fn display_label(&self, style: AcceleratorStyle) -> String {
+        let (modifiers, separator) = match style {
+            AcceleratorStyle::Windows => (
+                [
+                    (Modifiers::CONTROL, "Ctrl"),
+                    (Modifiers::SHIFT, "Shift"),
+                    (Modifiers::ALT, "Alt"),
+                    (Modifiers::SUPER, "Windows"),
+                ],
+                "+",
+            ),
+            AcceleratorStyle::MacOS => (
+                [
+                    (Modifiers::CONTROL, "⌃"),
+                    (Modifiers::ALT, "⌥"),
+                    (Modifiers::SHIFT, "⇧"),
+                    (Modifiers::SUPER, "⌘"),
+                ],
+                "",
+            ),
+            AcceleratorStyle::Gtk => (
+                [
+                    (Modifiers::SHIFT, "Shift"),
+                    (Modifiers::CONTROL, "Ctrl"),
+                    (Modifiers::ALT, "Alt"),
+                    (Modifiers::SUPER, "Super"),
+                ],
+                "+",
+            ),
+        };
+
+        let mut label = String::new();
+        for (modifier, name) in modifiers {
+            if self.mods.contains(modifier) {
+                label.push_str(name);
+                label.push_str(separator);
+            }
+        }
+        match self.character {
+            Some(character) => label.extend(character.to_uppercase()),
+            None => label.push_str(&key_label(self.key, style)),
+        }
+        label
+    }
+}
+
+/// The style of the labels returned from [`Accelerator::display_label`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AcceleratorStyle {
+    /// Modifiers spelled out in the `Ctrl+Shift+Alt+Windows` order, for example `Ctrl+Shift+K`.
+    Windows,
+    /// Modifier symbols in the `⌃⌥⇧⌘` order, for example `⌃⇧K`.
+    MacOS,
+    /// Modifiers spelled out in the `Shift+Ctrl+Alt+Super` order, for example `Shift+Ctrl+K`.
+    Gtk,
+}
+
+impl Default for AcceleratorStyle {
+    /// Returns the style of the current platform.
+    fn default() -> Self {
+        #[cfg(target_os = "windows")]
+        return Self::Windows;
+        #[cfg(target_os = "macos")]
+        return Self::MacOS;
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        return Self::Gtk;
+    }
+}
+
+fn key_label(key: Code, style: AcceleratorStyle) -> String {
+    let label = match (key, style) {
+        (Code::KeyA, _) => "A",
+        (Code::KeyB, _) => "B",
+        (Code::KeyC, _) => "C",
+        (Code::KeyD, _) => "D",
+        (Code::KeyE, _) => "E",
+        (Code::KeyF, _) => "F",
+        (Code::KeyG, _) => "G",
+        (Code::KeyH, _) => "H",
+        (Code::KeyI, _) => "I",
+        (Code::KeyJ, _) => "J",
+        (Code::KeyK, _) => "K",
+        (Code::KeyL, _) => "L",
+        (Code::KeyM, _) => "M",
+        (Code::KeyN, _) => "N",
+        (Code::KeyO, _) => "O",
+        (Code::KeyP, _) => "P",
+        (Code::KeyQ, _) => "Q",
+        (Code::KeyR, _) => "R",
+        (Code::KeyS, _) => "S",
+        (Code::KeyT, _) => "T",
+        (Code::KeyU, _) => "U",
+        (Code::KeyV, _) => "V",
+        (Code::KeyW, _) => "W",
+        (Code::KeyX, _) => "X",
+        (Code::KeyY, _) => "Y",
+        (Code::KeyZ, _) => "Z",
+        (Code::Digit0, _) => "0",
+        (Code::Digit1, _) => "1",
+        (Code::Digit2, _) => "2",
+        (Code::Digit3, _) => "3",
+        (Code::Digit4, _) => "4",
+        (Code::Digit5, _) => "5",
+        (Code::Digit6, _) => "6",
+        (Code::Digit7, _) => "7",
+        (Code::Digit8, _) => "8",
+        (Code::Digit9, _) => "9",
+        (Code::Comma, _) => ",",
+        (Code::Minus, _) => "-",
+        (Code::Period, _) => ".",
+        (Code::Equal, _) => "=",
+        (Code::Semicolon, _) => ";",
+        (Code::Slash, _) => "/",
+        (Code::Backslash, _) => "\\",
+        (Code::Quote, _) => "'",
+        (Code::Backquote, _) => "`",
+        (Code::BracketLeft, _) => "[",
+        (Code::BracketRight, _) => "]",
+
+        (Code::Space, AcceleratorStyle::MacOS) => "Space",
+        (Code::Tab, AcceleratorStyle::MacOS) => "⇥",
+        (Code::Enter, AcceleratorStyle::MacOS) => "↩",
+        (Code::Backspace, AcceleratorStyle::MacOS) => "⌫",
+        (Code::Escape, AcceleratorStyle::MacOS) => "⎋",
+        (Code::Delete, AcceleratorStyle::MacOS) => "⌦",
+        (Code::Home, AcceleratorStyle::MacOS) => "↖",
+        (Code::End, AcceleratorStyle::MacOS) => "↘",
+        (Code::PageUp, AcceleratorStyle::MacOS) => "⇞",
+        (Code::PageDown, AcceleratorStyle::MacOS) => "⇟",
+        (Code::ArrowLeft, AcceleratorStyle::MacOS) => "←",
+        (Code::ArrowRight, AcceleratorStyle::MacOS) => "→",
+        (Code::ArrowUp, AcceleratorStyle::MacOS) => "↑",
+        (Code::ArrowDown, AcceleratorStyle::MacOS) => "↓",
+
+        (Code::Escape, AcceleratorStyle::Windows) => "Esc",
+        (Code::Delete, AcceleratorStyle::Windows) => "Del",
+        (Code::Insert, AcceleratorStyle::Windows) => "Ins",
+        (Code::PageUp, AcceleratorStyle::Windows) => "PgUp",
+        (Code::PageDown, AcceleratorStyle::Windows) => "PgDn",
+
+        (Code::PageUp, AcceleratorStyle::Gtk) => "Page Up",
+        (Code::PageDown, AcceleratorStyle::Gtk) => "Page Down",
+
+        // These names match LibreOffice.
+        (Code::ArrowLeft, _) => "Left",
+        (Code::ArrowRight, _) => "Right",
+        (Code::ArrowUp, _) => "Up",
+        (Code::ArrowDown, _) => "Down",
+
+        (key, _) => return key.to_string(),
+    };
+    label.to_string()
+}
+
+impl fmt::Display for Accelerator {
+    /// Formats this accelerator as a string that can be parsed back into it, for example `shift+control+KeyQ`.
+    ///
+    /// [`CMD_OR_CTRL`] is formatted as `CmdOrCtrl` if it was parsed from it.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.cmd_or_ctrl && self.mods.contains(CMD_OR_CTRL) {
+            f.write_str("CmdOrCtrl+")?;
+            f.write_str(&Self::format(
+                self.mods - CMD_OR_CTRL,
+                self.key,
+                self.character,
+            ))
+        } else {
+            f.write_str(&Self::format(self.mods, self.key, self.character))
+        }
     }
 }
 
@@ -131,21 +454,364 @@ impl TryFrom<String> for Accelerator {
     }
 }
 
+/// A keyboard shortcut that consists of one or more strokes that have to be pressed one after another,
+/// for example `Ctrl+K Ctrl+C`.
+///
+/// Native menus can only trigger single-stroke accelerators, so menu items with a multi-stroke
+/// key sequence only show it in their label, and it is up to the application to feed its key events
+/// to a [`KeySequenceMatcher`] to trigger them.
+///
+/// With the `serde` feature, key sequences are serialized as the strings they are parsed from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct KeySequence {
+    strokes: Vec<Accelerator>,
+}
+
+impl KeySequence {
+    /// Creates a new key sequence that starts with `first` and continues with the `rest` of the strokes.
+    pub fn new(first: Accelerator, rest: impl IntoIterator<Item = Accelerator>) -> Self {
+        let mut strokes = vec![first];
+        strokes.extend(rest);
+        Self { strokes }
+    }
+
+    /// Returns the strokes of this key sequence, this is never empty.
+    pub fn strokes(&self) -> &[Accelerator] {
+        &self.strokes
+    }
+
+    /// Returns the first stroke of this key sequence.
+    pub fn first(&self) -> Accelerator {
+        self.strokes[0]
+    }
+
+    /// Returns `true` if this key sequence has more than one stroke.
+    pub fn is_chord(&self) -> bool {
+        self.strokes.len() > 1
+    }
+
+    /// Returns a label for this key sequence in the given style, for example `Ctrl+K Ctrl+C`,
+    /// see [`Accelerator::display_label`].
+    pub fn display_label(&self, style: AcceleratorStyle) -> String {
+        self.strokes
+            .iter()
+            .map(|stroke| stroke.display_label(style))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the text used to show this key sequence in a menu item label.
+    pub(crate) fn label(&self) -> String {
+        self.display_label(AcceleratorStyle::default())
+    }
+}
+
+impl fmt::Display for KeySequence {
+    /// Formats this key sequence as a string that can be parsed back into it,
+    /// for example `control+KeyK control+KeyC`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, stroke) in self.strokes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{stroke}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<Accelerator> for KeySequence {
+    fn from(accelerator: Accelerator) -> Self {
+        Self::new(accelerator, [])
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = AcceleratorParseError;
+    fn from_str(sequence: &str) -> Result<Self, Self::Err> {
+        parse_key_sequence(sequence)
+    }
+}
+
+impl TryFrom<&str> for KeySequence {
+    type Error = AcceleratorParseError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        parse_key_sequence(value)
+    }
+}
+
+impl TryFrom<String> for KeySequence {
+    type Error = AcceleratorParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse_key_sequence(&value)
+    }
+}
+
+impl From<KeySequence> for String {
+    fn from(sequence: KeySequence) -> Self {
+        sequence.to_string()
+    }
+}
+
+/// The result of feeding a key event to a [`KeySequenceMatcher`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeySequenceMatch {
+    /// The key event doesn't match any key sequence.
+    NoMatch,
+    /// The key event matches the start of at least one key sequence
+    /// and the matcher is waiting for the next stroke.
+    Pending,
+    /// The key event completed the key sequence registered for the menu item with this id.
+    Matched(MenuId),
+}
+
+/// A state machine that matches key events against multi-stroke [`KeySequence`]s.
+///
+/// Feed it the key presses of your window event loop using [`KeySequenceMatcher::process`],
+/// or [`KeySequenceMatcher::process_key`] to also match strokes created using [`Accelerator::from_char`],
+/// and call [`KeySequenceMatcher::reset`] if the user should no longer be able
+/// to complete a pending sequence, for example after a timeout or when the window loses focus.
+///
+/// ```no_run
+/// # use muda::accelerator::{Code, KeySequenceMatch, KeySequenceMatcher, Modifiers};
+/// let mut matcher = KeySequenceMatcher::new();
+/// matcher.register("comment", "Ctrl+KeyK Ctrl+KeyC".parse().unwrap());
+///
+/// assert_eq!(matcher.process(Modifiers::CONTROL, Code::KeyK), KeySequenceMatch::Pending);
+/// assert_eq!(
+///     matcher.process(Modifiers::CONTROL, Code::KeyC),
+///     KeySequenceMatch::Matched("comment".into())
+/// );
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct KeySequenceMatcher {
+    sequences: Vec<(MenuId, KeySequence)>,
+    pending: Vec<Stroke>,
+}
+
+/// A key press fed to a [`KeySequenceMatcher`].
+#[derive(Debug, Clone)]
+struct Stroke {
+    modifiers: Modifiers,
+    code: Code,
+    /// The logical key, if it is known, to match character based accelerators.
+    key: Option<Key>,
+}
+
+impl Stroke {
+    fn matches(&self, accelerator: &Accelerator) -> bool {
+        accelerator.matches(self.modifiers, self.code)
+            || self
+                .key
+                .as_ref()
+                .map(|key| accelerator.matches_key(self.modifiers, key))
+                .unwrap_or(false)
+    }
+}
+
+/// Returns `true` if the strokes of `sequence` start with the ones matching `pending`.
+fn starts_with_strokes(sequence: &KeySequence, pending: &[Stroke]) -> bool {
+    sequence.strokes().len() >= pending.len()
+        && sequence
+            .strokes()
+            .iter()
+            .zip(pending)
+            .all(|(accelerator, stroke)| stroke.matches(accelerator))
+}
+
+impl KeySequenceMatcher {
+    /// Creates a new matcher without any key sequences.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `sequence` for the menu item with `id`, replacing the sequence previously registered for it.
+    pub fn register<I: Into<MenuId>>(&mut self, id: I, sequence: KeySequence) {
+        let id = id.into();
+        self.unregister(&id);
+        self.sequences.push((id, sequence));
+    }
+
+    /// Removes the key sequence registered for the menu item with `id`.
+    pub fn unregister(&mut self, id: &MenuId) {
+        self.sequences.retain(|(i, _)| i != id);
+        self.reset();
+    }
+
+    /// Replaces all the registered key sequences, keeping the strokes pressed so far
+    /// if they are still the start of one of the new sequences.
+    pub(crate) fn set_sequences(&mut self, sequences: Vec<(MenuId, KeySequence)>) {
+        self.sequences = sequences;
+        let pending = &self.pending;
+        if !self
+            .sequences
+            .iter()
+            .any(|(_, sequence)| starts_with_strokes(sequence, pending))
+        {
+            self.reset();
+        }
+    }
+
+    /// Drops the strokes pressed so far.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Returns `true` if the matcher is waiting for the next stroke of a key sequence.
+    pub fn is_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Processes a key press and returns whether it completed, continued or broke a key sequence.
+    ///
+    /// Presses of modifier keys alone are ignored and don't break a pending sequence.
+    /// Strokes are matched by physical key, so strokes created using [`Accelerator::from_char`] never match,
+    /// use [`KeySequenceMatcher::process_key`] for them.
+    pub fn process(
+        &mut self,
+        modifiers: impl Borrow<Modifiers>,
+        key: impl Borrow<Code>,
+    ) -> KeySequenceMatch {
+        self.process_stroke(Stroke {
+            modifiers: *modifiers.borrow(),
+            code: *key.borrow(),
+            key: None,
+        })
+    }
+
+    /// Processes a key press like [`KeySequenceMatcher::process`], but also matches strokes
+    /// created using [`Accelerator::from_char`] against the logical `key` that was pressed,
+    /// see [`Accelerator::matches_key`].
+    pub fn process_key(
+        &mut self,
+        modifiers: impl Borrow<Modifiers>,
+        code: impl Borrow<Code>,
+        key: impl Borrow<Key>,
+    ) -> KeySequenceMatch {
+        self.process_stroke(Stroke {
+            modifiers: *modifiers.borrow(),
+            code: *code.borrow(),
+            key: Some(key.borrow().clone()),
+        })
+    }
+
+    fn process_stroke(&mut self, stroke: Stroke) -> KeySequenceMatch {
+        if is_modifier_key(stroke.code) {
+            return if self.is_pending() {
+                KeySequenceMatch::Pending
+            } else {
+                KeySequenceMatch::NoMatch
+            };
+        }
+
+        let result = self.advance(stroke.clone());
+        if result == KeySequenceMatch::NoMatch && self.is_pending() {
+            // the stroke broke the pending sequence, but it may start a new one
+            self.reset();
+            return self.advance(stroke);
+        }
+        result
+    }
+
+    fn advance(&mut self, stroke: Stroke) -> KeySequenceMatch {
+        self.pending.push(stroke);
+        let pending = &self.pending;
+
+        if let Some((id, _)) = self.sequences.iter().find(|(_, sequence)| {
+            sequence.strokes().len() == pending.len() && starts_with_strokes(sequence, pending)
+        }) {
+            let id = id.clone();
+            self.reset();
+            return KeySequenceMatch::Matched(id);
+        }
+
+        if self
+            .sequences
+            .iter()
+            .any(|(_, sequence)| starts_with_strokes(sequence, pending))
+        {
+            return KeySequenceMatch::Pending;
+        }
+
+        self.pending.pop();
+        KeySequenceMatch::NoMatch
+    }
+}
+
+fn is_modifier_key(key: Code) -> bool {
+    matches!(
+        key,
+        Code::ShiftLeft
+            | Code::ShiftRight
+            | Code::ControlLeft
+            | Code::ControlRight
+            | Code::AltLeft
+            | Code::AltRight
+            | Code::MetaLeft
+            | Code::MetaRight
+            | Code::Fn
+    )
+}
+
+fn parse_key_sequence(sequence: &str) -> Result<KeySequence, AcceleratorParseError> {
+    // strokes are separated by whitespace, but whitespace around `+`
+    // is part of a stroke, for example `Ctrl + K Ctrl + C`
+    let mut strokes: Vec<String> = Vec::new();
+    for part in sequence.split_whitespace() {
+        match strokes.last_mut() {
+            Some(stroke) if stroke.ends_with('+') || part.starts_with('+') => stroke.push_str(part),
+            _ => strokes.push(part.to_string()),
+        }
+    }
+
+    let mut strokes = strokes.iter().map(|stroke| parse_accelerator(stroke));
+    let first = strokes
+        .next()
+        .ok_or_else(|| AcceleratorParseError::EmptyToken(sequence.to_string()))??;
+    Ok(KeySequence::new(
+        first,
+        strokes.collect::<Result<Vec<_>, _>>()?,
+    ))
+}
+
+/// The non-modifier part of an accelerator.
+enum MainKey {
+    Code(Code),
+    Char(char),
+}
+
 fn parse_accelerator(accelerator: &str) -> Result<Accelerator, AcceleratorParseError> {
-    let tokens = accelerator.split('+').collect::<Vec<&str>>();
+    // `char:+` can't be split on `+`, so it is parsed as `char:plus`
+    let normalized = match accelerator.to_ascii_lowercase().find("char:+") {
+        Some(i) => format!(
+            "{}char:plus{}",
+            &accelerator[..i],
+            &accelerator[i + "char:+".len()..]
+        ),
+        None => accelerator.to_string(),
+    };
+    let tokens = normalized.split('+').collect::<Vec<&str>>();
 
     let mut mods = Modifiers::empty();
+    let mut cmd_or_ctrl = false;
     let mut key = None;
 
     match tokens.len() {
         // single key accelerator
         1 => {
-            key = Some(parse_key(tokens[0])?);
+            key = Some(parse_main_key(tokens[0])?);
         }
 
         // modifiers and key comobo accelerator
-        _ => {
-            for raw in tokens {
+        len => {
+            for (i, raw) in tokens.into_iter().enumerate() {
                 let token = raw.trim();
 
                 if token.is_empty() {
@@ -165,6 +831,10 @@ fn parse_accelerator(accelerator: &str) -> Result<Accelerator, AcceleratorParseE
                 }
 
                 match token.to_uppercase().as_str() {
+                    // `Super` is also the name of a key, which can only be the last token
+                    "SUPER" if i == len - 1 => {
+                        key = Some(MainKey::Code(Code::Super));
+                    }
                     "OPTION" | "ALT" => {
                         mods |= Modifiers::ALT;
                     }
@@ -180,21 +850,52 @@ fn parse_accelerator(accelerator: &str) -> Result<Accelerator, AcceleratorParseE
                     #[cfg(target_os = "macos")]
                     "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
                         mods |= Modifiers::SUPER;
+                        cmd_or_ctrl = true;
                     }
                     #[cfg(not(target_os = "macos"))]
                     "COMMANDORCONTROL" | "COMMANDORCTRL" | "CMDORCTRL" | "CMDORCONTROL" => {
                         mods |= Modifiers::CONTROL;
+                        cmd_or_ctrl = true;
                     }
                     _ => {
-                        key = Some(parse_key(token)?);
+                        key = Some(parse_main_key(token)?);
                     }
                 }
             }
         }
     }
 
-    let key = key.ok_or_else(|| AcceleratorParseError::InvalidFormat(accelerator.to_string()))?;
-    Ok(Accelerator::new(Some(mods), key))
+    let mut parsed = match key {
+        Some(MainKey::Code(key)) => Accelerator::new(Some(mods), key),
+        Some(MainKey::Char(character)) => Accelerator::from_char(Some(mods), character),
+        None => {
+            return Err(AcceleratorParseError::InvalidFormat(
+                accelerator.to_string(),
+            ))
+        }
+    };
+    parsed.cmd_or_ctrl = cmd_or_ctrl;
+    Ok(parsed)
+}
+
+fn parse_main_key(key: &str) -> Result<MainKey, AcceleratorParseError> {
+    let key = key.trim();
+    let prefix = "char:";
+    if key.len() >= prefix.len() && key.is_char_boundary(prefix.len()) {
+        let (head, character) = key.split_at(prefix.len());
+        if head.eq_ignore_ascii_case(prefix) {
+            if character.eq_ignore_ascii_case("plus") {
+                return Ok(MainKey::Char('+'));
+            }
+            let mut chars = character.chars();
+            return match (chars.next(), chars.next()) {
+                (Some(character), None) => Ok(MainKey::Char(character)),
+                _ => Err(AcceleratorParseError::UnsupportedKey(key.to_string())),
+            };
+        }
+    }
+
+    parse_key(key).map(MainKey::Code)
 }
 
 fn parse_key(key: &str) -> Result<Code, AcceleratorParseError> {
@@ -311,10 +1012,334 @@ fn parse_key(key: &str) -> Result<Code, AcceleratorParseError> {
         "F23" => Ok(F23),
         "F24" => Ok(F24),
 
+        "INTLBACKSLASH" => Ok(IntlBackslash),
+        "INTLRO" => Ok(IntlRo),
+        "INTLYEN" => Ok(IntlYen),
+        "ALTLEFT" => Ok(AltLeft),
+        "ALTRIGHT" => Ok(AltRight),
+        "CONTEXTMENU" => Ok(ContextMenu),
+        "CONTROLLEFT" => Ok(ControlLeft),
+        "CONTROLRIGHT" => Ok(ControlRight),
+        "METALEFT" => Ok(MetaLeft),
+        "METARIGHT" => Ok(MetaRight),
+        "SHIFTLEFT" => Ok(ShiftLeft),
+        "SHIFTRIGHT" => Ok(ShiftRight),
+        "CONVERT" => Ok(Convert),
+        "KANAMODE" => Ok(KanaMode),
+        "LANG1" => Ok(Lang1),
+        "LANG2" => Ok(Lang2),
+        "LANG3" => Ok(Lang3),
+        "LANG4" => Ok(Lang4),
+        "LANG5" => Ok(Lang5),
+        "NONCONVERT" => Ok(NonConvert),
+        "HELP" => Ok(Help),
+        "NUMPADBACKSPACE" => Ok(NumpadBackspace),
+        "NUMPADCLEAR" => Ok(NumpadClear),
+        "NUMPADCLEARENTRY" => Ok(NumpadClearEntry),
+        "NUMPADCOMMA" => Ok(NumpadComma),
+        "NUMPADHASH" => Ok(NumpadHash),
+        "NUMPADMEMORYADD" => Ok(NumpadMemoryAdd),
+        "NUMPADMEMORYCLEAR" => Ok(NumpadMemoryClear),
+        "NUMPADMEMORYRECALL" => Ok(NumpadMemoryRecall),
+        "NUMPADMEMORYSTORE" => Ok(NumpadMemoryStore),
+        "NUMPADMEMORYSUBTRACT" => Ok(NumpadMemorySubtract),
+        "NUMPADPARENLEFT" => Ok(NumpadParenLeft),
+        "NUMPADPARENRIGHT" => Ok(NumpadParenRight),
+        "NUMPADSTAR" => Ok(NumpadStar),
+        "FN" => Ok(Fn),
+        "FNLOCK" => Ok(FnLock),
+        "PAUSE" => Ok(Pause),
+        "BROWSERBACK" => Ok(BrowserBack),
+        "BROWSERFAVORITES" => Ok(BrowserFavorites),
+        "BROWSERFORWARD" => Ok(BrowserForward),
+        "BROWSERHOME" => Ok(BrowserHome),
+        "BROWSERREFRESH" => Ok(BrowserRefresh),
+        "BROWSERSEARCH" => Ok(BrowserSearch),
+        "BROWSERSTOP" => Ok(BrowserStop),
+        "EJECT" => Ok(Eject),
+        "LAUNCHAPP1" => Ok(LaunchApp1),
+        "LAUNCHAPP2" => Ok(LaunchApp2),
+        "LAUNCHMAIL" => Ok(LaunchMail),
+        "MEDIAPLAYPAUSE" => Ok(MediaPlayPause),
+        "MEDIASELECT" => Ok(MediaSelect),
+        "MEDIASTOP" => Ok(MediaStop),
+        "MEDIATRACKNEXT" => Ok(MediaTrackNext),
+        "MEDIATRACKPREVIOUS" => Ok(MediaTrackPrevious),
+        "POWER" => Ok(Power),
+        "SLEEP" => Ok(Sleep),
+        "WAKEUP" => Ok(WakeUp),
+        "HYPER" => Ok(Hyper),
+        "SUPER" => Ok(Super),
+        "TURBO" => Ok(Turbo),
+        "ABORT" => Ok(Abort),
+        "RESUME" => Ok(Resume),
+        "SUSPEND" => Ok(Suspend),
+        "AGAIN" => Ok(Again),
+        "COPY" => Ok(Copy),
+        "CUT" => Ok(Cut),
+        "FIND" => Ok(Find),
+        "OPEN" => Ok(Open),
+        "PASTE" => Ok(Paste),
+        "PROPS" => Ok(Props),
+        "SELECT" => Ok(Select),
+        "UNDO" => Ok(Undo),
+        "HIRAGANA" => Ok(Hiragana),
+        "KATAKANA" => Ok(Katakana),
+        "F25" => Ok(F25),
+        "F26" => Ok(F26),
+        "F27" => Ok(F27),
+        "F28" => Ok(F28),
+        "F29" => Ok(F29),
+        "F30" => Ok(F30),
+        "F31" => Ok(F31),
+        "F32" => Ok(F32),
+        "F33" => Ok(F33),
+        "F34" => Ok(F34),
+        "F35" => Ok(F35),
+        "BRIGHTNESSDOWN" => Ok(BrightnessDown),
+        "BRIGHTNESSUP" => Ok(BrightnessUp),
+        "DISPLAYTOGGLEINTEXT" => Ok(DisplayToggleIntExt),
+        "KEYBOARDLAYOUTSELECT" => Ok(KeyboardLayoutSelect),
+        "LAUNCHASSISTANT" => Ok(LaunchAssistant),
+        "LAUNCHCONTROLPANEL" => Ok(LaunchControlPanel),
+        "LAUNCHSCREENSAVER" => Ok(LaunchScreenSaver),
+        "MAILFORWARD" => Ok(MailForward),
+        "MAILREPLY" => Ok(MailReply),
+        "MAILSEND" => Ok(MailSend),
+        "MEDIAFASTFORWARD" => Ok(MediaFastForward),
+        "MEDIAPAUSE" => Ok(MediaPause),
+        "MEDIAPLAY" => Ok(MediaPlay),
+        "MEDIARECORD" => Ok(MediaRecord),
+        "MEDIAREWIND" => Ok(MediaRewind),
+        "MICROPHONEMUTETOGGLE" => Ok(MicrophoneMuteToggle),
+        "PRIVACYSCREENTOGGLE" => Ok(PrivacyScreenToggle),
+        "SELECTTASK" => Ok(SelectTask),
+        "SHOWALLWINDOWS" => Ok(ShowAllWindows),
+        "ZOOMTOGGLE" => Ok(ZoomToggle),
+
         _ => Err(AcceleratorParseError::UnsupportedKey(key.to_string())),
     }
 }
 
+/// Every [`Code`] that can be parsed, used in tests of the platform specific conversions.
+#[cfg(test)]
+pub(crate) const ALL_CODES: &[Code] = &[
+    Code::Backquote,
+    Code::Backslash,
+    Code::BracketLeft,
+    Code::BracketRight,
+    Code::Comma,
+    Code::Digit0,
+    Code::Digit1,
+    Code::Digit2,
+    Code::Digit3,
+    Code::Digit4,
+    Code::Digit5,
+    Code::Digit6,
+    Code::Digit7,
+    Code::Digit8,
+    Code::Digit9,
+    Code::Equal,
+    Code::IntlBackslash,
+    Code::IntlRo,
+    Code::IntlYen,
+    Code::KeyA,
+    Code::KeyB,
+    Code::KeyC,
+    Code::KeyD,
+    Code::KeyE,
+    Code::KeyF,
+    Code::KeyG,
+    Code::KeyH,
+    Code::KeyI,
+    Code::KeyJ,
+    Code::KeyK,
+    Code::KeyL,
+    Code::KeyM,
+    Code::KeyN,
+    Code::KeyO,
+    Code::KeyP,
+    Code::KeyQ,
+    Code::KeyR,
+    Code::KeyS,
+    Code::KeyT,
+    Code::KeyU,
+    Code::KeyV,
+    Code::KeyW,
+    Code::KeyX,
+    Code::KeyY,
+    Code::KeyZ,
+    Code::Minus,
+    Code::Period,
+    Code::Quote,
+    Code::Semicolon,
+    Code::Slash,
+    Code::AltLeft,
+    Code::AltRight,
+    Code::Backspace,
+    Code::CapsLock,
+    Code::ContextMenu,
+    Code::ControlLeft,
+    Code::ControlRight,
+    Code::Enter,
+    Code::MetaLeft,
+    Code::MetaRight,
+    Code::ShiftLeft,
+    Code::ShiftRight,
+    Code::Space,
+    Code::Tab,
+    Code::Convert,
+    Code::KanaMode,
+    Code::Lang1,
+    Code::Lang2,
+    Code::Lang3,
+    Code::Lang4,
+    Code::Lang5,
+    Code::NonConvert,
+    Code::Delete,
+    Code::End,
+    Code::Help,
+    Code::Home,
+    Code::Insert,
+    Code::PageDown,
+    Code::PageUp,
+    Code::ArrowDown,
+    Code::ArrowLeft,
+    Code::ArrowRight,
+    Code::ArrowUp,
+    Code::NumLock,
+    Code::Numpad0,
+    Code::Numpad1,
+    Code::Numpad2,
+    Code::Numpad3,
+    Code::Numpad4,
+    Code::Numpad5,
+    Code::Numpad6,
+    Code::Numpad7,
+    Code::Numpad8,
+    Code::Numpad9,
+    Code::NumpadAdd,
+    Code::NumpadBackspace,
+    Code::NumpadClear,
+    Code::NumpadClearEntry,
+    Code::NumpadComma,
+    Code::NumpadDecimal,
+    Code::NumpadDivide,
+    Code::NumpadEnter,
+    Code::NumpadEqual,
+    Code::NumpadHash,
+    Code::NumpadMemoryAdd,
+    Code::NumpadMemoryClear,
+    Code::NumpadMemoryRecall,
+    Code::NumpadMemoryStore,
+    Code::NumpadMemorySubtract,
+    Code::NumpadMultiply,
+    Code::NumpadParenLeft,
+    Code::NumpadParenRight,
+    Code::NumpadStar,
+    Code::NumpadSubtract,
+    Code::Escape,
+    Code::Fn,
+    Code::FnLock,
+    Code::PrintScreen,
+    Code::ScrollLock,
+    Code::Pause,
+    Code::BrowserBack,
+    Code::BrowserFavorites,
+    Code::BrowserForward,
+    Code::BrowserHome,
+    Code::BrowserRefresh,
+    Code::BrowserSearch,
+    Code::BrowserStop,
+    Code::Eject,
+    Code::LaunchApp1,
+    Code::LaunchApp2,
+    Code::LaunchMail,
+    Code::MediaPlayPause,
+    Code::MediaSelect,
+    Code::MediaStop,
+    Code::MediaTrackNext,
+    Code::MediaTrackPrevious,
+    Code::Power,
+    Code::Sleep,
+    Code::AudioVolumeDown,
+    Code::AudioVolumeMute,
+    Code::AudioVolumeUp,
+    Code::WakeUp,
+    Code::Hyper,
+    Code::Super,
+    Code::Turbo,
+    Code::Abort,
+    Code::Resume,
+    Code::Suspend,
+    Code::Again,
+    Code::Copy,
+    Code::Cut,
+    Code::Find,
+    Code::Open,
+    Code::Paste,
+    Code::Props,
+    Code::Select,
+    Code::Undo,
+    Code::Hiragana,
+    Code::Katakana,
+    Code::F1,
+    Code::F2,
+    Code::F3,
+    Code::F4,
+    Code::F5,
+    Code::F6,
+    Code::F7,
+    Code::F8,
+    Code::F9,
+    Code::F10,
+    Code::F11,
+    Code::F12,
+    Code::F13,
+    Code::F14,
+    Code::F15,
+    Code::F16,
+    Code::F17,
+    Code::F18,
+    Code::F19,
+    Code::F20,
+    Code::F21,
+    Code::F22,
+    Code::F23,
+    Code::F24,
+    Code::F25,
+    Code::F26,
+    Code::F27,
+    Code::F28,
+    Code::F29,
+    Code::F30,
+    Code::F31,
+    Code::F32,
+    Code::F33,
+    Code::F34,
+    Code::F35,
+    Code::BrightnessDown,
+    Code::BrightnessUp,
+    Code::DisplayToggleIntExt,
+    Code::KeyboardLayoutSelect,
+    Code::LaunchAssistant,
+    Code::LaunchControlPanel,
+    Code::LaunchScreenSaver,
+    Code::MailForward,
+    Code::MailReply,
+    Code::MailSend,
+    Code::MediaFastForward,
+    Code::MediaPause,
+    Code::MediaPlay,
+    Code::MediaRecord,
+    Code::MediaRewind,
+    Code::MicrophoneMuteToggle,
+    Code::PrivacyScreenToggle,
+    Code::SelectTask,
+    Code::ShowAllWindows,
+    Code::ZoomToggle,
+];
+
 #[test]
 fn test_parse_accelerator() {
     macro_rules! assert_parse_accelerator {
@@ -323,6 +1348,7 @@ fn test_parse_accelerator() {
             let l = $lrh;
             assert_eq!(r.mods, l.mods);
             assert_eq!(r.key, l.key);
+            assert_eq!(r.character, l.character);
         };
     }
 
@@ -331,6 +1357,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::empty(),
             key: Code::KeyX,
+            character: None,
+            cmd_or_ctrl: false,
             id: 0,
         }
     );
@@ -340,6 +1368,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::CONTROL,
             key: Code::KeyX,
+            character: None,
+            cmd_or_ctrl: false,
             id: 0,
         }
     );
@@ -349,6 +1379,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::SHIFT,
             key: Code::KeyC,
+            character: None,
+            cmd_or_ctrl: false,
             id: 0,
         }
     );
@@ -358,6 +1390,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::SHIFT,
             key: Code::KeyC,
+            character: None,
+            cmd_or_ctrl: false,
             id: 0,
         }
     );
@@ -367,6 +1401,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::SUPER | Modifiers::CONTROL | Modifiers::SHIFT | Modifiers::ALT,
             key: Code::ArrowUp,
+            character: None,
+            cmd_or_ctrl: false,
             id: 0,
         }
     );
@@ -375,6 +1411,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::empty(),
             key: Code::Digit5,
+            character: None,
+            cmd_or_ctrl: false,
             id: 0,
         }
     );
@@ -383,6 +1421,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::empty(),
             key: Code::KeyG,
+            character: None,
+            cmd_or_ctrl: false,
             id: 0,
         }
     );
@@ -392,6 +1432,8 @@ fn test_parse_accelerator() {
         Accelerator {
             mods: Modifiers::SHIFT,
             key: Code::F12,
+            character: None,
+            cmd_or_ctrl: false,
             id: 0,
         }
     );
@@ -404,6 +1446,8 @@ fn test_parse_accelerator() {
             #[cfg(not(target_os = "macos"))]
             mods: Modifiers::CONTROL,
             key: Code::Space,
+            character: None,
+            cmd_or_ctrl: false,
             id: 0,
         }
     );
@@ -427,3 +1471,264 @@ fn test_equality() {
             && h5.id() != h6.id()
     );
 }
+
+#[test]
+fn test_parse_key_sequence() {
+    let sequence = parse_key_sequence("Ctrl+KeyK Ctrl+KeyC").unwrap();
+    assert_eq!(
+        sequence.strokes(),
+        &[
+            Accelerator::new(Some(Modifiers::CONTROL), Code::KeyK),
+            Accelerator::new(Some(Modifiers::CONTROL), Code::KeyC),
+        ]
+    );
+    assert!(sequence.is_chord());
+
+    assert_eq!(
+        parse_key_sequence("Ctrl + K   Shift + Alt + C").unwrap(),
+        KeySequence::new(
+            Accelerator::new(Some(Modifiers::CONTROL), Code::KeyK),
+            [Accelerator::new(
+                Some(Modifiers::SHIFT | Modifiers::ALT),
+                Code::KeyC
+            )]
+        )
+    );
+    assert_eq!(
+        parse_key_sequence("Shift+F12").unwrap(),
+        KeySequence::from(Accelerator::new(Some(Modifiers::SHIFT), Code::F12))
+    );
+    assert!(!parse_key_sequence("Shift+F12").unwrap().is_chord());
+
+    assert!(parse_key_sequence("").is_err());
+    assert!(parse_key_sequence("Ctrl+K Ctrl+C+Shift").is_err());
+}
+
+#[test]
+fn test_key_sequence_matcher() {
+    let mut matcher = KeySequenceMatcher::new();
+    matcher.register("comment", "Ctrl+KeyK Ctrl+KeyC".parse().unwrap());
+    matcher.register("uncomment", "Ctrl+KeyK Ctrl+KeyU".parse().unwrap());
+    matcher.register("save", "Ctrl+KeyS".parse().unwrap());
+
+    assert_eq!(
+        matcher.process(Modifiers::CONTROL, Code::KeyS),
+        KeySequenceMatch::Matched("save".into())
+    );
+
+    assert_eq!(
+        matcher.process(Modifiers::CONTROL, Code::KeyK),
+        KeySequenceMatch::Pending
+    );
+    assert_eq!(
+        matcher.process(Modifiers::CONTROL, Code::ControlLeft),
+        KeySequenceMatch::Pending
+    );
+    assert_eq!(
+        matcher.process(Modifiers::CONTROL | Modifiers::NUM_LOCK, Code::KeyU),
+        KeySequenceMatch::Matched("uncomment".into())
+    );
+    assert!(!matcher.is_pending());
+
+    // a stroke that breaks a pending sequence can start or complete another one
+    assert_eq!(
+        matcher.process(Modifiers::CONTROL, Code::KeyK),
+        KeySequenceMatch::Pending
+    );
+    assert_eq!(
+        matcher.process(Modifiers::CONTROL, Code::KeyS),
+        KeySequenceMatch::Matched("save".into())
+    );
+
+    assert_eq!(
+        matcher.process(Modifiers::CONTROL, Code::KeyK),
+        KeySequenceMatch::Pending
+    );
+    assert_eq!(
+        matcher.process(Modifiers::empty(), Code::KeyC),
+        KeySequenceMatch::NoMatch
+    );
+    assert!(!matcher.is_pending());
+}
+
+#[test]
+fn test_accelerator_to_string_round_trip() {
+    for accelerator in [
+        "KeyX",
+        "CmdOrCtrl+Shift+KeyZ",
+        "super+ctrl+SHIFT+alt+ArrowUp",
+        "Alt+F12",
+        "Shift+Backquote",
+        "NumpadAdd",
+    ] {
+        let accelerator = parse_accelerator(accelerator).unwrap();
+        assert_eq!(
+            accelerator.to_string().parse::<Accelerator>().unwrap(),
+            accelerator
+        );
+    }
+    assert_eq!(
+        parse_accelerator("ctrl+alt+KeyK").unwrap().to_string(),
+        "control+alt+KeyK"
+    );
+    assert_eq!(
+        parse_accelerator("Shift+CmdOrCtrl+KeyZ")
+            .unwrap()
+            .to_string(),
+        "CmdOrCtrl+shift+KeyZ"
+    );
+    assert_eq!(
+        Accelerator::new(Some(CMD_OR_CTRL), Code::KeyZ),
+        parse_accelerator("CmdOrCtrl+KeyZ").unwrap()
+    );
+
+    let sequence = parse_key_sequence("Ctrl+KeyK Shift+Ctrl+KeyC").unwrap();
+    assert_eq!(sequence.to_string(), "control+KeyK shift+control+KeyC");
+    assert_eq!(
+        sequence.to_string().parse::<KeySequence>().unwrap(),
+        sequence
+    );
+}
+
+#[test]
+fn test_display_label() {
+    let accelerator = parse_accelerator("ctrl+shift+KeyK").unwrap();
+    assert_eq!(
+        accelerator.display_label(AcceleratorStyle::Windows),
+        "Ctrl+Shift+K"
+    );
+    assert_eq!(accelerator.display_label(AcceleratorStyle::MacOS), "⌃⇧K");
+    assert_eq!(
+        accelerator.display_label(AcceleratorStyle::Gtk),
+        "Shift+Ctrl+K"
+    );
+
+    let accelerator = parse_accelerator("super+alt+ArrowUp").unwrap();
+    assert_eq!(
+        accelerator.display_label(AcceleratorStyle::Windows),
+        "Alt+Windows+Up"
+    );
+    assert_eq!(accelerator.display_label(AcceleratorStyle::MacOS), "⌥⌘↑");
+    assert_eq!(
+        accelerator.display_label(AcceleratorStyle::Gtk),
+        "Alt+Super+Up"
+    );
+
+    assert_eq!(
+        parse_accelerator("PageDown")
+            .unwrap()
+            .display_label(AcceleratorStyle::Windows),
+        "PgDn"
+    );
+    assert_eq!(
+        parse_accelerator("F5")
+            .unwrap()
+            .display_label(AcceleratorStyle::Gtk),
+        "F5"
+    );
+    assert_eq!(
+        parse_key_sequence("Ctrl+KeyK Ctrl+KeyC")
+            .unwrap()
+            .display_label(AcceleratorStyle::MacOS),
+        "⌃K ⌃C"
+    );
+}
+
+#[test]
+fn test_char_accelerator() {
+    let accelerator = parse_accelerator("Ctrl+char:Z").unwrap();
+    assert_eq!(
+        accelerator,
+        Accelerator::from_char(Some(Modifiers::CONTROL), 'z')
+    );
+    assert_eq!(accelerator.character(), Some('z'));
+    assert_ne!(
+        accelerator.id(),
+        Accelerator::new(Some(Modifiers::CONTROL), Code::KeyZ).id()
+    );
+    assert_eq!(accelerator.to_string(), "control+char:z");
+    assert_eq!(
+        accelerator.display_label(AcceleratorStyle::Windows),
+        "Ctrl+Z"
+    );
+
+    let plus = parse_accelerator("ctrl+char:+").unwrap();
+    assert_eq!(plus.character(), Some('+'));
+    assert_eq!(plus.to_string().parse::<Accelerator>().unwrap(), plus);
+    assert_eq!(parse_accelerator("char:ü").unwrap().character(), Some('ü'));
+    assert!(parse_accelerator("ctrl+char:ab").is_err());
+
+    assert!(accelerator.matches_key(Modifiers::CONTROL, Key::Character("z".into())));
+    assert!(accelerator.matches_key(
+        Modifiers::CONTROL | Modifiers::NUM_LOCK,
+        Key::Character("Z".into())
+    ));
+    assert!(!accelerator.matches_key(
+        Modifiers::CONTROL | Modifiers::SHIFT,
+        Key::Character("Z".into())
+    ));
+    assert!(!accelerator.matches_key(Modifiers::CONTROL, Key::Character("y".into())));
+    assert!(!accelerator.matches(Modifiers::CONTROL, Code::KeyZ));
+
+    // the shift key needed to type `?` on a layout doesn't matter
+    let help = Accelerator::from_char(Some(Modifiers::CONTROL), '?');
+    assert!(help.matches_key(
+        Modifiers::CONTROL | Modifiers::SHIFT,
+        Key::Character("?".into())
+    ));
+
+    let code = Accelerator::new(Some(Modifiers::CONTROL), Code::KeyZ);
+    assert!(!code.matches_key(Modifiers::CONTROL, Key::Character("z".into())));
+
+    // on AZERTY, the key that types `z` is `KeyW`
+    let mut matcher = KeySequenceMatcher::new();
+    matcher.register("undo", accelerator.into());
+    matcher.register("comment", "Ctrl+char:k Ctrl+KeyC".parse().unwrap());
+    assert_eq!(
+        matcher.process(Modifiers::CONTROL, Code::KeyW),
+        KeySequenceMatch::NoMatch
+    );
+    assert_eq!(
+        matcher.process_key(Modifiers::CONTROL, Code::KeyW, Key::Character("z".into())),
+        KeySequenceMatch::Matched("undo".into())
+    );
+    assert_eq!(
+        matcher.process_key(Modifiers::CONTROL, Code::KeyK, Key::Character("k".into())),
+        KeySequenceMatch::Pending
+    );
+    assert_eq!(
+        matcher.process_key(Modifiers::CONTROL, Code::KeyC, Key::Character("c".into())),
+        KeySequenceMatch::Matched("comment".into())
+    );
+}
+
+#[test]
+fn test_parse_every_code() {
+    for code in ALL_CODES {
+        let accelerator = parse_accelerator(&format!("Ctrl+{code}")).unwrap();
+        assert_eq!(accelerator.key, *code);
+        assert_eq!(
+            accelerator.to_string().parse::<Accelerator>().unwrap(),
+            accelerator
+        );
+        // names are case insensitive
+        assert_eq!(parse_key(&code.to_string().to_lowercase()).unwrap(), *code);
+    }
+    assert!(parse_key("Unidentified").is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_key_sequence_serde() {
+    let sequence: KeySequence = "CmdOrCtrl+KeyK CmdOrCtrl+KeyC".parse().unwrap();
+    let json = serde_json::to_string(&sequence).unwrap();
+    assert_eq!(json, r#""CmdOrCtrl+KeyK CmdOrCtrl+KeyC""#);
+    assert_eq!(
+        serde_json::from_str::<KeySequence>(&json).unwrap(),
+        sequence
+    );
+
+    // key sequences always have at least one stroke
+    assert!(serde_json::from_str::<KeySequence>("\"\"").is_err());
+    assert!(serde_json::from_str::<KeySequence>(r#"{"strokes":[]}"#).is_err());
+}
@@ -9,6 +9,7 @@ use crate::{accelerator::Accelerator, CheckMenuItem, MenuId};
 pub struct CheckMenuItemBuilder {
     text: String,
     enabled: bool,
+    tooltip: Option<String>,
     checked: bool,
     acccelerator: Option<Accelerator>,
     id: Option<MenuId>,
@@ -39,6 +40,14 @@ impl CheckMenuItemBuilder {
         self
     }
 
+    /// Set the tooltip of this check menu item.
+    ///
+    /// See [`CheckMenuItem::set_tooltip`] for more info.
+    pub fn tooltip<S: Into<String>>(mut self, tooltip: S) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
     /// Check or uncheck this menu item.
     pub fn checked(mut self, checked: bool) -> Self {
         self.checked = checked;
@@ -59,10 +68,12 @@ impl CheckMenuItemBuilder {
 
     /// Build this check menu item.
     pub fn build(self) -> CheckMenuItem {
-        if let Some(id) = self.id {
+        let item = if let Some(id) = self.id {
             CheckMenuItem::with_id(id, self.text, self.enabled, self.checked, self.acccelerator)
         } else {
             CheckMenuItem::new(self.text, self.enabled, self.checked, self.acccelerator)
-        }
+        };
+        item.set_tooltip(self.tooltip.as_deref());
+        item
     }
 }
@@ -13,6 +13,7 @@ use crate::{
 pub struct IconMenuItemBuilder {
     text: String,
     enabled: bool,
+    tooltip: Option<String>,
     id: Option<MenuId>,
     acccelerator: Option<Accelerator>,
     icon: Option<Icon>,
@@ -44,6 +45,14 @@ impl IconMenuItemBuilder {
         self
     }
 
+    /// Set the tooltip of this icon menu item.
+    ///
+    /// See [`IconMenuItem::set_tooltip`] for more info.
+    pub fn tooltip<S: Into<String>>(mut self, tooltip: S) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
     /// Set this icon menu item icon.
     pub fn icon(mut self, icon: Option<Icon>) -> Self {
         self.icon = icon;
@@ -72,7 +81,7 @@ impl IconMenuItemBuilder {
 
     /// Build this icon menu item.
     pub fn build(self) -> IconMenuItem {
-        if let Some(id) = self.id {
+        let item = if let Some(id) = self.id {
             if self.icon.is_some() {
                 IconMenuItem::with_id(id, self.text, self.enabled, self.icon, self.acccelerator)
             } else {
@@ -93,6 +102,8 @@ impl IconMenuItemBuilder {
                 self.native_icon,
                 self.acccelerator,
             )
-        }
+        };
+        item.set_tooltip(self.tooltip.as_deref());
+        item
     }
 }
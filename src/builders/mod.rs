@@ -7,10 +7,12 @@
 mod check;
 mod icon;
 mod normal;
+mod radio;
 mod submenu;
 
 pub use crate::about_metadata::AboutMetadataBuilder;
 pub use check::*;
 pub use icon::*;
 pub use normal::*;
+pub use radio::*;
 pub use submenu::*;
@@ -9,6 +9,7 @@ use crate::{accelerator::Accelerator, MenuId, MenuItem};
 pub struct MenuItemBuilder {
     text: String,
     enabled: bool,
+    tooltip: Option<String>,
     id: Option<MenuId>,
     acccelerator: Option<Accelerator>,
 }
@@ -38,6 +39,14 @@ impl MenuItemBuilder {
         self
     }
 
+    /// Set the tooltip of this menu item.
+    ///
+    /// See [`MenuItem::set_tooltip`] for more info.
+    pub fn tooltip<S: Into<String>>(mut self, tooltip: S) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
     /// Set this menu item accelerator.
     pub fn acccelerator<A: TryInto<Accelerator>>(
         mut self,
@@ -52,10 +61,12 @@ impl MenuItemBuilder {
 
     /// Build this menu item.
     pub fn build(self) -> MenuItem {
-        if let Some(id) = self.id {
+        let item = if let Some(id) = self.id {
             MenuItem::with_id(id, self.text, self.enabled, self.acccelerator)
         } else {
             MenuItem::new(self.text, self.enabled, self.acccelerator)
-        }
+        };
+        item.set_tooltip(self.tooltip.as_deref());
+        item
     }
 }
@@ -0,0 +1,99 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{accelerator::Accelerator, MenuId, RadioGroup, RadioMenuItem};
+
+/// A builder type for [`RadioMenuItem`]
+#[derive(Clone, Debug, Default)]
+pub struct RadioMenuItemBuilder {
+    text: String,
+    group: RadioGroup,
+    enabled: bool,
+    tooltip: Option<String>,
+    checked: bool,
+    acccelerator: Option<Accelerator>,
+    id: Option<MenuId>,
+}
+
+impl RadioMenuItemBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the id this radio menu item.
+    pub fn id(mut self, id: MenuId) -> Self {
+        self.id.replace(id);
+        self
+    }
+
+    /// Set the text for this radio menu item.
+    ///
+    /// See [`RadioMenuItem::set_text`] for more info.
+    pub fn text<S: Into<String>>(mut self, text: S) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Set the [`RadioGroup`] this radio menu item belongs to.
+    pub fn group(mut self, group: &RadioGroup) -> Self {
+        self.group = group.clone();
+        self
+    }
+
+    /// Enable or disable this menu item.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set the tooltip of this radio menu item.
+    ///
+    /// See [`RadioMenuItem::set_tooltip`] for more info.
+    pub fn tooltip<S: Into<String>>(mut self, tooltip: S) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Check or uncheck this menu item.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// Set this radio menu item accelerator.
+    pub fn acccelerator<A: TryInto<Accelerator>>(
+        mut self,
+        acccelerator: Option<A>,
+    ) -> crate::Result<Self>
+    where
+        crate::Error: From<<A as TryInto<Accelerator>>::Error>,
+    {
+        self.acccelerator = acccelerator.map(|a| a.try_into()).transpose()?;
+        Ok(self)
+    }
+
+    /// Build this radio menu item.
+    pub fn build(self) -> RadioMenuItem {
+        let item = if let Some(id) = self.id {
+            RadioMenuItem::with_id(
+                id,
+                self.text,
+                &self.group,
+                self.enabled,
+                self.checked,
+                self.acccelerator,
+            )
+        } else {
+            RadioMenuItem::new(
+                self.text,
+                &self.group,
+                self.enabled,
+                self.checked,
+                self.acccelerator,
+            )
+        };
+        item.set_tooltip(self.tooltip.as_deref());
+        item
+    }
+}
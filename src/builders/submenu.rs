@@ -9,6 +9,7 @@ use crate::{IsMenuItem, MenuId, Submenu};
 pub struct SubmenuBuilder<'a> {
     text: String,
     enabled: bool,
+    tooltip: Option<String>,
     id: Option<MenuId>,
     items: Vec<&'a dyn IsMenuItem>,
 }
@@ -18,6 +19,7 @@ impl std::fmt::Debug for SubmenuBuilder<'_> {
         f.debug_struct("SubmenuBuilder")
             .field("text", &self.text)
             .field("enabled", &self.enabled)
+            .field("tooltip", &self.tooltip)
             .finish()
     }
 }
@@ -47,6 +49,14 @@ impl<'a> SubmenuBuilder<'a> {
         self
     }
 
+    /// Set the tooltip of this submenu.
+    ///
+    /// See [`Submenu::set_tooltip`] for more info.
+    pub fn tooltip<S: Into<String>>(mut self, tooltip: S) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
     /// Add an item to this submenu.
     pub fn item(mut self, item: &'a dyn IsMenuItem) -> Self {
         self.items.push(item);
@@ -61,10 +71,12 @@ impl<'a> SubmenuBuilder<'a> {
 
     /// Build this menu item.
     pub fn build(self) -> crate::Result<Submenu> {
-        if let Some(id) = self.id {
+        let submenu = if let Some(id) = self.id {
             Submenu::with_id_and_items(id, self.text, self.enabled, &self.items)
         } else {
             Submenu::with_items(self.text, self.enabled, &self.items)
-        }
+        }?;
+        submenu.set_tooltip(self.tooltip.as_deref());
+        Ok(submenu)
     }
 }
@@ -0,0 +1,312 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::borrow::Borrow;
+
+use crate::{
+    accelerator::{Code, Key, KeySequence, KeySequenceMatch, KeySequenceMatcher, Modifiers},
+    Menu, MenuEvent, MenuEventKind, MenuEventSource, MenuId, MenuItemKind,
+};
+
+/// Triggers the accelerators of a [`Menu`] from key events, independently of the platform menus.
+///
+/// This is useful for windows that don't have a native menu bar attached, for example a
+/// custom-drawn window on Linux, where the accelerators of the menu never fire on their own.
+/// Feed the key presses of your window to [`AcceleratorDispatcher::handle_key`], or to
+/// [`AcceleratorDispatcher::handle_key_event`] to also trigger character based accelerators
+/// created using [`Accelerator::from_char`](crate::accelerator::Accelerator::from_char),
+/// and it emits the same [`MenuEvent`]s a native menu would, with [`MenuEventSource::Accelerator`].
+///
+/// The accelerators are read from the menu on every key press, so changes to the menu
+/// are picked up right away. Multi-stroke [`KeySequence`]s are supported as well.
+///
+/// ```no_run
+/// # use muda::{AcceleratorDispatcher, Menu, accelerator::{Code, Modifiers}};
+/// # let menu = Menu::new();
+/// let mut dispatcher = AcceleratorDispatcher::new(&menu);
+/// // in your window event loop
+/// if let Some(id) = dispatcher.handle_key(Modifiers::CONTROL, Code::KeyS) {
+///     println!("triggered {id:?}");
+/// }
+/// ```
+pub struct AcceleratorDispatcher {
+    menu: Menu,
+    matcher: KeySequenceMatcher,
+}
+
+impl AcceleratorDispatcher {
+    /// Creates a new dispatcher for the accelerators of `menu`.
+    pub fn new(menu: &Menu) -> Self {
+        Self {
+            menu: menu.clone(),
+            matcher: KeySequenceMatcher::new(),
+        }
+    }
+
+    /// Handles a key press and returns the id of the menu item whose accelerator it triggered,
+    /// which is `Some` only if a [`MenuEvent`] was emitted.
+    ///
+    /// Disabled or hidden items and items inside disabled or hidden submenus are skipped. Triggering a check menu item
+    /// toggles it and triggering a radio menu item checks it, like activating them from the menu would,
+    /// so triggering a radio menu item that is already checked doesn't emit an event and returns `None`.
+    /// Predefined menu items are not triggered since their action is performed by the platform.
+    ///
+    /// Accelerators are matched by physical key, so character based accelerators never trigger,
+    /// use [`AcceleratorDispatcher::handle_key_event`] for them.
+    pub fn handle_key(
+        &mut self,
+        modifiers: impl Borrow<Modifiers>,
+        code: impl Borrow<Code>,
+    ) -> Option<MenuId> {
+        self.handle(|matcher| matcher.process(modifiers, code))
+    }
+
+    /// Handles a key press like [`AcceleratorDispatcher::handle_key`], but also triggers character based accelerators
+    /// by matching them against the logical `key` that was pressed, see [`KeySequenceMatcher::process_key`].
+    pub fn handle_key_event(
+        &mut self,
+        modifiers: impl Borrow<Modifiers>,
+        code: impl Borrow<Code>,
+        key: impl Borrow<Key>,
+    ) -> Option<MenuId> {
+        self.handle(|matcher| matcher.process_key(modifiers, code, key))
+    }
+
+    fn handle(
+        &mut self,
+        process: impl FnOnce(&mut KeySequenceMatcher) -> KeySequenceMatch,
+    ) -> Option<MenuId> {
+        dispatch(
+            self.menu.id(),
+            self.menu.items(),
+            &mut self.matcher,
+            process,
+        )
+    }
+
+    /// Returns `true` if the last key presses are the start of a multi-stroke key sequence.
+    pub fn is_pending(&self) -> bool {
+        self.matcher.is_pending()
+    }
+
+    /// Drops the strokes of a pending multi-stroke key sequence,
+    /// for example after a timeout or when the window loses focus.
+    pub fn reset(&mut self) {
+        self.matcher.reset()
+    }
+}
+
+/// Feeds a key press to `matcher` using the key sequences of the enabled and visible items in `items`,
+/// and activates the item whose key sequence it completed, see [`AcceleratorDispatcher::handle_key`].
+pub(crate) fn dispatch(
+    root: &MenuId,
+    items: Vec<MenuItemKind>,
+    matcher: &mut KeySequenceMatcher,
+    process: impl FnOnce(&mut KeySequenceMatcher) -> KeySequenceMatch,
+) -> Option<MenuId> {
+    let mut enabled = Vec::new();
+    enabled_items(items, &mut enabled);
+
+    matcher.set_sequences(
+        enabled
+            .iter()
+            .map(|(item, sequence)| (item.id().clone(), sequence.clone()))
+            .collect(),
+    );
+
+    let id = match process(matcher) {
+        KeySequenceMatch::Matched(id) => id,
+        _ => return None,
+    };
+
+    let (item, _) = enabled.into_iter().find(|(item, _)| *item.id() == id)?;
+    if activate(&item, root, MenuEventSource::Accelerator) {
+        Some(id)
+    } else {
+        None
+    }
+}
+
+/// Activates `item` of the menu `root` like the native menus do, and returns whether
+/// a [`MenuEvent`] from `source` was emitted for it.
+pub(crate) fn activate(item: &MenuItemKind, root: &MenuId, source: MenuEventSource) -> bool {
+    let kind = match item {
+        MenuItemKind::Check(i) => {
+            let checked = !i.is_checked();
+            i.set_checked(checked);
+            MenuEventKind::Toggled { checked }
+        }
+        MenuItemKind::Radio(i) => {
+            // only report the item that became the selected item of its group
+            if i.is_checked() {
+                return false;
+            }
+            i.set_checked(true);
+            MenuEventKind::Toggled { checked: true }
+        }
+        _ => MenuEventKind::Activated,
+    };
+
+    MenuEvent::send(MenuEvent {
+        id: item.id().clone(),
+        kind,
+        source,
+        root: Some(root.clone()),
+    });
+    true
+}
+
+/// Collects the enabled and visible items in `items` and their enabled and visible submenus
+/// which have an accelerator or a key sequence.
+fn enabled_items(items: Vec<MenuItemKind>, out: &mut Vec<(MenuItemKind, KeySequence)>) {
+    for item in items {
+        let (enabled, sequence) = match &item {
+            MenuItemKind::Submenu(i) => {
+                if i.is_enabled() && i.is_visible() {
+                    enabled_items(i.items(), out);
+                }
+                continue;
+            }
+            MenuItemKind::Predefined(_) => continue,
+            MenuItemKind::MenuItem(i) => (i.is_enabled() && i.is_visible(), i.key_sequence()),
+            MenuItemKind::Check(i) => (i.is_enabled() && i.is_visible(), i.key_sequence()),
+            MenuItemKind::Icon(i) => (i.is_enabled() && i.is_visible(), i.key_sequence()),
+            MenuItemKind::Radio(i) => (i.is_enabled() && i.is_visible(), i.key_sequence()),
+        };
+
+        if let (true, Some(sequence)) = (enabled, sequence) {
+            out.push((item, sequence));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        accelerator::{Accelerator, Code, Key, Modifiers},
+        AcceleratorDispatcher, CheckMenuItem, Menu, MenuEventKind, MenuEventSource, MenuId,
+        MenuItem, RadioGroup, RadioMenuItem, Submenu,
+    };
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn dispatch_accelerators() {
+        let save = MenuItem::with_id("save", "Save", true, Some("Ctrl+KeyS".parse().unwrap()));
+        let delete = MenuItem::with_id(
+            "delete",
+            "Delete",
+            false,
+            Some("Ctrl+KeyD".parse().unwrap()),
+        );
+        let wrap = CheckMenuItem::with_id(
+            "wrap",
+            "Wrap",
+            true,
+            false,
+            Some("Ctrl+KeyW".parse().unwrap()),
+        );
+        let comment = MenuItem::with_id("comment", "Comment", true, None);
+        comment
+            .set_key_sequence(Some("Ctrl+KeyK Ctrl+KeyC".parse().unwrap()))
+            .unwrap();
+        let export =
+            MenuItem::with_id("export", "Export", true, Some("Ctrl+KeyE".parse().unwrap()));
+        let disabled = Submenu::with_items("Disabled", false, &[&export]).unwrap();
+        let file = Submenu::with_items("File", true, &[&save, &delete, &wrap, &comment, &disabled])
+            .unwrap();
+        let menu = Menu::with_id_and_items("dispatcher-test", &[&file]).unwrap();
+        let events = menu.events();
+
+        let mut dispatcher = AcceleratorDispatcher::new(&menu);
+        let ctrl = Modifiers::CONTROL;
+
+        assert_eq!(
+            dispatcher.handle_key(ctrl, Code::KeyS),
+            Some(MenuId::new("save"))
+        );
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.id, "save");
+        assert_eq!(event.kind, MenuEventKind::Activated);
+        assert_eq!(event.source, MenuEventSource::Accelerator);
+        assert_eq!(event.root, Some(MenuId::new("dispatcher-test")));
+
+        // disabled items and items in disabled submenus are skipped
+        assert_eq!(dispatcher.handle_key(ctrl, Code::KeyD), None);
+        assert_eq!(dispatcher.handle_key(ctrl, Code::KeyE), None);
+        assert_eq!(dispatcher.handle_key(Modifiers::ALT, Code::KeyS), None);
+        assert!(events.try_recv().is_err());
+
+        assert_eq!(
+            dispatcher.handle_key(ctrl, Code::KeyW),
+            Some(MenuId::new("wrap"))
+        );
+        assert!(wrap.is_checked());
+        assert_eq!(
+            events.try_recv().unwrap().kind,
+            MenuEventKind::Toggled { checked: true }
+        );
+
+        assert_eq!(dispatcher.handle_key(ctrl, Code::KeyK), None);
+        assert!(dispatcher.is_pending());
+        assert_eq!(
+            dispatcher.handle_key(ctrl, Code::KeyC),
+            Some(MenuId::new("comment"))
+        );
+        assert_eq!(events.try_recv().unwrap().id, "comment");
+
+        // changes to the menu are picked up right away
+        delete.set_enabled(true);
+        assert_eq!(
+            dispatcher.handle_key(ctrl, Code::KeyD),
+            Some(MenuId::new("delete"))
+        );
+        events.try_recv().unwrap();
+
+        // hidden items are skipped
+        save.set_visible(false);
+        assert_eq!(dispatcher.handle_key(ctrl, Code::KeyS), None);
+        save.set_visible(true);
+        file.set_visible(false);
+        assert_eq!(dispatcher.handle_key(ctrl, Code::KeyS), None);
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn dispatch_character_accelerators_and_radio_items() {
+        let undo = MenuItem::with_id(
+            "undo",
+            "Undo",
+            true,
+            Some(Accelerator::from_char(Some(Modifiers::CONTROL), 'z')),
+        );
+        let group = RadioGroup::new();
+        let small = RadioMenuItem::with_id(
+            "small",
+            "Small",
+            &group,
+            true,
+            true,
+            Some("Ctrl+Digit1".parse().unwrap()),
+        );
+        let menu = Menu::with_id_and_items("dispatcher-key-test", &[&undo, &small]).unwrap();
+        let events = menu.events();
+
+        let mut dispatcher = AcceleratorDispatcher::new(&menu);
+        let ctrl = Modifiers::CONTROL;
+
+        // on AZERTY, the key that types `z` is `KeyW`
+        assert_eq!(dispatcher.handle_key(ctrl, Code::KeyW), None);
+        assert_eq!(
+            dispatcher.handle_key_event(ctrl, Code::KeyW, Key::Character("z".into())),
+            Some(MenuId::new("undo"))
+        );
+        assert_eq!(events.try_recv().unwrap().id, "undo");
+
+        // the radio item is already checked, so no event is emitted
+        assert_eq!(dispatcher.handle_key(ctrl, Code::Digit1), None);
+        assert!(small.is_checked());
+        assert!(events.try_recv().is_err());
+    }
+}
@@ -5,6 +5,7 @@
 use thiserror::Error;
 
 pub use crate::accelerator::AcceleratorParseError;
+use crate::MenuId;
 
 /// Errors returned by muda.
 #[non_exhaustive]
@@ -12,20 +13,33 @@ pub use crate::accelerator::AcceleratorParseError;
 pub enum Error {
     #[error("This menu item is not a child of this `Menu` or `Submenu`")]
     NotAChildOfThisMenu,
-    #[cfg(windows)]
+    #[cfg(all(windows, not(feature = "headless")))]
     #[error("This menu has not been initialized for this hwnd`")]
     NotInitialized,
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "headless")))]
     #[error("This menu has not been initialized for this gtk window`")]
     NotInitialized,
-    #[cfg(windows)]
+    #[cfg(feature = "headless")]
+    #[error("This menu has not been initialized for this window`")]
+    NotInitialized,
+    #[cfg(all(windows, not(feature = "headless")))]
     #[error("This menu has already been initialized for this hwnd`")]
     AlreadyInitialized,
-    #[cfg(target_os = "linux")]
+    #[cfg(all(target_os = "linux", not(feature = "headless")))]
     #[error("This menu has already been initialized for this gtk window`")]
     AlreadyInitialized,
+    #[cfg(feature = "headless")]
+    #[error("This menu has already been initialized for this window`")]
+    AlreadyInitialized,
     #[error(transparent)]
     AcceleratorParseError(#[from] AcceleratorParseError),
+    #[error("A menu item with id `{}` already exists in this menu", .0 .0)]
+    DuplicateId(MenuId),
+    #[error("This accelerator or key sequence conflicts with the one of the menu item with id `{}`", .0 .0)]
+    AcceleratorConflict(MenuId),
+    #[cfg(all(target_os = "linux", feature = "dbusmenu"))]
+    #[error("Failed to export the menu over D-Bus: {0}")]
+    DBus(#[from] gtk::glib::Error),
 }
 
 /// Convenient type alias of Result type for muda.
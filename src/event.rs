@@ -0,0 +1,576 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use once_cell::sync::Lazy;
+
+use crate::{util::Counter, MenuId};
+
+/// Describes a menu event emitted when a menu item is activated
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MenuEvent {
+    /// Id of the menu item which triggered this event
+    pub id: MenuId,
+    /// What happened to the menu item.
+    pub kind: MenuEventKind,
+    /// How this event was triggered.
+    pub source: MenuEventSource,
+    /// Id of the root [`Menu`] or context menu this event came from, if it is known.
+    ///
+    /// [`Menu`]: crate::Menu
+    pub root: Option<MenuId>,
+}
+
+/// Describes what happened to the menu item of a [`MenuEvent`].
+///
+/// [`MenuEventKind::SubmenuOpened`], [`MenuEventKind::SubmenuClosed`] and [`MenuEventKind::Highlighted`]
+/// are navigation events, they are only delivered to [`MenuEvent::navigation_receiver`]
+/// and the handler set with [`MenuEvent::set_navigation_event_handler`], see [`MenuEventKind::is_navigation`].
+///
+/// ## Platform-specific:
+///
+/// - **Windows:** [`MenuEventKind::SubmenuOpened`], [`MenuEventKind::SubmenuClosed`] and
+///   [`MenuEventKind::Highlighted`] events are always reported with [`MenuEventSource::Mouse`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MenuEventKind {
+    /// The menu item was activated.
+    Activated,
+    /// A check or radio menu item was toggled, `checked` is its new state.
+    Toggled { checked: bool },
+    /// The submenu is about to be shown.
+    SubmenuOpened,
+    /// The submenu was hidden.
+    SubmenuClosed,
+    /// The menu item was highlighted, for example when hovered with the mouse.
+    Highlighted,
+}
+
+impl MenuEventKind {
+    /// Returns whether this is a navigation event, where the user moves through an open menu
+    /// without activating an item.
+    pub fn is_navigation(&self) -> bool {
+        matches!(
+            self,
+            MenuEventKind::SubmenuOpened
+                | MenuEventKind::SubmenuClosed
+                | MenuEventKind::Highlighted
+        )
+    }
+}
+
+/// Describes how a [`MenuEvent`] was triggered.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MenuEventSource {
+    /// A mouse click or a touch.
+    Mouse,
+    /// The keyboard accelerator of the menu item.
+    Accelerator,
+    /// The mnemonic of the menu item while its menu is open.
+    Mnemonic,
+    /// Keyboard navigation inside an open menu, like pressing `Enter` on the highlighted item.
+    Keyboard,
+    /// The event was triggered from code and not by the user.
+    Programmatic,
+}
+
+/// Describes which [`MenuEvent`]s a handler added with [`MenuEvent::add_event_handler`] receives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuEventFilter {
+    /// Receive all menu events.
+    All,
+    /// Receive events of menu items whose id starts with this prefix.
+    IdPrefix(String),
+    /// Receive events that came from the root [`Menu`] or context menu with this id.
+    ///
+    /// [`Menu`]: crate::Menu
+    Root(MenuId),
+}
+
+impl MenuEventFilter {
+    /// Returns whether `event` passes this filter.
+    pub fn matches(&self, event: &MenuEvent) -> bool {
+        match self {
+            MenuEventFilter::All => true,
+            MenuEventFilter::IdPrefix(prefix) => event.id.0.starts_with(prefix.as_str()),
+            MenuEventFilter::Root(root) => event.root.as_ref() == Some(root),
+        }
+    }
+}
+
+/// A guard returned by [`MenuEvent::add_event_handler`],
+/// the handler is removed when this guard is dropped.
+#[must_use = "the event handler is removed when the guard is dropped"]
+#[derive(Debug)]
+pub struct MenuEventHandlerGuard {
+    id: u32,
+}
+
+impl Drop for MenuEventHandlerGuard {
+    fn drop(&mut self) {
+        MENU_EVENT_HANDLERS
+            .lock()
+            .unwrap()
+            .retain(|(id, _, _)| *id != self.id);
+    }
+}
+
+/// A reciever that could be used to listen to menu events.
+pub type MenuEventReceiver = Receiver<MenuEvent>;
+/// A sender that could be used to receive the menu events of a single menu,
+/// see [`Menu::set_event_sender`](crate::Menu::set_event_sender).
+pub type MenuEventSender = Sender<MenuEvent>;
+type MenuEventHandler = Arc<dyn Fn(MenuEvent) + Send + Sync + 'static>;
+
+static COUNTER: Counter = Counter::new();
+static MENU_CHANNEL: Lazy<(Sender<MenuEvent>, MenuEventReceiver)> = Lazy::new(unbounded);
+static MENU_EVENT_HANDLER: Lazy<Mutex<Option<MenuEventHandler>>> = Lazy::new(Default::default);
+static MENU_EVENT_HANDLERS: Lazy<Mutex<Vec<(u32, MenuEventFilter, MenuEventHandler)>>> =
+    Lazy::new(Default::default);
+static MENU_NAVIGATION_CHANNEL: Lazy<(Sender<MenuEvent>, MenuEventReceiver)> = Lazy::new(unbounded);
+static MENU_NAVIGATION_CHANNEL_USED: AtomicBool = AtomicBool::new(false);
+static MENU_NAVIGATION_HANDLER: Lazy<Mutex<Option<MenuEventHandler>>> = Lazy::new(Default::default);
+static MENU_EVENT_SENDERS: Lazy<Mutex<HashMap<MenuId, (usize, MenuEventSender)>>> =
+    Lazy::new(Default::default);
+#[cfg(feature = "futures")]
+static MENU_EVENT_STREAMS: Lazy<Mutex<Vec<futures_channel::mpsc::UnboundedSender<MenuEvent>>>> =
+    Lazy::new(Default::default);
+
+/// A [`Stream`](futures_core::Stream) of menu events, see [`MenuEvent::stream`].
+#[cfg(feature = "futures")]
+#[derive(Debug)]
+pub struct MenuEventStream(futures_channel::mpsc::UnboundedReceiver<MenuEvent>);
+
+#[cfg(feature = "futures")]
+impl futures_core::Stream for MenuEventStream {
+    type Item = MenuEvent;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        std::pin::Pin::new(&mut self.0).poll_next(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl MenuEvent {
+    /// Returns the id of the menu item which triggered this event
+    pub fn id(&self) -> &MenuId {
+        &self.id
+    }
+
+    /// Returns what happened to the menu item.
+    pub fn kind(&self) -> MenuEventKind {
+        self.kind
+    }
+
+    /// Returns how this event was triggered.
+    pub fn source(&self) -> MenuEventSource {
+        self.source
+    }
+
+    /// Returns the id of the root menu this event came from, if it is known.
+    pub fn root(&self) -> Option<&MenuId> {
+        self.root.as_ref()
+    }
+
+    /// Gets a reference to the event channel's [`MenuEventReceiver`]
+    /// which can be used to listen for menu events.
+    ///
+    /// ## Note
+    ///
+    /// This will not receive any events if [`MenuEvent::set_event_handler`] has been called with a `Some` value
+    /// and will not receive the events claimed by the handlers added with [`MenuEvent::add_event_handler`]
+    /// or sent to the event sender of their root menu.
+    pub fn receiver<'a>() -> &'a MenuEventReceiver {
+        &MENU_CHANNEL.1
+    }
+
+    /// Gets a reference to the channel receiving the navigation events, see [`MenuEventKind::is_navigation`],
+    /// which can be used to fill a submenu right before it is shown or to show hints for highlighted items.
+    ///
+    /// Navigation events are only sent to this channel after this function was called once,
+    /// and never to [`MenuEvent::receiver`], the event handlers, the event senders of menus or the streams.
+    ///
+    /// ## Note
+    ///
+    /// This will not receive any events if [`MenuEvent::set_navigation_event_handler`] has been called with a `Some` value.
+    pub fn navigation_receiver<'a>() -> &'a MenuEventReceiver {
+        MENU_NAVIGATION_CHANNEL_USED.store(true, Ordering::Relaxed);
+        &MENU_NAVIGATION_CHANNEL.1
+    }
+
+    /// Set a handler to be called for new navigation events, see [`MenuEventKind::is_navigation`].
+    ///
+    /// Calling this function with a `Some` value will not send new navigation events to the channel
+    /// associated with [`MenuEvent::navigation_receiver`].
+    /// Calling it again replaces the previous handler and calling it with `None` removes it.
+    pub fn set_navigation_event_handler<F: Fn(MenuEvent) + Send + Sync + 'static>(f: Option<F>) {
+        *MENU_NAVIGATION_HANDLER.lock().unwrap() = f.map(|f| Arc::new(f) as MenuEventHandler);
+    }
+
+    /// Returns a new [`Stream`](futures_core::Stream) of all the menu events emitted after this call,
+    /// except the navigation events which are only sent to [`MenuEvent::navigation_receiver`].
+    ///
+    /// Streams receive every event in addition to, and independently of, [`MenuEvent::receiver`],
+    /// the event handlers and the event senders of menus,
+    /// and wake the task polling them as soon as an event is emitted.
+    #[cfg(feature = "futures")]
+    pub fn stream() -> MenuEventStream {
+        let (sender, receiver) = futures_channel::mpsc::unbounded();
+        MENU_EVENT_STREAMS.lock().unwrap().push(sender);
+        MenuEventStream(receiver)
+    }
+
+    /// Set a handler to be called for new events. Useful for implementing custom event sender.
+    ///
+    /// ## Note
+    ///
+    /// Calling this function with a `Some` value,
+    /// will not send new events to the channel associated with [`MenuEvent::receiver`].
+    /// Calling it again replaces the previous handler and calling it with `None` removes it.
+    ///
+    /// This handler only receives the events that are not claimed by
+    /// the handlers added with [`MenuEvent::add_event_handler`].
+    pub fn set_event_handler<F: Fn(MenuEvent) + Send + Sync + 'static>(f: Option<F>) {
+        *MENU_EVENT_HANDLER.lock().unwrap() = f.map(|f| Arc::new(f) as MenuEventHandler);
+    }
+
+    /// Add a handler to be called for new events that pass `filter`.
+    ///
+    /// The handler is removed when the returned [`MenuEventHandlerGuard`] is dropped.
+    ///
+    /// An event that passes the filter of at least one handler is claimed by these handlers
+    /// and will not be sent to the handler set with [`MenuEvent::set_event_handler`]
+    /// or to the channel associated with [`MenuEvent::receiver`].
+    pub fn add_event_handler<F: Fn(MenuEvent) + Send + Sync + 'static>(
+        filter: MenuEventFilter,
+        f: F,
+    ) -> MenuEventHandlerGuard {
+        let id = COUNTER.next();
+        MENU_EVENT_HANDLERS
+            .lock()
+            .unwrap()
+            .push((id, filter, Arc::new(f)));
+        MenuEventHandlerGuard { id }
+    }
+
+    /// Set or remove the sender that receives all the events coming from the root menu with `root` id.
+    ///
+    /// `owner` is the address of the menu setting the sender, which removes it
+    /// with [`MenuEvent::remove_root_senders`] once it is dropped.
+    pub(crate) fn set_root_sender(root: &MenuId, owner: usize, sender: Option<MenuEventSender>) {
+        let mut senders = MENU_EVENT_SENDERS.lock().unwrap();
+        match sender {
+            Some(sender) => {
+                senders.insert(root.clone(), (owner, sender));
+            }
+            None => {
+                if senders.get(root).map(|(o, _)| *o == owner).unwrap_or(false) {
+                    senders.remove(root);
+                }
+            }
+        }
+    }
+
+    /// Remove the senders set by the menu at the address `owner`, called when that menu is dropped
+    /// so a later menu with the same id doesn't inherit them.
+    pub(crate) fn remove_root_senders(owner: usize) {
+        MENU_EVENT_SENDERS
+            .lock()
+            .unwrap()
+            .retain(|_, (o, _)| *o != owner);
+    }
+
+    pub(crate) fn send(event: MenuEvent) {
+        if event.kind.is_navigation() {
+            Self::send_navigation(event);
+            return;
+        }
+
+        #[cfg(feature = "futures")]
+        MENU_EVENT_STREAMS
+            .lock()
+            .unwrap()
+            .retain(|stream| stream.unbounded_send(event.clone()).is_ok());
+
+        let event = match Self::send_to_root_sender(event) {
+            Some(event) => event,
+            None => return,
+        };
+
+        // clone the handlers out of the registry so they can add or remove handlers themselves
+        let handlers = MENU_EVENT_HANDLERS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, filter, _)| filter.matches(&event))
+            .map(|(_, _, handler)| handler.clone())
+            .collect::<Vec<_>>();
+
+        if !handlers.is_empty() {
+            for handler in handlers {
+                handler(event.clone());
+            }
+            return;
+        }
+
+        let handler = MENU_EVENT_HANDLER.lock().unwrap().clone();
+        if let Some(handler) = handler {
+            handler(event);
+        } else {
+            let _ = MENU_CHANNEL.0.send(event);
+        }
+    }
+
+    fn send_navigation(event: MenuEvent) {
+        let handler = MENU_NAVIGATION_HANDLER.lock().unwrap().clone();
+        if let Some(handler) = handler {
+            handler(event);
+        } else if MENU_NAVIGATION_CHANNEL_USED.load(Ordering::Relaxed) {
+            let _ = MENU_NAVIGATION_CHANNEL.0.send(event);
+        }
+    }
+
+    /// Send `event` to the sender of its root menu,
+    /// returns the event back if there is no such sender or its receiver was dropped.
+    fn send_to_root_sender(event: MenuEvent) -> Option<MenuEvent> {
+        let root = match &event.root {
+            Some(root) => root.clone(),
+            None => return Some(event),
+        };
+
+        // the lock isn't held while sending, as a full bounded channel blocks
+        // and the receiving side may set the senders itself
+        let sender = MENU_EVENT_SENDERS
+            .lock()
+            .unwrap()
+            .get(&root)
+            .map(|(_, sender)| sender.clone());
+        let sender = match sender {
+            Some(sender) => sender,
+            None => return Some(event),
+        };
+
+        match sender.send(event) {
+            Ok(()) => None,
+            Err(e) => {
+                // only remove the sender if it wasn't replaced in the meantime
+                let mut senders = MENU_EVENT_SENDERS.lock().unwrap();
+                if senders
+                    .get(&root)
+                    .map(|(_, s)| s.same_channel(&sender))
+                    .unwrap_or(false)
+                {
+                    senders.remove(&root);
+                }
+                Some(e.into_inner())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{MenuEvent, MenuEventFilter, MenuEventKind, MenuEventSource, MenuId};
+
+    // tests that read from the global channel must not run at the same time
+    static CHANNEL_LOCK: Mutex<()> = Mutex::new(());
+
+    fn event(id: &str, root: Option<&str>) -> MenuEvent {
+        MenuEvent {
+            id: MenuId::new(id),
+            kind: MenuEventKind::Activated,
+            source: MenuEventSource::Programmatic,
+            root: root.map(MenuId::new),
+        }
+    }
+
+    #[test]
+    fn event_handler_guard_removes_handler() {
+        let _lock = CHANNEL_LOCK.lock().unwrap();
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let received_c = received.clone();
+        let guard = MenuEvent::add_event_handler(
+            MenuEventFilter::IdPrefix("handler-test-".into()),
+            move |e| received_c.lock().unwrap().push(e.id),
+        );
+
+        MenuEvent::send(event("handler-test-1", None));
+        MenuEvent::send(event("other-handler-test", None));
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![MenuId::new("handler-test-1")]
+        );
+
+        drop(guard);
+
+        MenuEvent::send(event("handler-test-2", None));
+        assert_eq!(received.lock().unwrap().len(), 1);
+        assert!(MenuEvent::receiver()
+            .try_iter()
+            .any(|e| e.id == "handler-test-2"));
+    }
+
+    #[test]
+    fn root_sender_receives_root_events() {
+        let _lock = CHANNEL_LOCK.lock().unwrap();
+        let (tx, rx) = crossbeam_channel::unbounded();
+        let root = MenuId::new("root-sender-test");
+        MenuEvent::set_root_sender(&root, 0, Some(tx));
+
+        MenuEvent::send(event("root-sender-test-item", Some("root-sender-test")));
+        assert_eq!(rx.try_recv().unwrap().id, "root-sender-test-item");
+        assert!(!MenuEvent::receiver()
+            .try_iter()
+            .any(|e| e.id == "root-sender-test-item"));
+
+        // falls back to the global channel once the receiver is dropped
+        drop(rx);
+        MenuEvent::send(event("root-sender-test-item-2", Some("root-sender-test")));
+        assert!(MenuEvent::receiver()
+            .try_iter()
+            .any(|e| e.id == "root-sender-test-item-2"));
+    }
+
+    #[test]
+    fn full_root_sender_doesnt_block_other_menus() {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+        MenuEvent::set_root_sender(&MenuId::new("full-sender-test"), 0, Some(tx));
+        MenuEvent::send(event("full-sender-test-1", Some("full-sender-test")));
+
+        // blocks until the first event is received
+        let blocked = std::thread::spawn(|| {
+            MenuEvent::send(event("full-sender-test-2", Some("full-sender-test")))
+        });
+        std::thread::sleep(std::time::Duration::from_millis(50));
+
+        let (other_tx, other_rx) = crossbeam_channel::unbounded();
+        let other = MenuId::new("other-sender-test");
+        MenuEvent::set_root_sender(&other, 0, Some(other_tx));
+        MenuEvent::send(event("other-sender-test-1", Some("other-sender-test")));
+        assert_eq!(other_rx.try_recv().unwrap().id, "other-sender-test-1");
+
+        assert_eq!(rx.recv().unwrap().id, "full-sender-test-1");
+        blocked.join().unwrap();
+        assert_eq!(rx.recv().unwrap().id, "full-sender-test-2");
+    }
+
+    #[test]
+    fn navigation_events_only_reach_navigation_consumers() {
+        let _lock = CHANNEL_LOCK.lock().unwrap();
+        let (tx, rx) = crossbeam_channel::unbounded();
+        MenuEvent::set_root_sender(&MenuId::new("navigation-test"), 0, Some(tx));
+        let navigation = MenuEvent::navigation_receiver();
+
+        for kind in [
+            MenuEventKind::SubmenuOpened,
+            MenuEventKind::SubmenuClosed,
+            MenuEventKind::Highlighted,
+        ] {
+            assert!(kind.is_navigation());
+            MenuEvent::send(MenuEvent {
+                kind,
+                ..event("navigation-test-item", Some("navigation-test"))
+            });
+        }
+        assert!(!MenuEventKind::Activated.is_navigation());
+        assert!(!MenuEventKind::Toggled { checked: true }.is_navigation());
+
+        let received = navigation
+            .try_iter()
+            .filter(|e| e.id == "navigation-test-item")
+            .map(|e| e.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            received,
+            [
+                MenuEventKind::SubmenuOpened,
+                MenuEventKind::SubmenuClosed,
+                MenuEventKind::Highlighted
+            ]
+        );
+        assert!(rx.try_recv().is_err());
+        assert!(!MenuEvent::receiver()
+            .try_iter()
+            .any(|e| e.id == "navigation-test-item"));
+
+        // activations still go to the sender of their menu
+        MenuEvent::send(event("navigation-test-item", Some("navigation-test")));
+        assert_eq!(rx.try_recv().unwrap().kind, MenuEventKind::Activated);
+        assert!(!navigation
+            .try_iter()
+            .any(|e| e.id == "navigation-test-item"));
+        MenuEvent::set_root_sender(&MenuId::new("navigation-test"), 0, None);
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn stream_receives_events() {
+        use std::{
+            pin::Pin,
+            sync::atomic::{AtomicBool, Ordering},
+            task::{Context, Poll, Wake, Waker},
+        };
+
+        use futures_core::Stream;
+
+        struct Flag(AtomicBool);
+
+        impl Wake for Flag {
+            fn wake(self: Arc<Self>) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let woken = Arc::new(Flag(AtomicBool::new(false)));
+        let waker = Waker::from(woken.clone());
+        let mut cx = Context::from_waker(&waker);
+        let mut stream = MenuEvent::stream();
+
+        // drain the events other tests may have sent until the stream waits for more
+        while let Poll::Ready(e) = Pin::new(&mut stream).poll_next(&mut cx) {
+            assert!(e.is_some());
+        }
+
+        MenuEvent::send(event("stream-test", None));
+        assert!(woken.0.load(Ordering::SeqCst));
+
+        let mut received = Vec::new();
+        while let Poll::Ready(Some(e)) = Pin::new(&mut stream).poll_next(&mut cx) {
+            received.push(e.id);
+        }
+        assert!(received.contains(&MenuId::new("stream-test")));
+    }
+
+    #[test]
+    fn event_filter_matches() {
+        let e = event("file-open", Some("main"));
+
+        assert!(MenuEventFilter::All.matches(&e));
+        assert!(MenuEventFilter::IdPrefix("file-".into()).matches(&e));
+        assert!(!MenuEventFilter::IdPrefix("edit-".into()).matches(&e));
+        assert!(MenuEventFilter::Root(MenuId::new("main")).matches(&e));
+        assert!(!MenuEventFilter::Root(MenuId::new("context")).matches(&e));
+    }
+}
@@ -0,0 +1,323 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Simulating the user of a menu in tests, enabled by the `headless` feature.
+//!
+//! Menus and items are still the ones of the native backend, this only adds ways to use them
+//! without a window: the windows a menu is added to are only numbers, and clicks and key presses
+//! are resolved from the menu tree and sent like the ones of the native menus.
+//!
+//! On Linux, gtk widgets are only created when a menu is added to a gtk window or shown as
+//! a context menu, so tests that don't do that need neither a display nor `gtk::init`,
+//! but building still needs the gtk development packages. There is no pure-Rust backend
+//! replacing the native one, as it couldn't provide the gtk based API like
+//! `ContextMenu::gtk_context_menu` and `GioMenu`, so enabling this feature would break
+//! the other crates using muda in the same build.
+
+use std::{
+    borrow::Borrow,
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use crate::{
+    accelerator::{Accelerator, Code, Key, KeySequenceMatch, KeySequenceMatcher, Modifiers},
+    dispatcher, IsMenuItem, Menu, MenuEventSource, MenuId, MenuItemKind,
+};
+
+thread_local! {
+    /// The headless windows menus were added to, see [`Menu::init_for_headless_window`].
+    static WINDOWS: RefCell<Vec<Window>> = const { RefCell::new(Vec::new()) };
+}
+
+struct Window {
+    menu: Weak<RefCell<crate::platform_impl::Menu>>,
+    window: u64,
+    visible: bool,
+    /// The strokes of the key sequence being pressed in this window.
+    matcher: KeySequenceMatcher,
+}
+
+/// Calls `f` with the entry of `menu` for `window`, if `menu` was added to it.
+fn with_window<R>(menu: &Menu, window: u64, f: impl FnOnce(&mut Window) -> R) -> Option<R> {
+    WINDOWS.with(|windows| {
+        let mut windows = windows.borrow_mut();
+        windows.retain(|w| w.menu.strong_count() > 0);
+        windows
+            .iter_mut()
+            .find(|w| w.window == window && w.menu.as_ptr() == Rc::as_ptr(&menu.inner))
+            .map(f)
+    })
+}
+
+pub(crate) fn init_for_window(menu: &Menu, window: u64) -> crate::Result<()> {
+    if with_window(menu, window, |_| ()).is_some() {
+        return Err(crate::Error::AlreadyInitialized);
+    }
+
+    WINDOWS.with(|windows| {
+        windows.borrow_mut().push(Window {
+            menu: Rc::downgrade(&menu.inner),
+            window,
+            visible: true,
+            matcher: KeySequenceMatcher::new(),
+        })
+    });
+    Ok(())
+}
+
+pub(crate) fn remove_for_window(menu: &Menu, window: u64) -> crate::Result<()> {
+    with_window(menu, window, |_| ()).ok_or(crate::Error::NotInitialized)?;
+
+    WINDOWS.with(|windows| {
+        windows
+            .borrow_mut()
+            .retain(|w| w.window != window || w.menu.as_ptr() != Rc::as_ptr(&menu.inner))
+    });
+    Ok(())
+}
+
+pub(crate) fn set_visible_on_window(menu: &Menu, window: u64, visible: bool) -> crate::Result<()> {
+    with_window(menu, window, |w| {
+        w.visible = visible;
+        w.matcher.reset();
+    })
+    .ok_or(crate::Error::NotInitialized)
+}
+
+pub(crate) fn is_visible_on_window(menu: &Menu, window: u64) -> bool {
+    with_window(menu, window, |w| w.visible).unwrap_or(false)
+}
+
+/// Feeds a key press to the accelerators of `menu` in `window`, like [`crate::AcceleratorDispatcher`] does.
+///
+/// Key presses in windows `menu` wasn't added to, or is hidden on, are ignored.
+pub(crate) fn press(
+    menu: &Menu,
+    window: u64,
+    process: impl FnOnce(&mut KeySequenceMatcher) -> KeySequenceMatch,
+) -> Option<MenuId> {
+    // the matcher is taken out while dispatching, as the menu event handler may change the windows
+    let mut matcher = with_window(menu, window, |w| {
+        w.visible.then(|| std::mem::take(&mut w.matcher))
+    })??;
+    let id = dispatcher::dispatch(menu.id(), menu.items(), &mut matcher, process);
+    with_window(menu, window, |w| w.matcher = matcher);
+    id
+}
+
+/// Presses the key of `accelerator`, the character of character based accelerators.
+pub(crate) fn press_accelerator(
+    menu: &Menu,
+    window: u64,
+    accelerator: &Accelerator,
+) -> Option<MenuId> {
+    press(menu, window, |matcher| match accelerator.character() {
+        Some(character) => matcher.process_key(
+            accelerator.mods,
+            accelerator.key,
+            Key::Character(character.to_string()),
+        ),
+        None => matcher.process(accelerator.mods, accelerator.key),
+    })
+}
+
+pub(crate) fn press_key(
+    menu: &Menu,
+    window: u64,
+    modifiers: impl Borrow<Modifiers>,
+    code: impl Borrow<Code>,
+    key: impl Borrow<Key>,
+) -> Option<MenuId> {
+    press(menu, window, |matcher| {
+        matcher.process_key(modifiers, code, key)
+    })
+}
+
+/// Returns whether `item` is in `items` or their submenus, and if so whether a user could
+/// reach it: the item and the submenus leading to it are all enabled and visible.
+fn find(items: &[MenuItemKind], item: *const crate::platform_impl::MenuChild) -> Option<bool> {
+    for i in items {
+        let reachable = {
+            let child = i.child();
+            child.is_enabled() && child.is_visible()
+        };
+        if i.child_ptr() == item {
+            return Some(reachable);
+        }
+        if let MenuItemKind::Submenu(submenu) = i {
+            if let Some(found) = find(&submenu.items(), item) {
+                return Some(reachable && found);
+            }
+        }
+    }
+    None
+}
+
+/// Clicks `item` of the menu `root` whose items are `items`, like a user would.
+///
+/// Clicks on disabled or hidden items, or items of disabled or hidden submenus, are ignored.
+/// Predefined items send no event, their action is performed by the platform.
+pub(crate) fn click(
+    root: &MenuId,
+    items: &[MenuItemKind],
+    item: &dyn IsMenuItem,
+) -> crate::Result<()> {
+    let item = item.kind();
+    match find(items, item.child_ptr()) {
+        Some(true) => {
+            if !matches!(item, MenuItemKind::Predefined(_)) {
+                dispatcher::activate(&item, root, MenuEventSource::Mouse);
+            }
+        }
+        Some(false) => {}
+        None => return Err(crate::Error::NotAChildOfThisMenu),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        accelerator::{Accelerator, Code, Key, KeySequence, Modifiers},
+        CheckMenuItem, Error, Menu, MenuEventKind, MenuEventSource, MenuItem, PredefinedMenuItem,
+        RadioGroup, RadioMenuItem, Submenu,
+    };
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn click_items() {
+        let new = MenuItem::with_id("new", "New", true, None);
+        let close = MenuItem::with_id("close", "Close", false, None);
+        let wrap = CheckMenuItem::with_id("wrap", "Wrap", true, false, None);
+        let group = RadioGroup::new();
+        let light = RadioMenuItem::with_id("light", "Light", &group, true, true, None);
+        let dark = RadioMenuItem::with_id("dark", "Dark", &group, true, false, None);
+        let hidden = MenuItem::with_id("hidden", "Hidden", true, None);
+        let recent = Submenu::with_items("Recent", true, &[&hidden]).unwrap();
+        let copy = PredefinedMenuItem::copy(None);
+        recent.set_visible(false);
+        let file = Submenu::with_items(
+            "File",
+            true,
+            &[&new, &close, &copy, &wrap, &light, &dark, &recent],
+        )
+        .unwrap();
+        let menu = Menu::with_id_and_items("menu", &[&file]).unwrap();
+        let events = menu.events();
+
+        menu.click(&new).unwrap();
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.id, "new");
+        assert_eq!(event.kind, MenuEventKind::Activated);
+        assert_eq!(event.source, MenuEventSource::Mouse);
+        assert_eq!(event.root.as_ref().unwrap(), "menu");
+
+        menu.click(&wrap).unwrap();
+        assert!(wrap.is_checked());
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.kind, MenuEventKind::Toggled { checked: true });
+
+        menu.click(&dark).unwrap();
+        assert!(dark.is_checked());
+        assert!(!light.is_checked());
+        assert_eq!(events.try_recv().unwrap().id, "dark");
+        // clicking the selected radio item doesn't change anything
+        menu.click(&dark).unwrap();
+        assert!(dark.is_checked());
+
+        // disabled, hidden and predefined items send no events
+        menu.click(&close).unwrap();
+        menu.click(&hidden).unwrap();
+        menu.click(&copy).unwrap();
+        assert!(events.try_recv().is_err());
+
+        let other = MenuItem::new("Other", true, None);
+        assert!(matches!(
+            menu.click(&other),
+            Err(Error::NotAChildOfThisMenu)
+        ));
+
+        // submenus shown as context menus are the root of the events of their items
+        let file_events = file.events();
+        file.click(&new).unwrap();
+        assert_eq!(
+            file_events.try_recv().unwrap().root.as_ref(),
+            Some(file.id())
+        );
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn press_keys_in_headless_windows() {
+        let ctrl = |code| Accelerator::new(Some(Modifiers::CONTROL), code);
+        let save = MenuItem::with_id("save", "Save", true, Some(ctrl(Code::KeyS)));
+        let plus = MenuItem::with_id(
+            "zoom-in",
+            "Zoom In",
+            true,
+            Some(Accelerator::from_char(Some(Modifiers::CONTROL), '+')),
+        );
+        let comment = MenuItem::with_id("comment", "Comment", true, None);
+        comment
+            .set_key_sequence(Some(KeySequence::new(ctrl(Code::KeyK), [ctrl(Code::KeyC)])))
+            .unwrap();
+        let menu = Menu::with_items(&[&save, &plus, &comment]).unwrap();
+        let events = menu.events();
+
+        // keys only reach the menu once it is added to the window
+        assert_eq!(menu.press_accelerator(1, &ctrl(Code::KeyS)), None);
+        menu.init_for_headless_window(1).unwrap();
+        assert!(matches!(
+            menu.init_for_headless_window(1),
+            Err(Error::AlreadyInitialized)
+        ));
+        assert!(menu.is_visible_on_headless_window(1));
+        assert_eq!(menu.press_accelerator(2, &ctrl(Code::KeyS)), None);
+
+        assert_eq!(
+            menu.press_accelerator(1, &ctrl(Code::KeyS)),
+            Some("save".into())
+        );
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.id, "save");
+        assert_eq!(event.source, MenuEventSource::Accelerator);
+
+        // character accelerators match the logical key
+        assert_eq!(
+            menu.press_key(
+                1,
+                Modifiers::CONTROL,
+                Code::Equal,
+                Key::Character("+".into())
+            ),
+            Some("zoom-in".into())
+        );
+        assert_eq!(events.try_recv().unwrap().id, "zoom-in");
+
+        // key sequences are pressed one stroke at a time
+        assert_eq!(menu.press_accelerator(1, &ctrl(Code::KeyK)), None);
+        assert_eq!(
+            menu.press_accelerator(1, &ctrl(Code::KeyC)),
+            Some("comment".into())
+        );
+        assert_eq!(events.try_recv().unwrap().id, "comment");
+
+        // hidden menus don't get the keys, and disabled items don't handle them
+        menu.hide_for_headless_window(1).unwrap();
+        assert!(!menu.is_visible_on_headless_window(1));
+        assert_eq!(menu.press_accelerator(1, &ctrl(Code::KeyS)), None);
+        menu.show_for_headless_window(1).unwrap();
+        save.set_enabled(false);
+        assert_eq!(menu.press_accelerator(1, &ctrl(Code::KeyS)), None);
+
+        menu.remove_for_headless_window(1).unwrap();
+        assert!(matches!(
+            menu.remove_for_headless_window(1),
+            Err(Error::NotInitialized)
+        ));
+        assert_eq!(menu.press_accelerator(1, &ctrl(Code::KeyK)), None);
+        assert!(events.try_recv().is_err());
+    }
+}
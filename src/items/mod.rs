@@ -6,17 +6,24 @@ mod check;
 mod icon;
 mod normal;
 mod predefined;
+mod radio;
 mod submenu;
 
 pub use check::*;
 pub use icon::*;
 pub use normal::*;
 pub use predefined::*;
+pub use radio::*;
 pub use submenu::*;
 
 #[cfg(test)]
 mod test {
-    use crate::{CheckMenuItem, IconMenuItem, MenuId, MenuItem, PredefinedMenuItem, Submenu};
+    use crate::{
+        accelerator::{Accelerator, Code, KeySequence, Modifiers},
+        CheckMenuItem, IconMenuItem, Menu, MenuEvent, MenuEventKind, MenuEventSource, MenuId,
+        MenuItem, MenuItemBuilder, PredefinedMenuItem, RadioGroup, RadioMenuItem, Submenu,
+        SubmenuBuilder,
+    };
 
     #[test]
     #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
@@ -32,6 +39,10 @@ mod test {
             id,
             IconMenuItem::with_id(id.clone(), "", true, None, None).id()
         );
+        assert_eq!(
+            id,
+            RadioMenuItem::with_id(id.clone(), "", &RadioGroup::new(), true, true, None).id()
+        );
     }
 
     #[test]
@@ -48,6 +59,10 @@ mod test {
         assert_eq!(item.id(), &expected);
         assert_eq!(item.into_id(), expected);
 
+        let item = RadioMenuItem::with_id(id, "test", &RadioGroup::new(), true, true, None);
+        assert_eq!(item.id(), &expected);
+        assert_eq!(item.into_id(), expected);
+
         let item = MenuItem::with_id(id, "test", true, None);
         assert_eq!(item.id(), &expected);
         assert_eq!(item.into_id(), expected);
@@ -59,4 +74,183 @@ mod test {
         let item = PredefinedMenuItem::separator();
         assert_eq!(item.id().clone(), item.into_id());
     }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn radio_group_is_exclusive() {
+        let group = RadioGroup::new();
+        let a = RadioMenuItem::with_id("a", "a", &group, true, true, None);
+        let b = RadioMenuItem::with_id("b", "b", &group, true, false, None);
+        let c = RadioMenuItem::with_id("c", "c", &group, true, true, None);
+        assert!(!a.is_checked());
+        assert!(!b.is_checked());
+        assert!(c.is_checked());
+
+        b.set_checked(true);
+        assert!(!a.is_checked());
+        assert!(b.is_checked());
+        assert!(!c.is_checked());
+        assert_eq!(group.selected().unwrap().id(), b.id());
+
+        b.set_checked(false);
+        assert!(group.selected().is_none());
+
+        drop(a);
+        assert_eq!(group.items().len(), 2);
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn radio_group_unchecks_others_right_away() {
+        let group = RadioGroup::new();
+        let a = RadioMenuItem::with_id("a", "a", &group, true, true, None);
+        let b = RadioMenuItem::with_id("b", "b", &group, true, false, None);
+
+        // the state of the platform items is updated without reading the items through the group
+        let checked = |item: &RadioMenuItem| item.inner.borrow().is_checked();
+        b.set_checked(true);
+        assert!(!checked(&a));
+        assert!(checked(&b));
+
+        // a backend checks an item while it is borrowed, after unchecking the others
+        {
+            let mut inner = a.inner.borrow_mut();
+            group.uncheck_others(&*inner);
+            inner.set_checked(true);
+        }
+        assert!(checked(&a));
+        assert!(!checked(&b));
+        assert_eq!(group.selected().unwrap().id(), a.id());
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn submenu_sender_is_removed_with_the_submenu() {
+        let send = || {
+            MenuEvent::send(MenuEvent {
+                id: MenuId::new("sender-cleanup-item"),
+                kind: MenuEventKind::Activated,
+                source: MenuEventSource::Programmatic,
+                root: Some(MenuId::new("sender-cleanup")),
+            })
+        };
+
+        let menu = Menu::new();
+        let submenu = Submenu::with_id("sender-cleanup", "", true);
+        menu.append(&submenu).unwrap();
+        let events = submenu.events();
+
+        // the menu still holds the submenu
+        drop(submenu);
+        send();
+        assert_eq!(events.try_recv().unwrap().id, "sender-cleanup-item");
+
+        drop(menu);
+        let _submenu = Submenu::with_id("sender-cleanup", "", true);
+        send();
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn find_by_id_in_nested_submenus() {
+        let inner = Submenu::with_items(
+            "inner",
+            true,
+            &[&MenuItem::with_id("file.open", "open", true, None)],
+        )
+        .unwrap();
+        let outer = Submenu::with_items(
+            "outer",
+            true,
+            &[&MenuItem::with_id("file.new", "new", true, None), &inner],
+        )
+        .unwrap();
+        let menu = Menu::with_items(&[&outer]).unwrap();
+
+        let found = menu.find_by_id(&MenuId::new("file.open")).unwrap();
+        assert_eq!(found.id(), "file.open");
+        assert!(menu.find_by_id(&MenuId::new("file.close")).is_none());
+        assert!(outer.find_by_id(inner.id()).is_some());
+
+        let all = menu
+            .iter_all()
+            .map(|(path, item)| (path, item.id().clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            all,
+            vec![
+                (vec![], outer.id().clone()),
+                (vec![outer.id().clone()], MenuId::new("file.new")),
+                (vec![outer.id().clone()], inner.id().clone()),
+                (
+                    vec![outer.id().clone(), inner.id().clone()],
+                    MenuId::new("file.open")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn key_sequence_replaces_accelerator() {
+        let item = MenuItem::new("Comment", true, Some("Ctrl+Slash".parse().unwrap()));
+        assert_eq!(
+            item.key_sequence(),
+            Some(KeySequence::from(Accelerator::new(
+                Some(Modifiers::CONTROL),
+                Code::Slash
+            )))
+        );
+
+        let chord: KeySequence = "Ctrl+KeyK Ctrl+KeyC".parse().unwrap();
+        item.set_key_sequence(Some(chord.clone())).unwrap();
+        assert_eq!(item.key_sequence(), Some(chord));
+        assert_eq!(item.inner.borrow().accelerator(), None);
+
+        item.set_accelerator(Some("Ctrl+KeyS".parse().unwrap()))
+            .unwrap();
+        assert_eq!(item.key_sequence(), Some("Ctrl+KeyS".parse().unwrap()));
+
+        item.set_key_sequence(None).unwrap();
+        assert_eq!(item.key_sequence(), None);
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn set_tooltips() {
+        let item = MenuItemBuilder::new()
+            .text("Reindent")
+            .tooltip("Reindent the selected lines")
+            .build();
+        assert_eq!(
+            item.tooltip().as_deref(),
+            Some("Reindent the selected lines")
+        );
+        item.set_tooltip(None);
+        assert_eq!(item.tooltip(), None);
+
+        let submenu = SubmenuBuilder::new()
+            .text("Edit")
+            .item(&item)
+            .tooltip("Editing commands")
+            .build()
+            .unwrap();
+        assert_eq!(submenu.tooltip().as_deref(), Some("Editing commands"));
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn set_secondary_text() {
+        let inbox = MenuItem::new("&Inbox", true, None);
+        assert_eq!(inbox.secondary_text(), None);
+
+        inbox.set_secondary_text(Some("12"));
+        assert_eq!(inbox.secondary_text().as_deref(), Some("12"));
+        // the text of the item is left untouched
+        assert_eq!(inbox.text(), "&Inbox");
+
+        inbox.set_secondary_text(None);
+        assert_eq!(inbox.secondary_text(), None);
+    }
 }
@@ -1,6 +1,10 @@
 use std::{cell::RefCell, mem, rc::Rc};
 
-use crate::{accelerator::Accelerator, sealed::IsMenuItemBase, IsMenuItem, MenuId, MenuItemKind};
+use crate::{
+    accelerator::{Accelerator, KeySequence},
+    sealed::IsMenuItemBase,
+    validation, IsMenuItem, MenuId, MenuItemKind, MnemonicLabel,
+};
 
 /// A menu item inside a [`Menu`] or [`Submenu`] and contains only text.
 ///
@@ -79,6 +83,11 @@ impl MenuItem {
         self.inner.borrow_mut().set_text(text.as_ref())
     }
 
+    /// Returns the text of this menu item split into its plain text and its mnemonic.
+    pub fn label(&self) -> MnemonicLabel {
+        MnemonicLabel::parse(&self.inner.borrow().text_with_mnemonic())
+    }
+
     /// Get whether this menu item is enabled or not.
     pub fn is_enabled(&self) -> bool {
         self.inner.borrow().is_enabled()
@@ -89,9 +98,79 @@ impl MenuItem {
         self.inner.borrow_mut().set_enabled(enabled)
     }
 
+    /// Get whether this menu item is visible or not.
+    pub fn is_visible(&self) -> bool {
+        self.inner.borrow().is_visible()
+    }
+
+    /// Show or hide this menu item, keeping it in its menus.
+    /// Hidden items don't trigger their accelerators.
+    pub fn set_visible(&self, visible: bool) {
+        self.inner.borrow_mut().set_visible(visible)
+    }
+
+    /// Get the tooltip of this menu item.
+    pub fn tooltip(&self) -> Option<String> {
+        self.inner.borrow().tooltip()
+    }
+
+    /// Set the tooltip of this menu item, shown when hovering it to describe what it does.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows**: Unsupported.
+    pub fn set_tooltip(&self, tooltip: Option<&str>) {
+        self.inner.borrow_mut().set_tooltip(tooltip)
+    }
+
+    /// Get the secondary text of this menu item.
+    pub fn secondary_text(&self) -> Option<String> {
+        self.inner.borrow().secondary_text()
+    }
+
+    /// Set a secondary text shown at the end of this menu item, like a count or a badge,
+    /// which can be updated without changing the text of the menu item.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows**: Shown before the accelerator.
+    /// - **macOS**: Shown as a badge, requires macOS 14 or later.
+    pub fn set_secondary_text(&self, text: Option<&str>) {
+        self.inner.borrow_mut().set_secondary_text(text)
+    }
+
     /// Set this menu item accelerator.
     pub fn set_accelerator(&self, acccelerator: Option<Accelerator>) -> crate::Result<()> {
-        self.inner.borrow_mut().set_accelerator(acccelerator)
+        validation::check_set_accelerator(self, acccelerator)?;
+        let mut inner = self.inner.borrow_mut();
+        inner.set_key_sequence(None);
+        inner.set_accelerator(acccelerator)
+    }
+
+    /// Get this menu item key sequence, which is a single-stroke key sequence
+    /// if it only has an accelerator.
+    pub fn key_sequence(&self) -> Option<KeySequence> {
+        let inner = self.inner.borrow();
+        inner
+            .key_sequence()
+            .or_else(|| inner.accelerator().map(KeySequence::from))
+    }
+
+    /// Set this menu item key sequence.
+    ///
+    /// A single-stroke key sequence is set as the accelerator of this menu item,
+    /// while a multi-stroke key sequence is only shown in its label, see [`KeySequence`].
+    pub fn set_key_sequence(&self, key_sequence: Option<KeySequence>) -> crate::Result<()> {
+        match key_sequence {
+            Some(key_sequence) if key_sequence.is_chord() => {
+                validation::check_set_key_sequence(self, Some(&key_sequence))?;
+                let mut inner = self.inner.borrow_mut();
+                inner.set_accelerator(None)?;
+                inner.set_key_sequence(Some(key_sequence));
+                Ok(())
+            }
+            key_sequence => self.set_accelerator(key_sequence.map(|s| s.first())),
+        }
     }
 
     /// Convert this menu item into its menu ID.
@@ -7,7 +7,7 @@ use std::{cell::RefCell, mem, rc::Rc};
 use crate::{
     accelerator::{Accelerator, CMD_OR_CTRL},
     sealed::IsMenuItemBase,
-    AboutMetadata, IsMenuItem, MenuId, MenuItemKind,
+    AboutMetadata, IsMenuItem, MenuId, MenuItemKind, MnemonicLabel,
 };
 use keyboard_types::{Code, Modifiers};
 
@@ -63,7 +63,9 @@ impl PredefinedMenuItem {
     ///
     /// ## Platform-specific:
     ///
-    /// - **Windows / Linux:** Unsupported.
+    /// - **Windows:** Unsupported.
+    /// - **Linux:** Uses the `undo` signal of the focused widget when it has one,
+    ///   otherwise sends it <kbd>Ctrl</kbd>+<kbd>Z</kbd>.
     pub fn undo(text: Option<&str>) -> PredefinedMenuItem {
         PredefinedMenuItem::new(PredefinedMenuItemType::Undo, text)
     }
@@ -71,25 +73,19 @@ impl PredefinedMenuItem {
     ///
     /// ## Platform-specific:
     ///
-    /// - **Windows / Linux:** Unsupported.
+    /// - **Windows:** Unsupported.
+    /// - **Linux:** Uses the `redo` signal of the focused widget when it has one,
+    ///   otherwise sends it <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>Z</kbd>.
     pub fn redo(text: Option<&str>) -> PredefinedMenuItem {
         PredefinedMenuItem::new(PredefinedMenuItemType::Redo, text)
     }
 
     /// Minimize window menu item
-    ///
-    /// ## Platform-specific:
-    ///
-    /// - **Linux:** Unsupported.
     pub fn minimize(text: Option<&str>) -> PredefinedMenuItem {
         PredefinedMenuItem::new(PredefinedMenuItemType::Minimize, text)
     }
 
     /// Maximize window menu item
-    ///
-    /// ## Platform-specific:
-    ///
-    /// - **Linux:** Unsupported.
     pub fn maximize(text: Option<&str>) -> PredefinedMenuItem {
         PredefinedMenuItem::new(PredefinedMenuItemType::Maximize, text)
     }
@@ -98,16 +94,12 @@ impl PredefinedMenuItem {
     ///
     /// ## Platform-specific:
     ///
-    /// - **Windows / Linux:** Unsupported.
+    /// - **Windows:** Unsupported.
     pub fn fullscreen(text: Option<&str>) -> PredefinedMenuItem {
         PredefinedMenuItem::new(PredefinedMenuItemType::Fullscreen, text)
     }
 
     /// Hide window menu item
-    ///
-    /// ## Platform-specific:
-    ///
-    /// - **Linux:** Unsupported.
     pub fn hide(text: Option<&str>) -> PredefinedMenuItem {
         PredefinedMenuItem::new(PredefinedMenuItemType::Hide, text)
     }
@@ -131,10 +123,6 @@ impl PredefinedMenuItem {
     }
 
     /// Close window menu item
-    ///
-    /// ## Platform-specific:
-    ///
-    /// - **Linux:** Unsupported.
     pub fn close_window(text: Option<&str>) -> PredefinedMenuItem {
         PredefinedMenuItem::new(PredefinedMenuItemType::CloseWindow, text)
     }
@@ -143,7 +131,8 @@ impl PredefinedMenuItem {
     ///
     /// ## Platform-specific:
     ///
-    /// - **Linux:** Unsupported.
+    /// - **Linux:** Quits the gtk main loop, or when it isn't running,
+    ///   closes all the visible windows of the application.
     pub fn quit(text: Option<&str>) -> PredefinedMenuItem {
         PredefinedMenuItem::new(PredefinedMenuItemType::Quit, text)
     }
@@ -171,7 +160,7 @@ impl PredefinedMenuItem {
         PredefinedMenuItem::new(PredefinedMenuItemType::BringAllToFront, text)
     }
 
-    fn new<S: AsRef<str>>(item: PredefinedMenuItemType, text: Option<S>) -> Self {
+    pub(crate) fn new<S: AsRef<str>>(item: PredefinedMenuItemType, text: Option<S>) -> Self {
         let item = crate::platform_impl::MenuChild::new_predefined(
             item,
             text.map(|t| t.as_ref().to_string()),
@@ -197,6 +186,58 @@ impl PredefinedMenuItem {
         self.inner.borrow_mut().set_text(text.as_ref())
     }
 
+    /// Get whether this predefined menu item is visible or not.
+    pub fn is_visible(&self) -> bool {
+        self.inner.borrow().is_visible()
+    }
+
+    /// Show or hide this predefined menu item, keeping it in its menus.
+    pub fn set_visible(&self, visible: bool) {
+        self.inner.borrow_mut().set_visible(visible)
+    }
+
+    /// Get the tooltip of this predefined menu item.
+    pub fn tooltip(&self) -> Option<String> {
+        self.inner.borrow().tooltip()
+    }
+
+    /// Set the tooltip of this predefined menu item, shown when hovering it to describe what it does.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows**: Unsupported.
+    pub fn set_tooltip(&self, tooltip: Option<&str>) {
+        self.inner.borrow_mut().set_tooltip(tooltip)
+    }
+
+    /// Get the secondary text of this predefined menu item.
+    pub fn secondary_text(&self) -> Option<String> {
+        self.inner.borrow().secondary_text()
+    }
+
+    /// Set a secondary text shown at the end of this predefined menu item, like a count or a badge,
+    /// which can be updated without changing the text of the predefined menu item.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows**: Shown before the accelerator.
+    /// - **macOS**: Shown as a badge, requires macOS 14 or later.
+    pub fn set_secondary_text(&self, text: Option<&str>) {
+        self.inner.borrow_mut().set_secondary_text(text)
+    }
+
+    /// Returns the text of this predefined menu item split into its plain text and its mnemonic.
+    pub fn label(&self) -> MnemonicLabel {
+        MnemonicLabel::parse(&self.inner.borrow().text_with_mnemonic())
+    }
+
+    /// Returns whether this predefined menu item is supported on the current platform.
+    ///
+    /// Unsupported items are left out of native menus or do nothing when activated.
+    pub fn is_supported(&self) -> bool {
+        self.inner.borrow().is_supported()
+    }
+
     /// Convert this menu item into its menu ID.
     pub fn into_id(mut self) -> MenuId {
         // Note: `Rc::into_inner` is available from Rust 1.70
@@ -346,3 +387,28 @@ impl PredefinedMenuItemType {
         }
     }
 }
+
+#[test]
+#[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+fn test_supported_predefined_items() {
+    assert!(PredefinedMenuItem::separator().is_supported());
+    assert!(PredefinedMenuItem::copy(None).is_supported());
+    assert!(PredefinedMenuItem::minimize(None).is_supported());
+    assert!(PredefinedMenuItem::close_window(None).is_supported());
+    assert!(PredefinedMenuItem::quit(None).is_supported());
+    assert!(PredefinedMenuItem::about(None, None).is_supported());
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        assert!(!PredefinedMenuItem::services(None).is_supported());
+        assert!(!PredefinedMenuItem::bring_all_to_front(None).is_supported());
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        assert!(PredefinedMenuItem::undo(None).is_supported());
+        assert!(PredefinedMenuItem::redo(None).is_supported());
+        assert!(PredefinedMenuItem::fullscreen(None).is_supported());
+        assert!(!PredefinedMenuItem::hide_others(None).is_supported());
+    }
+}
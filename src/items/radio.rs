@@ -0,0 +1,334 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+    cell::RefCell,
+    mem,
+    rc::{Rc, Weak},
+};
+
+use crate::{
+    accelerator::{Accelerator, KeySequence},
+    sealed::IsMenuItemBase,
+    validation, IsMenuItem, MenuId, MenuItemKind, MnemonicLabel,
+};
+
+/// A group of [`RadioMenuItem`]s where at most one item is checked at a time.
+///
+/// Checking an item in the group, either by the user or through [`RadioMenuItem::set_checked`],
+/// unchecks all the other items in the same group.
+#[derive(Debug, Clone, Default)]
+pub struct RadioGroup {
+    items: Rc<RefCell<Vec<Weak<RefCell<crate::platform_impl::MenuChild>>>>>,
+    name: Option<Rc<str>>,
+}
+
+impl RadioGroup {
+    /// Create a new empty radio group.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a new empty radio group with a name,
+    /// which is used for this group in a [`MenuSpec`](crate::MenuSpec).
+    pub fn with_name<S: AsRef<str>>(name: S) -> Self {
+        Self {
+            name: Some(name.as_ref().into()),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the name of this group if it was created with [`RadioGroup::with_name`].
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the radio menu items that belong to this group.
+    pub fn items(&self) -> Vec<RadioMenuItem> {
+        self.items
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|inner| {
+                let id = inner.borrow().id().clone();
+                RadioMenuItem {
+                    id: Rc::new(id),
+                    inner,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the currently checked radio menu item in this group, if any.
+    pub fn selected(&self) -> Option<RadioMenuItem> {
+        self.items().into_iter().find(|i| i.is_checked())
+    }
+
+    /// Returns whether `self` and `other` are the same group.
+    pub(crate) fn ptr_eq(&self, other: &RadioGroup) -> bool {
+        Rc::ptr_eq(&self.items, &other.items)
+    }
+
+    pub(crate) fn add(&self, item: &Rc<RefCell<crate::platform_impl::MenuChild>>) {
+        let mut items = self.items.borrow_mut();
+        items.retain(|i| i.strong_count() > 0);
+        items.push(Rc::downgrade(item));
+    }
+
+    /// Uncheck all the items in this group except `item`.
+    ///
+    /// `item` is compared by address and isn't borrowed, so this can be called while it is
+    /// mutably borrowed. The other items must not be borrowed, callers uncheck them
+    /// before borrowing the item that gets checked, so they are all unchecked right away.
+    pub(crate) fn uncheck_others(&self, item: *const crate::platform_impl::MenuChild) {
+        let others = self
+            .items
+            .borrow()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|i| !std::ptr::eq(i.as_ptr(), item))
+            .collect::<Vec<_>>();
+
+        for other in others {
+            other.borrow_mut().set_checked(false);
+        }
+    }
+}
+
+/// A radio menu item inside a [`Menu`] or [`Submenu`]
+/// and usually contains a text and a radio mark or a similar indicator
+/// that shows which item of its [`RadioGroup`] is currently selected.
+///
+/// A [`MenuEvent`] is only emitted when the item becomes the checked item of its group.
+///
+/// ## Platform-specific:
+///
+/// - **macOS:** Checked items show a check mark, following the platform conventions.
+///
+/// [`Menu`]: crate::Menu
+/// [`Submenu`]: crate::Submenu
+/// [`MenuEvent`]: crate::MenuEvent
+#[derive(Clone)]
+pub struct RadioMenuItem {
+    pub(crate) id: Rc<MenuId>,
+    pub(crate) inner: Rc<RefCell<crate::platform_impl::MenuChild>>,
+}
+
+impl IsMenuItemBase for RadioMenuItem {}
+impl IsMenuItem for RadioMenuItem {
+    fn kind(&self) -> MenuItemKind {
+        MenuItemKind::Radio(self.clone())
+    }
+
+    fn id(&self) -> &MenuId {
+        self.id()
+    }
+
+    fn into_id(self) -> MenuId {
+        self.into_id()
+    }
+}
+
+impl RadioMenuItem {
+    /// Create a new radio menu item that belongs to `group`.
+    ///
+    /// - `text` could optionally contain an `&` before a character to assign this character as the mnemonic
+    ///   for this radio menu item. To display a `&` without assigning a mnemenonic, use `&&`.
+    /// - If `checked` is `true`, the other items in `group` will be unchecked.
+    pub fn new<S: AsRef<str>>(
+        text: S,
+        group: &RadioGroup,
+        enabled: bool,
+        checked: bool,
+        acccelerator: Option<Accelerator>,
+    ) -> Self {
+        let item = crate::platform_impl::MenuChild::new_radio(
+            text.as_ref(),
+            enabled,
+            checked,
+            acccelerator,
+            group.clone(),
+            None,
+        );
+        Self::from_child(item, group, checked)
+    }
+
+    /// Create a new radio menu item with the specified id that belongs to `group`.
+    ///
+    /// - `text` could optionally contain an `&` before a character to assign this character as the mnemonic
+    ///   for this radio menu item. To display a `&` without assigning a mnemenonic, use `&&`.
+    /// - If `checked` is `true`, the other items in `group` will be unchecked.
+    pub fn with_id<I: Into<MenuId>, S: AsRef<str>>(
+        id: I,
+        text: S,
+        group: &RadioGroup,
+        enabled: bool,
+        checked: bool,
+        acccelerator: Option<Accelerator>,
+    ) -> Self {
+        let id = id.into();
+        let item = crate::platform_impl::MenuChild::new_radio(
+            text.as_ref(),
+            enabled,
+            checked,
+            acccelerator,
+            group.clone(),
+            Some(id),
+        );
+        Self::from_child(item, group, checked)
+    }
+
+    fn from_child(
+        item: crate::platform_impl::MenuChild,
+        group: &RadioGroup,
+        checked: bool,
+    ) -> Self {
+        let item = Self {
+            id: Rc::new(item.id().clone()),
+            inner: Rc::new(RefCell::new(item)),
+        };
+        group.add(&item.inner);
+        if checked {
+            group.uncheck_others(item.inner.as_ptr());
+        }
+        item
+    }
+
+    /// Returns a unique identifier associated with this radio menu item.
+    pub fn id(&self) -> &MenuId {
+        &self.id
+    }
+
+    /// Returns the [`RadioGroup`] this radio menu item belongs to.
+    pub fn group(&self) -> RadioGroup {
+        self.inner.borrow().radio_group()
+    }
+
+    /// Get the text for this radio menu item.
+    pub fn text(&self) -> String {
+        self.inner.borrow().text()
+    }
+
+    /// Set the text for this radio menu item. `text` could optionally contain
+    /// an `&` before a character to assign this character as the mnemonic
+    ///   for this radio menu item. To display a `&` without assigning a mnemenonic, use `&&`.
+    pub fn set_text<S: AsRef<str>>(&self, text: S) {
+        self.inner.borrow_mut().set_text(text.as_ref())
+    }
+
+    /// Returns the text of this radio menu item split into its plain text and its mnemonic.
+    pub fn label(&self) -> MnemonicLabel {
+        MnemonicLabel::parse(&self.inner.borrow().text_with_mnemonic())
+    }
+
+    /// Get whether this radio menu item is enabled or not.
+    pub fn is_enabled(&self) -> bool {
+        self.inner.borrow().is_enabled()
+    }
+
+    /// Enable or disable this radio menu item.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.inner.borrow_mut().set_enabled(enabled)
+    }
+
+    /// Get whether this radio menu item is visible or not.
+    pub fn is_visible(&self) -> bool {
+        self.inner.borrow().is_visible()
+    }
+
+    /// Show or hide this radio menu item, keeping it in its menus.
+    /// Hidden items don't trigger their accelerators.
+    pub fn set_visible(&self, visible: bool) {
+        self.inner.borrow_mut().set_visible(visible)
+    }
+
+    /// Get the tooltip of this radio menu item.
+    pub fn tooltip(&self) -> Option<String> {
+        self.inner.borrow().tooltip()
+    }
+
+    /// Set the tooltip of this radio menu item, shown when hovering it to describe what it does.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows**: Unsupported.
+    pub fn set_tooltip(&self, tooltip: Option<&str>) {
+        self.inner.borrow_mut().set_tooltip(tooltip)
+    }
+
+    /// Get the secondary text of this radio menu item.
+    pub fn secondary_text(&self) -> Option<String> {
+        self.inner.borrow().secondary_text()
+    }
+
+    /// Set a secondary text shown at the end of this radio menu item, like a count or a badge,
+    /// which can be updated without changing the text of the radio menu item.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows**: Shown before the accelerator.
+    /// - **macOS**: Shown as a badge, requires macOS 14 or later.
+    pub fn set_secondary_text(&self, text: Option<&str>) {
+        self.inner.borrow_mut().set_secondary_text(text)
+    }
+
+    /// Set this radio menu item accelerator.
+    pub fn set_accelerator(&self, acccelerator: Option<Accelerator>) -> crate::Result<()> {
+        validation::check_set_accelerator(self, acccelerator)?;
+        let mut inner = self.inner.borrow_mut();
+        inner.set_key_sequence(None);
+        inner.set_accelerator(acccelerator)
+    }
+
+    /// Get this radio menu item key sequence, which is a single-stroke key sequence
+    /// if it only has an accelerator.
+    pub fn key_sequence(&self) -> Option<KeySequence> {
+        let inner = self.inner.borrow();
+        inner
+            .key_sequence()
+            .or_else(|| inner.accelerator().map(KeySequence::from))
+    }
+
+    /// Set this radio menu item key sequence.
+    ///
+    /// A single-stroke key sequence is set as the accelerator of this radio menu item,
+    /// while a multi-stroke key sequence is only shown in its label, see [`KeySequence`].
+    pub fn set_key_sequence(&self, key_sequence: Option<KeySequence>) -> crate::Result<()> {
+        match key_sequence {
+            Some(key_sequence) if key_sequence.is_chord() => {
+                validation::check_set_key_sequence(self, Some(&key_sequence))?;
+                let mut inner = self.inner.borrow_mut();
+                inner.set_accelerator(None)?;
+                inner.set_key_sequence(Some(key_sequence));
+                Ok(())
+            }
+            key_sequence => self.set_accelerator(key_sequence.map(|s| s.first())),
+        }
+    }
+
+    /// Get whether this radio menu item is checked or not.
+    pub fn is_checked(&self) -> bool {
+        self.inner.borrow().is_checked()
+    }
+
+    /// Check or uncheck this radio menu item.
+    ///
+    /// Checking this item unchecks the other items in its [`RadioGroup`].
+    pub fn set_checked(&self, checked: bool) {
+        if checked {
+            self.group().uncheck_others(self.inner.as_ptr());
+        }
+        self.inner.borrow_mut().set_checked(checked)
+    }
+
+    /// Convert this menu item into its menu ID.
+    pub fn into_id(mut self) -> MenuId {
+        // Note: `Rc::into_inner` is available from Rust 1.70
+        if let Some(id) = Rc::get_mut(&mut self.id) {
+            mem::take(id)
+        } else {
+            self.id().clone()
+        }
+    }
+}
@@ -2,11 +2,12 @@
 // SPDX-License-Identifier: Apache-2.inner
 // SPDX-License-Identifier: MIT
 
-use std::{cell::RefCell, mem, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, mem, rc::Rc};
 
 use crate::{
-    dpi::Position, sealed::IsMenuItemBase, util::AddOp, ContextMenu, IsMenuItem, MenuId,
-    MenuItemKind,
+    dpi::Position, mnemonic, sealed::IsMenuItemBase, spec, util::AddOp, validation, ContextMenu,
+    IsMenuItem, MenuEvent, MenuEventReceiver, MenuEventSender, MenuId, MenuItemKind, MenuItemsIter,
+    MnemonicConflict, MnemonicLabel, SubmenuSpec,
 };
 
 /// A menu that can be added to a [`Menu`] or another [`Submenu`].
@@ -91,9 +92,32 @@ impl Submenu {
         &self.id
     }
 
+    /// Set a sender that receives all the [`MenuEvent`]s coming from this submenu
+    /// when it is shown as a context menu, instead of the global channel,
+    /// the handlers added with [`MenuEvent::add_event_handler`]
+    /// and the handler set with [`MenuEvent::set_event_handler`].
+    ///
+    /// Pass `None` to send these events globally again. The events are also sent globally
+    /// if the receiver of `sender` is dropped.
+    pub fn set_event_sender(&self, sender: Option<MenuEventSender>) {
+        let owner = self.inner.as_ptr() as usize;
+        MenuEvent::set_root_sender(self.inner.borrow().id(), owner, sender)
+    }
+
+    /// Returns a receiver for all the [`MenuEvent`]s coming from this submenu
+    /// when it is shown as a context menu, see [`Submenu::set_event_sender`].
+    pub fn events(&self) -> MenuEventReceiver {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        self.set_event_sender(Some(sender));
+        receiver
+    }
+
     /// Add a menu item to the end of this menu.
     pub fn append(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
-        self.inner.borrow_mut().add_menu_item(item, AddOp::Append)
+        validation::check_add_to_submenu(self, item)?;
+        self.inner.borrow_mut().add_menu_item(item, AddOp::Append)?;
+        validation::added_to_submenu(self, item);
+        Ok(())
     }
 
     /// Add menu items to the end of this submenu. It calls [`Submenu::append`] in a loop.
@@ -107,9 +131,12 @@ impl Submenu {
 
     /// Add a menu item to the beginning of this submenu.
     pub fn prepend(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
+        validation::check_add_to_submenu(self, item)?;
         self.inner
             .borrow_mut()
-            .add_menu_item(item, AddOp::Insert(0))
+            .add_menu_item(item, AddOp::Insert(0))?;
+        validation::added_to_submenu(self, item);
+        Ok(())
     }
 
     /// Add menu items to the beginning of this submenu.
@@ -121,9 +148,12 @@ impl Submenu {
 
     /// Insert a menu item at the specified `postion` in the submenu.
     pub fn insert(&self, item: &dyn IsMenuItem, position: usize) -> crate::Result<()> {
+        validation::check_add_to_submenu(self, item)?;
         self.inner
             .borrow_mut()
-            .add_menu_item(item, AddOp::Insert(position))
+            .add_menu_item(item, AddOp::Insert(position))?;
+        validation::added_to_submenu(self, item);
+        Ok(())
     }
 
     /// Insert menu items at the specified `postion` in the submenu.
@@ -157,6 +187,44 @@ impl Submenu {
         self.inner.borrow().items()
     }
 
+    /// Returns a depth-first iterator over all the items of this submenu and its submenus,
+    /// each item is yielded along with the ids of the submenus leading to it.
+    pub fn iter_all(&self) -> MenuItemsIter {
+        MenuItemsIter::new(self.items())
+    }
+
+    /// Find the item with `id` anywhere in this submenu or its submenus.
+    pub fn find_by_id(&self, id: &MenuId) -> Option<MenuItemKind> {
+        self.iter_all()
+            .map(|(_, item)| item)
+            .find(|item| item.id() == id)
+    }
+
+    /// Creates a new submenu with all the items described by `spec`.
+    ///
+    /// Radio items with the same group name anywhere in `spec` are put in the same [`RadioGroup`](crate::RadioGroup).
+    pub fn from_spec(spec: &SubmenuSpec) -> crate::Result<Self> {
+        spec.build(&mut HashMap::new())
+    }
+
+    /// Returns a [`SubmenuSpec`] describing the current state of this submenu and all its items,
+    /// see [`Menu::to_spec`](crate::Menu::to_spec).
+    pub fn to_spec(&self) -> SubmenuSpec {
+        SubmenuSpec::snapshot(self, &mut spec::radio_group_names(&self.items()))
+    }
+
+    /// Returns every group of items directly in this submenu that share the same mnemonic,
+    /// see [`Menu::mnemonic_conflicts`](crate::Menu::mnemonic_conflicts).
+    pub fn mnemonic_conflicts(&self) -> Vec<MnemonicConflict> {
+        mnemonic::conflicts(self.items())
+    }
+
+    /// Assigns a unique mnemonic to every item directly in this submenu,
+    /// see [`Menu::assign_mnemonics`](crate::Menu::assign_mnemonics).
+    pub fn assign_mnemonics(&self) {
+        mnemonic::assign(self.items())
+    }
+
     /// Get the text for this submenu.
     pub fn text(&self) -> String {
         self.inner.borrow().text()
@@ -169,6 +237,11 @@ impl Submenu {
         self.inner.borrow_mut().set_text(text.as_ref())
     }
 
+    /// Returns the text of this submenu split into its plain text and its mnemonic.
+    pub fn label(&self) -> MnemonicLabel {
+        MnemonicLabel::parse(&self.inner.borrow().text_with_mnemonic())
+    }
+
     /// Get whether this submenu is enabled or not.
     pub fn is_enabled(&self) -> bool {
         self.inner.borrow().is_enabled()
@@ -179,6 +252,47 @@ impl Submenu {
         self.inner.borrow_mut().set_enabled(enabled)
     }
 
+    /// Get whether this submenu is visible or not.
+    pub fn is_visible(&self) -> bool {
+        self.inner.borrow().is_visible()
+    }
+
+    /// Show or hide this submenu, keeping it in its menus.
+    /// The items of a hidden submenu don't trigger their accelerators.
+    pub fn set_visible(&self, visible: bool) {
+        self.inner.borrow_mut().set_visible(visible)
+    }
+
+    /// Get the tooltip of this submenu.
+    pub fn tooltip(&self) -> Option<String> {
+        self.inner.borrow().tooltip()
+    }
+
+    /// Set the tooltip of this submenu, shown when hovering it to describe what it does.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows**: Unsupported.
+    pub fn set_tooltip(&self, tooltip: Option<&str>) {
+        self.inner.borrow_mut().set_tooltip(tooltip)
+    }
+
+    /// Get the secondary text of this submenu.
+    pub fn secondary_text(&self) -> Option<String> {
+        self.inner.borrow().secondary_text()
+    }
+
+    /// Set a secondary text shown at the end of this submenu, like a count or a badge,
+    /// which can be updated without changing the text of the submenu.
+    ///
+    /// ## Platform-specific:
+    ///
+    /// - **Windows**: Shown before the accelerator.
+    /// - **macOS**: Shown as a badge, requires macOS 14 or later.
+    pub fn set_secondary_text(&self, text: Option<&str>) {
+        self.inner.borrow_mut().set_secondary_text(text)
+    }
+
     /// Set this submenu as the Window menu for the application on macOS.
     ///
     /// This will cause macOS to automatically add window-switching items and
@@ -199,6 +313,48 @@ impl Submenu {
         self.inner.borrow_mut().set_as_help_menu_for_nsapp()
     }
 
+    /// Clicks `item` like a user would when this submenu is shown as a context menu,
+    /// see [`Menu::click`](crate::Menu::click).
+    #[cfg(feature = "headless")]
+    pub fn click(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
+        crate::headless::click(self.id(), &self.items(), item)
+    }
+
+    /// Exports this submenu over D-Bus at `object_path` on `connection`, using the `com.canonical.dbusmenu`
+    /// protocol read by global menu bars and StatusNotifierItem trays. The items of this submenu
+    /// become the top-level items of the exported menu.
+    ///
+    /// Changes to the items are signaled to the D-Bus clients, and their clicks are
+    /// reported as [`MenuEvent`]s whose [`root`](MenuEvent::root) is this submenu.
+    /// The submenu is kept alive and exported until the returned [`DbusMenuExport`] is dropped.
+    ///
+    /// [`DbusMenuExport`]: crate::DbusMenuExport
+    #[cfg(all(target_os = "linux", feature = "dbusmenu"))]
+    pub fn export_dbusmenu(
+        &self,
+        connection: &gtk::gio::DBusConnection,
+        object_path: &str,
+    ) -> crate::Result<crate::DbusMenuExport> {
+        let submenu = self.clone();
+        crate::DbusMenuExport::new(connection, object_path, self.id().clone(), move || {
+            submenu.items()
+        })
+    }
+
+    /// Converts this submenu to a [`gio::Menu`](gtk::gio::Menu) model whose items activate
+    /// actions added to `application`. The items of this submenu become the top-level items of the model.
+    ///
+    /// See [`Menu::to_gio_menu`](crate::Menu::to_gio_menu) for how the items and their
+    /// states are converted. The model and the actions stay in sync with this submenu
+    /// until the returned [`GioMenu`] is dropped.
+    ///
+    /// [`GioMenu`]: crate::GioMenu
+    #[cfg(target_os = "linux")]
+    pub fn to_gio_menu(&self, application: &gtk::Application) -> crate::GioMenu {
+        let submenu = self.clone();
+        crate::GioMenu::new(application, self.id().clone(), move || submenu.items())
+    }
+
     /// Convert this submenu into its menu ID.
     pub fn into_id(mut self) -> MenuId {
         // Note: `Rc::into_inner` is available from Rust 1.70
@@ -0,0 +1,338 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! User overrides of menu item accelerators.
+
+use std::collections::BTreeMap;
+
+use crate::{accelerator::KeySequence, Menu, MenuId, MenuItemKind};
+
+/// A map of [`MenuId`] to the key sequence of the menu items with that id,
+/// used to let users rebind menu shortcuts.
+///
+/// Single-stroke key sequences are set as the accelerators of the menu items,
+/// see [`MenuItem::set_key_sequence`](crate::MenuItem::set_key_sequence).
+/// An id mapped to `None` removes the accelerator or key sequence of the menu items with that id.
+///
+/// With the `serde` feature, a keymap is (de)serialized as a map of ids to key sequence strings,
+/// for example `{ "save": "CmdOrCtrl+KeyS", "comment": "Ctrl+KeyK Ctrl+KeyC", "quit": null }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: BTreeMap<MenuId, Option<KeySequence>>,
+}
+
+impl Keymap {
+    /// Creates an empty keymap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the key sequence of the menu items with `id`, replacing the previous binding.
+    ///
+    /// An [`Accelerator`](crate::accelerator::Accelerator) can be converted into a key sequence using [`Into`].
+    pub fn set<I: Into<MenuId>>(&mut self, id: I, key_sequence: Option<KeySequence>) {
+        self.bindings.insert(id.into(), key_sequence);
+    }
+
+    /// Returns the binding of the menu items with `id`, or `None` if this keymap doesn't have one.
+    pub fn get(&self, id: &MenuId) -> Option<Option<&KeySequence>> {
+        self.bindings.get(id).map(Option::as_ref)
+    }
+
+    /// Removes the binding of the menu items with `id`.
+    pub fn remove(&mut self, id: &MenuId) -> Option<Option<KeySequence>> {
+        self.bindings.remove(id)
+    }
+
+    /// Returns an iterator over the bindings of this keymap, sorted by id.
+    pub fn iter(&self) -> impl Iterator<Item = (&MenuId, Option<&KeySequence>)> {
+        self.bindings.iter().map(|(id, s)| (id, s.as_ref()))
+    }
+
+    /// Returns the number of bindings in this keymap.
+    pub fn len(&self) -> usize {
+        self.bindings.len()
+    }
+
+    /// Returns `true` if this keymap doesn't have any bindings.
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+}
+
+impl<I: Into<MenuId>> FromIterator<(I, Option<KeySequence>)> for Keymap {
+    fn from_iter<T: IntoIterator<Item = (I, Option<KeySequence>)>>(iter: T) -> Self {
+        Self {
+            bindings: iter.into_iter().map(|(id, s)| (id.into(), s)).collect(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Keymap {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bindings: BTreeMap<&MenuId, Option<String>> = self
+            .bindings
+            .iter()
+            .map(|(id, s)| (id, s.as_ref().map(|s| s.to_string())))
+            .collect();
+        serde::Serialize::serialize(&bindings, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Keymap {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bindings: BTreeMap<MenuId, Option<String>> =
+            serde::Deserialize::deserialize(deserializer)?;
+        let bindings = bindings
+            .into_iter()
+            .map(|(id, s)| {
+                let s = s
+                    .map(|s| s.parse::<KeySequence>())
+                    .transpose()
+                    .map_err(serde::de::Error::custom)?;
+                Ok((id, s))
+            })
+            .collect::<Result<_, D::Error>>()?;
+        Ok(Self { bindings })
+    }
+}
+
+/// Returns every item in `menu`'s tree that can have an accelerator, once.
+fn accelerator_items(menu: &Menu) -> Vec<MenuItemKind> {
+    let mut items: Vec<MenuItemKind> = Vec::new();
+    for (_, item) in menu.iter_all() {
+        let can_have_accelerator = matches!(
+            item,
+            MenuItemKind::MenuItem(_)
+                | MenuItemKind::Check(_)
+                | MenuItemKind::Icon(_)
+                | MenuItemKind::Radio(_)
+        );
+        if can_have_accelerator && !items.iter().any(|i| i.child_ptr() == item.child_ptr()) {
+            items.push(item);
+        }
+    }
+    items
+}
+
+/// Returns the key sequence of `item`, which is a single-stroke key sequence if it only has an accelerator.
+fn key_sequence(item: &MenuItemKind) -> Option<KeySequence> {
+    let child = item.child();
+    child
+        .key_sequence()
+        .or_else(|| child.accelerator().map(KeySequence::from))
+}
+
+fn set_key_sequence(item: &MenuItemKind, key_sequence: Option<KeySequence>) -> crate::Result<()> {
+    match item {
+        MenuItemKind::MenuItem(i) => i.set_key_sequence(key_sequence),
+        MenuItemKind::Check(i) => i.set_key_sequence(key_sequence),
+        MenuItemKind::Icon(i) => i.set_key_sequence(key_sequence),
+        MenuItemKind::Radio(i) => i.set_key_sequence(key_sequence),
+        MenuItemKind::Submenu(_) | MenuItemKind::Predefined(_) => Ok(()),
+    }
+}
+
+/// Sets the key sequences of `changes` all at once, so key sequences can be swapped between items
+/// in the strict accelerator mode, and restores the previous key sequences if one of them fails.
+///
+/// Items whose key sequence doesn't change are left untouched,
+/// returns the items that changed along with their previous key sequences.
+fn set_key_sequences(
+    changes: Vec<(MenuItemKind, Option<KeySequence>)>,
+) -> crate::Result<Vec<(MenuItemKind, Option<KeySequence>)>> {
+    let changes = changes
+        .into_iter()
+        .map(|(item, key_sequence)| {
+            let previous = self::key_sequence(&item);
+            (item, key_sequence, previous)
+        })
+        .filter(|(_, key_sequence, previous)| key_sequence != previous)
+        .collect::<Vec<_>>();
+
+    for (item, _, _) in &changes {
+        set_key_sequence(item, None)?;
+    }
+
+    for (item, key_sequence, _) in &changes {
+        if let Err(e) = set_key_sequence(item, key_sequence.clone()) {
+            for (item, _, _) in &changes {
+                let _ = set_key_sequence(item, None);
+            }
+            for (item, _, previous) in changes {
+                let _ = set_key_sequence(&item, previous);
+            }
+            return Err(e);
+        }
+    }
+
+    Ok(changes
+        .into_iter()
+        .map(|(item, _, previous)| (item, previous))
+        .collect())
+}
+
+pub(crate) fn apply(menu: &Menu, keymap: &Keymap) -> crate::Result<Vec<MenuId>> {
+    let items = accelerator_items(menu);
+
+    let unknown_ids = keymap
+        .bindings
+        .keys()
+        .filter(|id| !items.iter().any(|i| i.id() == *id))
+        .cloned()
+        .collect();
+
+    let changes = items
+        .into_iter()
+        .filter_map(|item| {
+            let key_sequence = keymap.get(item.id())?.cloned();
+            Some((item, key_sequence))
+        })
+        .collect();
+    for (item, previous) in set_key_sequences(changes)? {
+        let mut child = item.child_mut();
+        if child.keymap_default().is_none() {
+            child.set_keymap_default(Some(previous));
+        }
+    }
+
+    Ok(unknown_ids)
+}
+
+pub(crate) fn export(menu: &Menu) -> Keymap {
+    accelerator_items(menu)
+        .into_iter()
+        .map(|item| (item.id().clone(), key_sequence(&item)))
+        .collect()
+}
+
+pub(crate) fn revert(menu: &Menu) -> crate::Result<()> {
+    let changes = accelerator_items(menu)
+        .into_iter()
+        .filter_map(|item| {
+            let key_sequence = item.child().keymap_default()?;
+            Some((item, key_sequence))
+        })
+        .collect::<Vec<_>>();
+    let items = changes
+        .iter()
+        .map(|(item, _)| item.clone())
+        .collect::<Vec<_>>();
+    set_key_sequences(changes)?;
+
+    for item in items {
+        item.child_mut().set_keymap_default(None);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{accelerator::KeySequence, CheckMenuItem, Menu, MenuId, MenuItem, Submenu};
+
+    use super::Keymap;
+
+    fn key_sequence(s: &str) -> Option<KeySequence> {
+        Some(s.parse().unwrap())
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn apply_export_and_revert_keymap() {
+        let save = MenuItem::with_id("save", "Save", true, Some("Ctrl+KeyS".parse().unwrap()));
+        let open = MenuItem::with_id("open", "Open", true, Some("Ctrl+KeyO".parse().unwrap()));
+        let wrap = CheckMenuItem::with_id("wrap", "Wrap", true, false, None);
+        let comment = MenuItem::with_id("comment", "Comment", true, None);
+        comment
+            .set_key_sequence(key_sequence("Ctrl+KeyK Ctrl+KeyC"))
+            .unwrap();
+        let submenu = Submenu::with_items("File", true, &[&save, &open, &wrap, &comment]).unwrap();
+        let menu = Menu::with_items(&[&submenu]).unwrap();
+        menu.set_strict_accelerators(true);
+
+        // swapping accelerators doesn't conflict in the strict accelerator mode
+        let keymap: Keymap = [
+            ("save", key_sequence("Ctrl+KeyO")),
+            ("open", key_sequence("Ctrl+KeyS")),
+            ("wrap", key_sequence("Alt+KeyZ Alt+KeyW")),
+            ("missing", None),
+        ]
+        .into_iter()
+        .collect();
+        let unknown = menu.apply_keymap(&keymap).unwrap();
+        assert_eq!(unknown, vec![MenuId::new("missing")]);
+
+        let exported = menu.keymap();
+        assert_eq!(exported.len(), 4);
+        assert_eq!(
+            exported.get(save.id()),
+            Some(key_sequence("Ctrl+KeyO").as_ref())
+        );
+        assert_eq!(
+            exported.get(open.id()),
+            Some(key_sequence("Ctrl+KeyS").as_ref())
+        );
+        assert_eq!(
+            exported.get(wrap.id()),
+            Some(key_sequence("Alt+KeyZ Alt+KeyW").as_ref())
+        );
+        // items that are not in the keymap keep their key sequence
+        assert_eq!(
+            exported.get(comment.id()),
+            Some(key_sequence("Ctrl+KeyK Ctrl+KeyC").as_ref())
+        );
+
+        // a conflicting keymap is not applied at all
+        let conflicting: Keymap = [
+            ("comment", key_sequence("Ctrl+KeyK Ctrl+KeyU")),
+            ("wrap", key_sequence("Ctrl+KeyS")),
+        ]
+        .into_iter()
+        .collect();
+        assert!(menu.apply_keymap(&conflicting).is_err());
+        assert_eq!(menu.keymap(), exported);
+
+        menu.revert_keymap().unwrap();
+        let reverted = menu.keymap();
+        assert_eq!(
+            reverted.get(save.id()),
+            Some(key_sequence("Ctrl+KeyS").as_ref())
+        );
+        assert_eq!(
+            reverted.get(open.id()),
+            Some(key_sequence("Ctrl+KeyO").as_ref())
+        );
+        assert_eq!(reverted.get(wrap.id()), Some(None));
+        assert_eq!(
+            reverted.get(comment.id()),
+            Some(key_sequence("Ctrl+KeyK Ctrl+KeyC").as_ref())
+        );
+
+        // items changed in code after reverting are not reverted again
+        save.set_accelerator(None).unwrap();
+        menu.revert_keymap().unwrap();
+        assert_eq!(menu.keymap().get(save.id()), Some(None));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn keymap_serde_round_trip() {
+        let keymap: Keymap = [
+            ("save", key_sequence("CmdOrCtrl+KeyS")),
+            ("comment", key_sequence("Ctrl+KeyK Ctrl+KeyC")),
+            ("quit", None),
+        ]
+        .into_iter()
+        .collect();
+
+        let json = serde_json::to_string(&keymap).unwrap();
+        assert_eq!(
+            json,
+            r#"{"comment":"control+KeyK control+KeyC","quit":null,"save":"CmdOrCtrl+KeyS"}"#
+        );
+        assert_eq!(serde_json::from_str::<Keymap>(&json).unwrap(), keymap);
+    }
+}
@@ -20,7 +20,7 @@
 //!
 //! # Dependencies (Linux Only)
 //!
-//! `gtk` is used for menus and `libxdo` is used to make the predfined `Copy`, `Cut`, `Paste` and `SelectAll` menu items work. Be sure to install following packages before building:
+//! `gtk` is used for menus and `libxdo` is used to make the predefined `Copy`, `Cut`, `Paste` and `SelectAll` menu items work in widgets that don't handle the `gtk` clipboard signals. Be sure to install following packages before building:
 //!
 //! #### Arch Linux / Manjaro:
 //!
@@ -125,20 +125,36 @@
 //!     }
 //! }
 //! ```
-
-use crossbeam_channel::{unbounded, Receiver, Sender};
-use once_cell::sync::{Lazy, OnceCell};
+//!
+//! With the `futures` feature enabled, you can use [`MenuEvent::stream`] to get a `Stream`
+//! that wakes your async task as soon as a menu event is emitted.
+//!
+//! ```ignore
+//! # use futures::StreamExt;
+//! let mut events = muda::MenuEvent::stream();
+//! while let Some(event) = events.next().await {
+//!     println!("{:?}", event.id);
+//! }
+//! ```
 
 mod about_metadata;
 pub mod accelerator;
 mod builders;
+mod dispatcher;
 mod error;
+mod event;
+#[cfg(feature = "headless")]
+mod headless;
 mod icon;
 mod items;
+mod keymap;
 mod menu;
 mod menu_id;
+mod mnemonic;
 mod platform_impl;
+mod spec;
 mod util;
+mod validation;
 
 #[cfg(target_os = "macos")]
 #[macro_use]
@@ -146,12 +162,22 @@ extern crate objc;
 
 pub use about_metadata::AboutMetadata;
 pub use builders::*;
+pub use dispatcher::AcceleratorDispatcher;
 pub use dpi;
 pub use error::*;
+pub use event::*;
 pub use icon::{BadIcon, Icon, NativeIcon};
 pub use items::*;
+pub use keymap::Keymap;
 pub use menu::Menu;
 pub use menu_id::MenuId;
+pub use mnemonic::{MnemonicConflict, MnemonicLabel};
+#[cfg(all(target_os = "linux", feature = "dbusmenu"))]
+pub use platform_impl::DbusMenuExport;
+#[cfg(target_os = "linux")]
+pub use platform_impl::GioMenu;
+pub use spec::*;
+pub use validation::{AcceleratorConflict, MenuValidationIssue};
 
 /// An enumeration of all available menu types, useful to match against
 /// the items returned from [`Menu::items`] or [`Submenu::items`]
@@ -162,6 +188,7 @@ pub enum MenuItemKind {
     Predefined(PredefinedMenuItem),
     Check(CheckMenuItem),
     Icon(IconMenuItem),
+    Radio(RadioMenuItem),
 }
 
 impl MenuItemKind {
@@ -173,6 +200,7 @@ impl MenuItemKind {
             MenuItemKind::Predefined(i) => i.id(),
             MenuItemKind::Check(i) => i.id(),
             MenuItemKind::Icon(i) => i.id(),
+            MenuItemKind::Radio(i) => i.id(),
         }
     }
 
@@ -256,6 +284,22 @@ impl MenuItemKind {
         }
     }
 
+    /// Casts this item to a [`RadioMenuItem`], and returns `None` if it wasn't.
+    pub fn as_radio_menuitem(&self) -> Option<&RadioMenuItem> {
+        match self {
+            MenuItemKind::Radio(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// Casts this item to a [`RadioMenuItem`], and panics if it wasn't.
+    pub fn as_radio_menuitem_unchecked(&self) -> &RadioMenuItem {
+        match self {
+            MenuItemKind::Radio(i) => i,
+            _ => panic!("Not a RadioMenuItem"),
+        }
+    }
+
     /// Convert this item into its menu ID.
     pub fn into_id(self) -> MenuId {
         match self {
@@ -264,6 +308,48 @@ impl MenuItemKind {
             MenuItemKind::Predefined(i) => i.into_id(),
             MenuItemKind::Check(i) => i.into_id(),
             MenuItemKind::Icon(i) => i.into_id(),
+            MenuItemKind::Radio(i) => i.into_id(),
+        }
+    }
+}
+
+/// A depth-first iterator over all the items of a [`Menu`] or a [`Submenu`] and their submenus,
+/// returned from [`Menu::iter_all`] and [`Submenu::iter_all`].
+///
+/// Each item is yielded along with its parent path, the ids of the submenus leading to it
+/// starting from the outermost one. A submenu is yielded right before its own items.
+pub struct MenuItemsIter {
+    stack: Vec<(Vec<MenuId>, std::vec::IntoIter<MenuItemKind>)>,
+}
+
+impl MenuItemsIter {
+    pub(crate) fn new(items: Vec<MenuItemKind>) -> Self {
+        Self {
+            stack: vec![(Vec::new(), items.into_iter())],
+        }
+    }
+}
+
+impl Iterator for MenuItemsIter {
+    type Item = (Vec<MenuId>, MenuItemKind);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (path, items) = self.stack.last_mut()?;
+            match items.next() {
+                Some(item) => {
+                    let path = path.clone();
+                    if let MenuItemKind::Submenu(submenu) = &item {
+                        let mut submenu_path = path.clone();
+                        submenu_path.push(submenu.id().clone());
+                        self.stack.push((submenu_path, submenu.items().into_iter()));
+                    }
+                    return Some((path, item));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
         }
     }
 }
@@ -290,6 +376,7 @@ pub(crate) enum MenuItemType {
     Predefined,
     Check,
     Icon,
+    Radio,
 }
 
 impl Default for MenuItemType {
@@ -343,57 +430,3 @@ pub trait ContextMenu {
     #[cfg(target_os = "macos")]
     fn ns_menu(&self) -> *mut std::ffi::c_void;
 }
-
-/// Describes a menu event emitted when a menu item is activated
-#[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct MenuEvent {
-    /// Id of the menu item which triggered this event
-    pub id: MenuId,
-}
-
-/// A reciever that could be used to listen to menu events.
-pub type MenuEventReceiver = Receiver<MenuEvent>;
-type MenuEventHandler = Box<dyn Fn(MenuEvent) + Send + Sync + 'static>;
-
-static MENU_CHANNEL: Lazy<(Sender<MenuEvent>, MenuEventReceiver)> = Lazy::new(unbounded);
-static MENU_EVENT_HANDLER: OnceCell<Option<MenuEventHandler>> = OnceCell::new();
-
-impl MenuEvent {
-    /// Returns the id of the menu item which triggered this event
-    pub fn id(&self) -> &MenuId {
-        &self.id
-    }
-
-    /// Gets a reference to the event channel's [`MenuEventReceiver`]
-    /// which can be used to listen for menu events.
-    ///
-    /// ## Note
-    ///
-    /// This will not receive any events if [`MenuEvent::set_event_handler`] has been called with a `Some` value.
-    pub fn receiver<'a>() -> &'a MenuEventReceiver {
-        &MENU_CHANNEL.1
-    }
-
-    /// Set a handler to be called for new events. Useful for implementing custom event sender.
-    ///
-    /// ## Note
-    ///
-    /// Calling this function with a `Some` value,
-    /// will not send new events to the channel associated with [`MenuEvent::receiver`]
-    pub fn set_event_handler<F: Fn(MenuEvent) + Send + Sync + 'static>(f: Option<F>) {
-        if let Some(f) = f {
-            let _ = MENU_EVENT_HANDLER.set(Some(Box::new(f)));
-        } else {
-            let _ = MENU_EVENT_HANDLER.set(None);
-        }
-    }
-
-    pub(crate) fn send(event: MenuEvent) {
-        if let Some(handler) = MENU_EVENT_HANDLER.get_or_init(|| None) {
-            handler(event);
-        } else {
-            let _ = MENU_CHANNEL.0.send(event);
-        }
-    }
-}
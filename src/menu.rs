@@ -2,16 +2,20 @@
 // SPDX-License-Identifier: Apache-2.inner
 // SPDX-License-Identifier: MIT
 
-use std::{cell::RefCell, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
 
-use crate::{dpi::Position, util::AddOp, ContextMenu, IsMenuItem, MenuId, MenuItemKind};
+use crate::{
+    dpi::Position, keymap, mnemonic, spec, util::AddOp, validation, AcceleratorConflict,
+    ContextMenu, IsMenuItem, Keymap, MenuEvent, MenuEventReceiver, MenuEventSender, MenuId,
+    MenuItemKind, MenuItemSpec, MenuItemsIter, MenuSpec, MenuValidationIssue, MnemonicConflict,
+};
 
 /// A root menu that can be added to a Window on Windows and Linux
 /// and used as the app global menu on macOS.
 #[derive(Clone)]
 pub struct Menu {
-    id: Rc<MenuId>,
-    inner: Rc<RefCell<crate::platform_impl::Menu>>,
+    pub(crate) id: Rc<MenuId>,
+    pub(crate) inner: Rc<RefCell<crate::platform_impl::Menu>>,
 }
 
 impl Default for Menu {
@@ -61,6 +65,110 @@ impl Menu {
         &self.id
     }
 
+    /// Enable or disable the strict id mode of this menu, disabled by default.
+    ///
+    /// In the strict id mode, adding an item to this menu or to any submenu in its tree
+    /// returns [`Error::DuplicateId`](crate::Error::DuplicateId) if another item
+    /// in the tree already has the same id. Adding the same item in more than one place is allowed.
+    pub fn set_strict_ids(&self, strict: bool) {
+        self.inner.borrow_mut().set_strict_ids(strict)
+    }
+
+    /// Returns whether this menu is in the strict id mode, see [`Menu::set_strict_ids`].
+    pub fn is_strict_ids(&self) -> bool {
+        self.inner.borrow().is_strict_ids()
+    }
+
+    /// Enable or disable the strict accelerator mode of this menu, disabled by default.
+    ///
+    /// In the strict accelerator mode, adding an item to this menu or to any submenu in its tree
+    /// and setting the accelerator or key sequence of an item in its tree returns
+    /// [`Error::AcceleratorConflict`](crate::Error::AcceleratorConflict)
+    /// if another item in the tree already uses the same accelerator or key sequence,
+    /// or one that starts with it or is the start of it, see [`AcceleratorConflict`].
+    pub fn set_strict_accelerators(&self, strict: bool) {
+        self.inner.borrow_mut().set_strict_accelerators(strict)
+    }
+
+    /// Returns whether this menu is in the strict accelerator mode, see [`Menu::set_strict_accelerators`].
+    pub fn is_strict_accelerators(&self) -> bool {
+        self.inner.borrow().is_strict_accelerators()
+    }
+
+    /// Returns every group of items in this menu tree that share the same accelerator or key sequence,
+    /// or whose key sequences start with the key sequence of another item, see [`AcceleratorConflict`].
+    ///
+    /// Key sequences are compared stroke by stroke, and two strokes are the same if they have
+    /// the same modifiers, key and character. `CmdOrCtrl` is resolved to the modifier of the current
+    /// platform when an accelerator is created, so it matches that modifier.
+    pub fn accelerator_conflicts(&self) -> Vec<AcceleratorConflict> {
+        validation::accelerator_conflicts(self)
+    }
+
+    /// Sets the accelerators or key sequences of all the items in this menu tree that have an id found in `keymap`,
+    /// and returns the ids of `keymap` that don't belong to any item which can have an accelerator.
+    ///
+    /// If setting one of the key sequences fails, for example because of a conflict in the
+    /// [strict accelerator mode](Menu::set_strict_accelerators), none of them are changed.
+    pub fn apply_keymap(&self, keymap: &Keymap) -> crate::Result<Vec<MenuId>> {
+        keymap::apply(self, keymap)
+    }
+
+    /// Returns the current accelerators or key sequences of all the items in this menu tree which can have an accelerator.
+    pub fn keymap(&self) -> Keymap {
+        keymap::export(self)
+    }
+
+    /// Reverts the accelerators and key sequences of all the items in this menu tree that were changed by
+    /// [`Menu::apply_keymap`] to the ones they had before the first keymap was applied,
+    /// which are the ones they were created with unless they were changed in code.
+    pub fn revert_keymap(&self) -> crate::Result<()> {
+        keymap::revert(self)
+    }
+
+    /// Returns every group of items directly in this menu that share the same mnemonic.
+    ///
+    /// Mnemonics are compared case-insensitively, and the items of submenus are not checked,
+    /// use [`Submenu::mnemonic_conflicts`](crate::Submenu::mnemonic_conflicts) for them.
+    pub fn mnemonic_conflicts(&self) -> Vec<MnemonicConflict> {
+        mnemonic::conflicts(self.items())
+    }
+
+    /// Assigns a mnemonic to every item directly in this menu that doesn't have one
+    /// or shares it with a previous item, picking the first unused letter or digit of its text
+    /// and preferring the first letter of each word.
+    ///
+    /// Items that have no unused character left keep their text as is.
+    /// The texts of [`PredefinedMenuItem`](crate::PredefinedMenuItem)s are never changed.
+    pub fn assign_mnemonics(&self) {
+        mnemonic::assign(self.items())
+    }
+
+    /// Check this menu tree for items sharing the same id, empty submenus
+    /// and items sharing the same accelerator, and returns all the issues found.
+    pub fn validate(&self) -> Vec<MenuValidationIssue> {
+        validation::validate(self)
+    }
+
+    /// Set a sender that receives all the [`MenuEvent`]s coming from this menu
+    /// when it is used as the menu of a window, the app menu or a context menu, instead of the global channel,
+    /// the handlers added with [`MenuEvent::add_event_handler`]
+    /// and the handler set with [`MenuEvent::set_event_handler`].
+    ///
+    /// Pass `None` to send these events globally again. The events are also sent globally
+    /// if the receiver of `sender` is dropped.
+    pub fn set_event_sender(&self, sender: Option<MenuEventSender>) {
+        let owner = self.inner.as_ptr() as usize;
+        MenuEvent::set_root_sender(self.inner.borrow().id(), owner, sender)
+    }
+
+    /// Returns a receiver for all the [`MenuEvent`]s coming from this menu, see [`Menu::set_event_sender`].
+    pub fn events(&self) -> MenuEventReceiver {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        self.set_event_sender(Some(sender));
+        receiver
+    }
+
     /// Add a menu item to the end of this menu.
     ///
     /// ## Platform-spcific:
@@ -69,7 +177,10 @@ impl Menu {
     ///
     /// [`Submenu`]: crate::Submenu
     pub fn append(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
-        self.inner.borrow_mut().add_menu_item(item, AddOp::Append)
+        validation::check_add(self, item)?;
+        self.inner.borrow_mut().add_menu_item(item, AddOp::Append)?;
+        validation::added(self, item);
+        Ok(())
     }
 
     /// Add menu items to the end of this menu. It calls [`Menu::append`] in a loop internally.
@@ -95,9 +206,12 @@ impl Menu {
     ///
     /// [`Submenu`]: crate::Submenu
     pub fn prepend(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
+        validation::check_add(self, item)?;
         self.inner
             .borrow_mut()
-            .add_menu_item(item, AddOp::Insert(0))
+            .add_menu_item(item, AddOp::Insert(0))?;
+        validation::added(self, item);
+        Ok(())
     }
 
     /// Add menu items to the beginning of this menu. It calls [`Menu::insert_items`] with position of `0` internally.
@@ -119,9 +233,12 @@ impl Menu {
     ///
     /// [`Submenu`]: crate::Submenu
     pub fn insert(&self, item: &dyn IsMenuItem, position: usize) -> crate::Result<()> {
+        validation::check_add(self, item)?;
         self.inner
             .borrow_mut()
-            .add_menu_item(item, AddOp::Insert(position))
+            .add_menu_item(item, AddOp::Insert(position))?;
+        validation::added(self, item);
+        Ok(())
     }
 
     /// Insert menu items at the specified `postion` in the menu.
@@ -161,6 +278,53 @@ impl Menu {
         self.inner.borrow().items()
     }
 
+    /// Returns a depth-first iterator over all the items of this menu and its submenus,
+    /// each item is yielded along with the ids of the submenus leading to it.
+    pub fn iter_all(&self) -> MenuItemsIter {
+        MenuItemsIter::new(self.items())
+    }
+
+    /// Find the item with `id` anywhere in this menu or its submenus.
+    pub fn find_by_id(&self, id: &MenuId) -> Option<MenuItemKind> {
+        self.iter_all()
+            .map(|(_, item)| item)
+            .find(|item| item.id() == id)
+    }
+
+    /// Creates a new menu with all the items described by `spec`.
+    ///
+    /// Radio items with the same group name anywhere in `spec` are put in the same [`RadioGroup`](crate::RadioGroup).
+    pub fn from_spec(spec: &MenuSpec) -> crate::Result<Self> {
+        let menu = match &spec.id {
+            Some(id) => Self::with_id(id.clone()),
+            None => Self::new(),
+        };
+        let mut groups = HashMap::new();
+        for item in &spec.items {
+            menu.append(item.build(&mut groups)?.as_ref())?;
+        }
+        Ok(menu)
+    }
+
+    /// Returns a [`MenuSpec`] describing the current state of this menu and all its items.
+    ///
+    /// Radio groups keep the name they were created with, see [`RadioGroup::with_name`],
+    /// other radio groups are named `group-1`, `group-2`, etc. in the order they are found.
+    /// Icon menu items only keep their native icon.
+    ///
+    /// [`RadioGroup::with_name`]: crate::RadioGroup::with_name
+    pub fn to_spec(&self) -> MenuSpec {
+        let items = self.items();
+        let mut groups = spec::radio_group_names(&items);
+        MenuSpec {
+            id: Some(self.id().clone()),
+            items: items
+                .iter()
+                .map(|i| MenuItemSpec::snapshot(i, &mut groups))
+                .collect(),
+        }
+    }
+
     /// Adds this menu to a [`gtk::Window`]
     ///
     /// - `container`: this is an optional paramter to specify a container for the [`gtk::MenuBar`],
@@ -295,6 +459,69 @@ impl Menu {
         self.inner.borrow().gtk_menubar_for_gtk_window(window)
     }
 
+    /// Exports this menu over D-Bus at `object_path` on `connection`, using the `com.canonical.dbusmenu`
+    /// protocol read by global menu bars and StatusNotifierItem trays.
+    ///
+    /// Changes to the items of this menu are signaled to the D-Bus clients, and their clicks are
+    /// reported as [`MenuEvent`]s whose [`root`](MenuEvent::root) is this menu.
+    /// The menu is kept alive and exported until the returned [`DbusMenuExport`] is dropped.
+    ///
+    /// ## Example:
+    /// ```no_run
+    /// let connection = gtk::gio::bus_get_sync(gtk::gio::BusType::Session, gtk::gio::Cancellable::NONE).unwrap();
+    /// let menu = muda::Menu::new();
+    /// // -- snip, add your menu items --
+    /// let export = menu.export_dbusmenu(&connection, "/MenuBar").unwrap();
+    /// // then pass the object path to the global menu or tray, and keep `export` around
+    /// ```
+    ///
+    /// [`DbusMenuExport`]: crate::DbusMenuExport
+    #[cfg(all(target_os = "linux", feature = "dbusmenu"))]
+    pub fn export_dbusmenu(
+        &self,
+        connection: &gtk::gio::DBusConnection,
+        object_path: &str,
+    ) -> crate::Result<crate::DbusMenuExport> {
+        let menu = self.clone();
+        crate::DbusMenuExport::new(connection, object_path, self.id().clone(), move || {
+            menu.items()
+        })
+    }
+
+    /// Converts this menu to a [`gio::Menu`](gtk::gio::Menu) model whose items activate actions
+    /// added to `application`, for apps that use [`set_menubar`](gtk::prelude::GtkApplicationExt::set_menubar) or
+    /// build their menus from a model.
+    ///
+    /// Enabled and checked states become the states of the actions, accelerators are registered
+    /// with `application`, and activating the actions is reported as [`MenuEvent`]s whose
+    /// [`root`](MenuEvent::root) is this menu. The model and the actions stay in sync with
+    /// this menu until the returned [`GioMenu`] is dropped.
+    ///
+    /// ## Example:
+    /// ```no_run
+    /// # use gtk::prelude::*;
+    /// let application = gtk::Application::new(Some("com.example.app"), Default::default());
+    /// let menu = muda::Menu::new();
+    /// // -- snip, add your menu items --
+    /// // the menubar is only synced with `menu` while `gio_menu` is alive,
+    /// // so keep it until the application shuts down
+    /// let gio_menu = std::rc::Rc::new(std::cell::RefCell::new(None));
+    /// let startup_menu = gio_menu.clone();
+    /// application.connect_startup(move |application| {
+    ///     let converted = menu.to_gio_menu(application);
+    ///     application.set_menubar(Some(converted.menu_model()));
+    ///     *startup_menu.borrow_mut() = Some(converted);
+    /// });
+    /// application.connect_shutdown(move |_| drop(gio_menu.take()));
+    /// ```
+    ///
+    /// [`GioMenu`]: crate::GioMenu
+    #[cfg(target_os = "linux")]
+    pub fn to_gio_menu(&self, application: &gtk::Application) -> crate::GioMenu {
+        let menu = self.clone();
+        crate::GioMenu::new(application, self.id().clone(), move || menu.items())
+    }
+
     /// Returns whether this menu visible on a on a win32 window
     #[cfg(target_os = "windows")]
     pub fn is_visible_on_hwnd(&self, hwnd: isize) -> bool {
@@ -312,6 +539,86 @@ impl Menu {
     pub fn remove_for_nsapp(&self) {
         self.inner.borrow_mut().remove_for_nsapp()
     }
+
+    /// Clicks `item` like a user would, which toggles check and radio items and sends
+    /// a [`MenuEvent`] from [`MenuEventSource::Mouse`](crate::MenuEventSource::Mouse).
+    ///
+    /// `item` can be in a submenu of this menu. Clicks are ignored when `item`, or a submenu
+    /// containing it, is disabled or hidden. Predefined items send no event, as on the native menus.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`crate::Error::NotAChildOfThisMenu`] if `item` isn't in this menu.
+    #[cfg(feature = "headless")]
+    pub fn click(&self, item: &dyn IsMenuItem) -> crate::Result<()> {
+        crate::headless::click(self.id(), &self.items(), item)
+    }
+
+    /// Adds this menu to the headless window `window`, which is any number identifying
+    /// a window of your tests, so keys pressed with [`Menu::press_key`] in it reach this menu.
+    ///
+    /// No native window is involved, so this works without a display.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`crate::Error::AlreadyInitialized`] if this menu was already added to `window`.
+    #[cfg(feature = "headless")]
+    pub fn init_for_headless_window(&self, window: u64) -> crate::Result<()> {
+        crate::headless::init_for_window(self, window)
+    }
+
+    /// Removes this menu from the headless window `window`.
+    #[cfg(feature = "headless")]
+    pub fn remove_for_headless_window(&self, window: u64) -> crate::Result<()> {
+        crate::headless::remove_for_window(self, window)
+    }
+
+    /// Hides this menu from the headless window `window`, key presses in it are then ignored.
+    #[cfg(feature = "headless")]
+    pub fn hide_for_headless_window(&self, window: u64) -> crate::Result<()> {
+        crate::headless::set_visible_on_window(self, window, false)
+    }
+
+    /// Shows this menu on the headless window `window`.
+    #[cfg(feature = "headless")]
+    pub fn show_for_headless_window(&self, window: u64) -> crate::Result<()> {
+        crate::headless::set_visible_on_window(self, window, true)
+    }
+
+    /// Returns whether this menu is visible on the headless window `window`.
+    #[cfg(feature = "headless")]
+    pub fn is_visible_on_headless_window(&self, window: u64) -> bool {
+        crate::headless::is_visible_on_window(self, window)
+    }
+
+    /// Presses a key in the headless window `window`, which triggers the accelerators and key sequences
+    /// of this menu like [`AcceleratorDispatcher::handle_key_event`](crate::AcceleratorDispatcher::handle_key_event) does,
+    /// with [`MenuEventSource::Accelerator`](crate::MenuEventSource::Accelerator).
+    ///
+    /// Returns the id of the item that handled the key press, which is never the case if this menu
+    /// wasn't added to `window` with [`Menu::init_for_headless_window`] or is hidden on it.
+    #[cfg(feature = "headless")]
+    pub fn press_key(
+        &self,
+        window: u64,
+        modifiers: impl std::borrow::Borrow<crate::accelerator::Modifiers>,
+        code: impl std::borrow::Borrow<crate::accelerator::Code>,
+        key: impl std::borrow::Borrow<crate::accelerator::Key>,
+    ) -> Option<MenuId> {
+        crate::headless::press_key(self, window, modifiers, code, key)
+    }
+
+    /// Presses the keys of `accelerator` in the headless window `window`, see [`Menu::press_key`].
+    ///
+    /// Multi-stroke [`KeySequence`](crate::accelerator::KeySequence)s are pressed one stroke at a time.
+    #[cfg(feature = "headless")]
+    pub fn press_accelerator(
+        &self,
+        window: u64,
+        accelerator: &crate::accelerator::Accelerator,
+    ) -> Option<MenuId> {
+        crate::headless::press_accelerator(self, window, accelerator)
+    }
 }
 
 impl ContextMenu for Menu {
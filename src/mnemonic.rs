@@ -0,0 +1,259 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Mnemonics (access keys) of menu item labels.
+
+use std::fmt;
+
+use crate::MenuItemKind;
+
+/// A menu item label split into its plain text and its mnemonic.
+///
+/// Menu item texts use the `&` convention to mark their mnemonic, the character
+/// after the first single `&` is the mnemonic and `&&` is a literal `&`.
+/// [`MnemonicLabel::parse`] reads this convention and the [`Display`](fmt::Display)
+/// implementation writes it back, so a label can be passed to `set_text` after being changed.
+///
+/// ## Examples
+///
+/// ```
+/// # use muda::MnemonicLabel;
+/// let label = MnemonicLabel::parse("Save &As");
+/// assert_eq!(label.text(), "Save As");
+/// assert_eq!(label.mnemonic(), Some('A'));
+/// assert_eq!(label.to_string(), "Save &As");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MnemonicLabel {
+    text: String,
+    position: Option<usize>,
+}
+
+impl MnemonicLabel {
+    /// Creates a label from its plain `text` and the position of its mnemonic,
+    /// counted in characters of `text`.
+    ///
+    /// A position past the end of `text` is ignored.
+    pub fn new<S: Into<String>>(text: S, position: Option<usize>) -> Self {
+        let text = text.into();
+        let position = position.filter(|p| *p < text.chars().count());
+        Self { text, position }
+    }
+
+    /// Parses a menu item text that marks its mnemonic with `&`.
+    pub fn parse(label: &str) -> Self {
+        let mut text = String::with_capacity(label.len());
+        let mut position = None;
+        let mut len = 0;
+        let mut chars = label.chars();
+        while let Some(c) = chars.next() {
+            if c == '&' {
+                match chars.next() {
+                    Some('&') => {}
+                    Some(next) => {
+                        position.get_or_insert(len);
+                        text.push(next);
+                        len += 1;
+                        continue;
+                    }
+                    None => break,
+                }
+            }
+            text.push(c);
+            len += 1;
+        }
+        Self { text, position }
+    }
+
+    /// Returns the text of this label without the mnemonic markers.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the mnemonic of this label, as it appears in its text.
+    pub fn mnemonic(&self) -> Option<char> {
+        self.position.and_then(|p| self.text.chars().nth(p))
+    }
+
+    /// Returns the position of the mnemonic in the text of this label, counted in characters.
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+}
+
+impl fmt::Display for MnemonicLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, c) in self.text.chars().enumerate() {
+            if Some(i) == self.position {
+                f.write_str("&")?;
+            }
+            if c == '&' {
+                f.write_str("&&")?;
+            } else {
+                write!(f, "{c}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for MnemonicLabel {
+    fn from(label: &str) -> Self {
+        Self::parse(label)
+    }
+}
+
+/// A group of sibling items that share the same mnemonic,
+/// returned from [`Menu::mnemonic_conflicts`](crate::Menu::mnemonic_conflicts)
+/// and [`Submenu::mnemonic_conflicts`](crate::Submenu::mnemonic_conflicts).
+#[derive(Clone)]
+pub struct MnemonicConflict {
+    /// The shared mnemonic, in lowercase.
+    pub mnemonic: char,
+    /// The items using this mnemonic, in the order they appear in their menu.
+    pub items: Vec<MenuItemKind>,
+}
+
+fn label(item: &MenuItemKind) -> MnemonicLabel {
+    MnemonicLabel::parse(&item.child().text_with_mnemonic())
+}
+
+/// Mnemonics are matched case-insensitively.
+fn normalize(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+pub(crate) fn conflicts(items: Vec<MenuItemKind>) -> Vec<MnemonicConflict> {
+    let mut conflicts: Vec<MnemonicConflict> = Vec::new();
+    for item in items {
+        if let Some(mnemonic) = label(&item).mnemonic().map(normalize) {
+            match conflicts.iter_mut().find(|c| c.mnemonic == mnemonic) {
+                Some(conflict) => conflict.items.push(item),
+                None => conflicts.push(MnemonicConflict {
+                    mnemonic,
+                    items: vec![item],
+                }),
+            }
+        }
+    }
+    conflicts.retain(|c| c.items.len() > 1);
+    conflicts
+}
+
+/// Returns the positions in `text` to try as a mnemonic, the first letter of each word first.
+fn candidates(text: &str) -> Vec<usize> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut word_starts = Vec::new();
+    let mut others = Vec::new();
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            continue;
+        }
+        if i == 0 || !chars[i - 1].is_alphanumeric() {
+            word_starts.push(i);
+        } else {
+            others.push(i);
+        }
+    }
+    word_starts.extend(others);
+    word_starts
+}
+
+pub(crate) fn assign(items: Vec<MenuItemKind>) {
+    let mut taken = Vec::new();
+    let mut unassigned = Vec::new();
+    for item in items {
+        let label = label(&item);
+        match label.mnemonic().map(normalize) {
+            Some(mnemonic) if !taken.contains(&mnemonic) => taken.push(mnemonic),
+            // the texts of predefined items are managed by muda and the OS
+            _ if matches!(item, MenuItemKind::Predefined(_)) => {}
+            _ => unassigned.push((item, label)),
+        }
+    }
+
+    for (item, label) in unassigned {
+        let chars = label.text().chars().collect::<Vec<_>>();
+        let position = candidates(label.text())
+            .into_iter()
+            .find(|p| !taken.contains(&normalize(chars[*p])));
+        if let Some(position) = position {
+            taken.push(normalize(chars[position]));
+            let label = MnemonicLabel::new(label.text(), Some(position));
+            item.child_mut().set_text(&label.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{CheckMenuItem, MenuItem, PredefinedMenuItem, Submenu};
+
+    use super::MnemonicLabel;
+
+    #[test]
+    fn test_parse_mnemonic_label() {
+        let label = MnemonicLabel::parse("&File");
+        assert_eq!(label.text(), "File");
+        assert_eq!(label.mnemonic(), Some('F'));
+        assert_eq!(label.position(), Some(0));
+
+        let label = MnemonicLabel::parse("Save && &Quit");
+        assert_eq!(label.text(), "Save & Quit");
+        assert_eq!(label.mnemonic(), Some('Q'));
+        assert_eq!(label.position(), Some(7));
+        assert_eq!(label.to_string(), "Save && &Quit");
+
+        // only the first mnemonic is used
+        let label = MnemonicLabel::parse("&Open &Recent");
+        assert_eq!(label.text(), "Open Recent");
+        assert_eq!(label.mnemonic(), Some('O'));
+        assert_eq!(label.to_string(), "&Open Recent");
+
+        let label = MnemonicLabel::parse("Plain text &");
+        assert_eq!(label.text(), "Plain text ");
+        assert_eq!(label.mnemonic(), None);
+
+        let label = MnemonicLabel::new("Éditer", Some(1));
+        assert_eq!(label.mnemonic(), Some('d'));
+        assert_eq!(label.to_string(), "É&diter");
+        assert_eq!(MnemonicLabel::new("Edit", Some(4)).position(), None);
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn find_and_assign_mnemonics() {
+        let new = MenuItem::new("&New", true, None);
+        let next = MenuItem::new("&next", true, None);
+        let open = MenuItem::new("Open", true, None);
+        let open_recent = MenuItem::new("Open Recent", true, None);
+        let wrap = CheckMenuItem::new("&Wrap && Indent", true, false, None);
+        let separator = PredefinedMenuItem::separator();
+        let submenu = Submenu::with_items(
+            "File",
+            true,
+            &[&new, &next, &separator, &open, &open_recent, &wrap],
+        )
+        .unwrap();
+
+        assert_eq!(new.label().mnemonic(), Some('N'));
+        assert_eq!(wrap.label().text(), "Wrap & Indent");
+
+        let conflicts = submenu.mnemonic_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].mnemonic, 'n');
+        assert_eq!(conflicts[0].items.len(), 2);
+        assert_eq!(conflicts[0].items[1].id(), next.id());
+
+        submenu.assign_mnemonics();
+        assert!(submenu.mnemonic_conflicts().is_empty());
+        assert_eq!(new.label().mnemonic(), Some('N'));
+        assert_eq!(wrap.label().mnemonic(), Some('W'));
+        assert_eq!(next.label().mnemonic(), Some('e'));
+        assert_eq!(open.label().mnemonic(), Some('O'));
+        assert_eq!(open_recent.label().mnemonic(), Some('R'));
+        assert_eq!(open_recent.label().text(), "Open Recent");
+        assert_eq!(separator.label().mnemonic(), None);
+    }
+}
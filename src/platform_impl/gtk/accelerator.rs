@@ -27,6 +27,12 @@ pub fn from_gtk_mnemonic<S: AsRef<str>>(string: S) -> String {
 pub fn parse_accelerator(
     accelerator: &Accelerator,
 ) -> Result<(gdk::ModifierType, u32), AcceleratorParseError> {
+    if let Some(character) = accelerator.character {
+        // gtk matches accelerators by keyval, which it resolves using the active keyboard layout
+        let key = *gdk::keys::Key::from_unicode(character);
+        return Ok((modifiers_to_gdk_modifier_type(accelerator.mods), key));
+    }
+
     let key = match &accelerator.key {
         Code::KeyA => 'A' as u32,
         Code::KeyB => 'B' as u32,
@@ -177,6 +183,174 @@ fn key_to_raw_key(src: &Code) -> Option<gdk::keys::Key> {
 
         Code::ContextMenu => Menu,
         Code::WakeUp => WakeUp,
+
+        // The keyvals these keys produce on the layouts they exist on.
+        Code::IntlBackslash => less,
+        Code::IntlRo => backslash,
+        Code::IntlYen => yen,
+
+        Code::Convert => Henkan,
+        Code::NonConvert => Muhenkan,
+        Code::KanaMode => Hiragana_Katakana,
+        Code::Lang1 => Hangul,
+        Code::Lang2 => Hangul_Hanja,
+        Code::Lang3 => Katakana,
+        Code::Lang4 => Hiragana,
+        Code::Lang5 => Zenkaku_Hankaku,
+        Code::Hiragana => Hiragana,
+        Code::Katakana => Katakana,
+
+        Code::Help => Help,
+
+        Code::Numpad0 => KP_0,
+        Code::Numpad1 => KP_1,
+        Code::Numpad2 => KP_2,
+        Code::Numpad3 => KP_3,
+        Code::Numpad4 => KP_4,
+        Code::Numpad5 => KP_5,
+        Code::Numpad6 => KP_6,
+        Code::Numpad7 => KP_7,
+        Code::Numpad8 => KP_8,
+        Code::Numpad9 => KP_9,
+        Code::NumpadAdd => KP_Add,
+        Code::NumpadBackspace => BackSpace,
+        Code::NumpadClear => Clear,
+        Code::NumpadClearEntry => Clear,
+        Code::NumpadComma => KP_Separator,
+        Code::NumpadDecimal => KP_Decimal,
+        Code::NumpadDivide => KP_Divide,
+        Code::NumpadEnter => KP_Enter,
+        Code::NumpadEqual => KP_Equal,
+        Code::NumpadHash => numbersign,
+        Code::NumpadMultiply => KP_Multiply,
+        Code::NumpadParenLeft => parenleft,
+        Code::NumpadParenRight => parenright,
+        Code::NumpadStar => KP_Multiply,
+        Code::NumpadSubtract => KP_Subtract,
+
+        Code::F25 => F25,
+        Code::F26 => F26,
+        Code::F27 => F27,
+        Code::F28 => F28,
+        Code::F29 => F29,
+        Code::F30 => F30,
+        Code::F31 => F31,
+        Code::F32 => F32,
+        Code::F33 => F33,
+        Code::F34 => F34,
+        Code::F35 => F35,
+
+        Code::BrowserBack => Back,
+        Code::BrowserFavorites => Favorites,
+        Code::BrowserForward => Forward,
+        Code::BrowserHome => HomePage,
+        Code::BrowserRefresh => Refresh,
+        Code::BrowserSearch => Search,
+        Code::BrowserStop => Stop,
+
+        Code::AudioVolumeDown => AudioLowerVolume,
+        Code::AudioVolumeMute => AudioMute,
+        Code::AudioVolumeUp => AudioRaiseVolume,
+        Code::MicrophoneMuteToggle => AudioMicMute,
+        Code::MediaPlayPause => AudioPlay,
+        Code::MediaPlay => AudioPlay,
+        Code::MediaPause => AudioPause,
+        Code::MediaStop => AudioStop,
+        Code::MediaTrackNext => AudioNext,
+        Code::MediaTrackPrevious => AudioPrev,
+        Code::MediaFastForward => AudioForward,
+        Code::MediaRewind => AudioRewind,
+        Code::MediaRecord => AudioRecord,
+        Code::MediaSelect => AudioMedia,
+
+        Code::LaunchApp1 => MyComputer,
+        Code::LaunchApp2 => Calculator,
+        Code::LaunchMail => Mail,
+        Code::LaunchScreenSaver => ScreenSaver,
+        Code::MailForward => MailForward,
+        Code::MailReply => Reply,
+        Code::MailSend => Send,
+
+        Code::Eject => Eject,
+        Code::Power => PowerOff,
+        Code::Sleep => Sleep,
+        Code::BrightnessDown => MonBrightnessDown,
+        Code::BrightnessUp => MonBrightnessUp,
+        Code::DisplayToggleIntExt => Display,
+
+        Code::Hyper => Hyper_L,
+        Code::Super => Super_L,
+
+        Code::Abort => Cancel,
+        Code::Suspend => Suspend,
+        Code::Again => Redo,
+        Code::Copy => Copy,
+        Code::Cut => Cut,
+        Code::Find => Find,
+        Code::Open => Open,
+        Code::Paste => Paste,
+        Code::Select => Select,
+        Code::Undo => Undo,
+
+        // These keys don't have a keyval.
+        Code::Fn
+        | Code::FnLock
+        | Code::Turbo
+        | Code::Resume
+        | Code::Props
+        | Code::NumpadMemoryAdd
+        | Code::NumpadMemoryClear
+        | Code::NumpadMemoryRecall
+        | Code::NumpadMemoryStore
+        | Code::NumpadMemorySubtract
+        | Code::KeyboardLayoutSelect
+        | Code::LaunchAssistant
+        | Code::LaunchControlPanel
+        | Code::PrivacyScreenToggle
+        | Code::SelectTask
+        | Code::ShowAllWindows
+        | Code::ZoomToggle => return None,
+        // Unidentified
         _ => return None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use keyboard_types::Code;
+
+    use super::parse_accelerator;
+    use crate::accelerator::{Accelerator, ALL_CODES};
+
+    #[test]
+    fn every_code_with_a_keyval_is_supported() {
+        let without_keyval = [
+            Code::Fn,
+            Code::FnLock,
+            Code::Turbo,
+            Code::Resume,
+            Code::Props,
+            Code::NumpadMemoryAdd,
+            Code::NumpadMemoryClear,
+            Code::NumpadMemoryRecall,
+            Code::NumpadMemoryStore,
+            Code::NumpadMemorySubtract,
+            Code::KeyboardLayoutSelect,
+            Code::LaunchAssistant,
+            Code::LaunchControlPanel,
+            Code::PrivacyScreenToggle,
+            Code::SelectTask,
+            Code::ShowAllWindows,
+            Code::ZoomToggle,
+        ];
+
+        for code in ALL_CODES {
+            let accelerator = Accelerator::new(None, *code);
+            assert_eq!(
+                parse_accelerator(&accelerator).is_ok(),
+                !without_keyval.contains(code),
+                "{code}"
+            );
+        }
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! The actions performed by predefined menu items.
+
+use gtk::{
+    gdk,
+    glib::{self, translate::IntoGlib, translate::ToGlibPtr},
+    prelude::*,
+};
+
+use crate::{items::PredefinedMenuItemType, AboutMetadata};
+
+pub fn is_supported(item_type: &PredefinedMenuItemType) -> bool {
+    !matches!(
+        item_type,
+        PredefinedMenuItemType::HideOthers
+            | PredefinedMenuItemType::ShowAll
+            | PredefinedMenuItemType::Services
+            | PredefinedMenuItemType::BringAllToFront
+            | PredefinedMenuItemType::None
+    )
+}
+
+/// Perform the action of a predefined item, `window` is the window it was activated for.
+pub fn perform(item_type: &PredefinedMenuItemType, window: Option<&gtk::Window>) {
+    match item_type {
+        PredefinedMenuItemType::Copy
+        | PredefinedMenuItemType::Cut
+        | PredefinedMenuItemType::Paste
+        | PredefinedMenuItemType::SelectAll => perform_edit_action(item_type, window),
+        PredefinedMenuItemType::Quit => quit(),
+        PredefinedMenuItemType::About(metadata) => {
+            if let Some(metadata) = metadata {
+                show_about_dialog(metadata);
+            }
+        }
+        _ => {
+            if let Some(window) = window {
+                perform_window_action(item_type, window);
+            }
+        }
+    }
+}
+
+/// Perform the action of a window related predefined item on `window`.
+fn perform_window_action(item_type: &PredefinedMenuItemType, window: &gtk::Window) {
+    match item_type {
+        PredefinedMenuItemType::Undo => undo_redo(window, false),
+        PredefinedMenuItemType::Redo => undo_redo(window, true),
+        PredefinedMenuItemType::Minimize => window.iconify(),
+        PredefinedMenuItemType::Maximize => {
+            if window.is_maximized() {
+                window.unmaximize()
+            } else {
+                window.maximize()
+            }
+        }
+        PredefinedMenuItemType::Fullscreen => {
+            let fullscreen = window
+                .window()
+                .map(|w| w.state().contains(gdk::WindowState::FULLSCREEN))
+                .unwrap_or(false);
+            if fullscreen {
+                window.unfullscreen()
+            } else {
+                window.fullscreen()
+            }
+        }
+        PredefinedMenuItemType::Hide => window.hide(),
+        PredefinedMenuItemType::CloseWindow => window.close(),
+        _ => {}
+    }
+}
+
+/// Quit the gtk main loop, or when the application drives its own loop,
+/// ask all of its visible windows to close instead.
+fn quit() {
+    if gtk::main_level() > 0 {
+        gtk::main_quit();
+        return;
+    }
+
+    for window in gtk::Window::list_toplevels() {
+        if let Ok(window) = window.downcast::<gtk::Window>() {
+            if window.window_type() == gtk::WindowType::Toplevel && window.is_visible() {
+                window.close();
+            }
+        }
+    }
+}
+
+fn show_about_dialog(metadata: &AboutMetadata) {
+    let mut builder = gtk::AboutDialog::builder().modal(true).resizable(false);
+
+    if let Some(name) = &metadata.name {
+        builder = builder.program_name(name);
+    }
+    if let Some(version) = &metadata.full_version() {
+        builder = builder.version(version);
+    }
+    if let Some(authors) = &metadata.authors {
+        builder = builder.authors(authors.clone());
+    }
+    if let Some(comments) = &metadata.comments {
+        builder = builder.comments(comments);
+    }
+    if let Some(copyright) = &metadata.copyright {
+        builder = builder.copyright(copyright);
+    }
+    if let Some(license) = &metadata.license {
+        builder = builder.license(license);
+    }
+    if let Some(website) = &metadata.website {
+        builder = builder.website(website);
+    }
+    if let Some(website_label) = &metadata.website_label {
+        builder = builder.website_label(website_label);
+    }
+    if let Some(icon) = &metadata.icon {
+        builder = builder.logo(&icon.inner.to_pixbuf());
+    }
+
+    let about = builder.build();
+    about.run();
+    unsafe {
+        about.destroy();
+    }
+}
+
+/// Perform the action of an edit related predefined item on the focused widget of `window`.
+fn perform_edit_action(item_type: &PredefinedMenuItemType, window: Option<&gtk::Window>) {
+    let widget = match window.and_then(|w| w.focused_widget()) {
+        Some(widget) => widget,
+        None => {
+            // the focused widget isn't a gtk widget of this application
+            send_xdo_keys(item_type);
+            return;
+        }
+    };
+
+    let signal = match item_type {
+        PredefinedMenuItemType::Copy => "copy-clipboard",
+        PredefinedMenuItemType::Cut => "cut-clipboard",
+        PredefinedMenuItemType::Paste => "paste-clipboard",
+        PredefinedMenuItemType::SelectAll => "select-all",
+        _ => return,
+    };
+
+    if let Some(editable) = widget.dynamic_cast_ref::<gtk::Editable>() {
+        match item_type {
+            PredefinedMenuItemType::Copy => editable.copy_clipboard(),
+            PredefinedMenuItemType::Cut => editable.cut_clipboard(),
+            PredefinedMenuItemType::Paste => editable.paste_clipboard(),
+            _ => editable.select_region(0, -1),
+        }
+    } else if widget.is::<gtk::TextView>() {
+        if let PredefinedMenuItemType::SelectAll = item_type {
+            widget.emit_by_name::<()>(signal, &[&true]);
+        } else {
+            widget.emit_by_name::<()>(signal, &[]);
+        }
+    } else if has_action_signal(&widget, signal) {
+        // other widgets like a selectable `GtkLabel`
+        widget.emit_by_name::<()>(signal, &[]);
+    } else {
+        // widgets like `WebKitWebView` only handle the usual shortcuts
+        send_xdo_keys(item_type);
+    }
+}
+
+/// Send the usual shortcut of an edit related predefined item to the focused window using `libxdo`,
+/// for widgets that can't be reached through their `gtk` signals. Does nothing without the `libxdo` feature.
+fn send_xdo_keys(item_type: &PredefinedMenuItemType) {
+    #[cfg(feature = "libxdo")]
+    if let Ok(xdo) = libxdo::XDo::new(None) {
+        let _ = xdo.send_keysequence(item_type.xdo_keys(), 0);
+    }
+    #[cfg(not(feature = "libxdo"))]
+    let _ = item_type;
+}
+
+/// Whether `widget` has an action signal named `signal` that takes no arguments.
+fn has_action_signal(widget: &gtk::Widget, signal: &str) -> bool {
+    glib::subclass::signal::SignalId::lookup(signal, widget.type_())
+        .map(|id| id.query().n_params() == 0)
+        .unwrap_or(false)
+}
+
+fn undo_redo(window: &gtk::Window, redo: bool) {
+    let widget = match window.focused_widget() {
+        Some(widget) => widget,
+        None => return,
+    };
+
+    // editors like `GtkSourceView` expose their history through action signals
+    let signal = if redo { "redo" } else { "undo" };
+    if has_action_signal(&widget, signal) {
+        widget.emit_by_name::<()>(signal, &[]);
+        return;
+    }
+
+    // other widgets like `WebKitWebView` only handle the usual shortcuts
+    let mods = if redo {
+        gdk::ModifierType::CONTROL_MASK | gdk::ModifierType::SHIFT_MASK
+    } else {
+        gdk::ModifierType::CONTROL_MASK
+    };
+    send_key_press(&widget, gdk::keys::constants::z, mods);
+}
+
+/// Deliver a synthesized key press directly to `widget`, which works on both X11 and Wayland
+/// and bypasses the accelerators of its window which may include our own menu items.
+fn send_key_press(widget: &gtk::Widget, key: gdk::keys::Key, mods: gdk::ModifierType) {
+    let window = match widget.window() {
+        Some(window) => window,
+        None => return,
+    };
+    let display = window.display();
+
+    let mut event = gdk::Event::new(gdk::EventType::KeyPress);
+    event.set_device(display.default_seat().and_then(|s| s.keyboard()).as_ref());
+    if let Some(key_event) = event.downcast_mut::<gdk::EventKey>() {
+        let keycode = gdk::Keymap::for_display(&display)
+            .and_then(|k| k.entries_for_keyval(*key).first().map(|k| k.keycode()))
+            .unwrap_or_default();
+
+        let raw: &mut gdk::ffi::GdkEventKey = key_event.as_mut();
+        // the event releases its reference to the window when it is freed
+        raw.window = window.to_glib_full();
+        raw.send_event = 1;
+        raw.time = gtk::current_event_time();
+        raw.state = mods.into_glib();
+        raw.keyval = *key;
+        raw.hardware_keycode = keycode as u16;
+    }
+
+    widget.event(&event);
+}
@@ -0,0 +1,850 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Exporting menus over D-Bus with the `com.canonical.dbusmenu` protocol,
+//! which is how global menu bars and StatusNotifierItem trays read the menus of other applications.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::{Rc, Weak},
+};
+
+use gtk::{
+    gdk, gio,
+    glib::{self, ToVariant, Variant},
+};
+
+use super::{accelerator::parse_accelerator, accelerator::to_gtk_mnemonic, MenuChild, COUNTER};
+use crate::{
+    accelerator::Accelerator, items::PredefinedMenuItemType, MenuEvent, MenuEventKind,
+    MenuEventSource, MenuId, MenuItemKind, MenuItemType,
+};
+
+const INTERFACE: &str = "com.canonical.dbusmenu";
+
+const INTROSPECTION_XML: &str = r#"
+<node>
+  <interface name="com.canonical.dbusmenu">
+    <property name="Version" type="u" access="read"/>
+    <property name="TextDirection" type="s" access="read"/>
+    <property name="Status" type="s" access="read"/>
+    <property name="IconThemePath" type="as" access="read"/>
+    <method name="GetLayout">
+      <arg type="i" name="parentId" direction="in"/>
+      <arg type="i" name="recursionDepth" direction="in"/>
+      <arg type="as" name="propertyNames" direction="in"/>
+      <arg type="u" name="revision" direction="out"/>
+      <arg type="(ia{sv}av)" name="layout" direction="out"/>
+    </method>
+    <method name="GetGroupProperties">
+      <arg type="ai" name="ids" direction="in"/>
+      <arg type="as" name="propertyNames" direction="in"/>
+      <arg type="a(ia{sv})" name="properties" direction="out"/>
+    </method>
+    <method name="GetProperty">
+      <arg type="i" name="id" direction="in"/>
+      <arg type="s" name="name" direction="in"/>
+      <arg type="v" name="value" direction="out"/>
+    </method>
+    <method name="Event">
+      <arg type="i" name="id" direction="in"/>
+      <arg type="s" name="eventId" direction="in"/>
+      <arg type="v" name="data" direction="in"/>
+      <arg type="u" name="timestamp" direction="in"/>
+    </method>
+    <method name="EventGroup">
+      <arg type="a(isvu)" name="events" direction="in"/>
+      <arg type="ai" name="idErrors" direction="out"/>
+    </method>
+    <method name="AboutToShow">
+      <arg type="i" name="id" direction="in"/>
+      <arg type="b" name="needUpdate" direction="out"/>
+    </method>
+    <method name="AboutToShowGroup">
+      <arg type="ai" name="ids" direction="in"/>
+      <arg type="ai" name="updatesNeeded" direction="out"/>
+      <arg type="ai" name="idErrors" direction="out"/>
+    </method>
+    <signal name="ItemsPropertiesUpdated">
+      <arg type="a(ia{sv})" name="updatedProps" direction="out"/>
+      <arg type="a(ias)" name="removedProps" direction="out"/>
+    </signal>
+    <signal name="LayoutUpdated">
+      <arg type="u" name="revision" direction="out"/>
+      <arg type="i" name="parent" direction="out"/>
+    </signal>
+    <signal name="ItemActivationRequested">
+      <arg type="i" name="id" direction="out"/>
+      <arg type="u" name="timestamp" direction="out"/>
+    </signal>
+  </interface>
+</node>
+"#;
+
+/// The properties that are only set on some items, and need to be reported as removed
+/// when an item stops having them.
+const OPTIONAL_PROPERTIES: &[&str] = &["shortcut", "icon-data", "toggle-state"];
+
+thread_local! {
+    static EXPORTS: RefCell<HashMap<u32, Export>> = RefCell::new(HashMap::new());
+    /// The changes waiting to be signaled, `Some` while a flush is scheduled.
+    static PENDING: RefCell<Option<Pending>> = const { RefCell::new(None) };
+}
+
+#[derive(Default)]
+struct Pending {
+    items: HashSet<MenuId>,
+    layouts: HashSet<MenuId>,
+}
+
+/// A [`Menu`](crate::Menu) or [`Submenu`](crate::Submenu) exported over D-Bus
+/// with the `com.canonical.dbusmenu` protocol.
+///
+/// The menu stays exported, and alive, until this is dropped.
+pub struct DbusMenuExport {
+    key: u32,
+    connection: gio::DBusConnection,
+    object_path: String,
+    registration: Option<gio::RegistrationId>,
+}
+
+impl DbusMenuExport {
+    pub(crate) fn new<F>(
+        connection: &gio::DBusConnection,
+        object_path: &str,
+        root: MenuId,
+        items: F,
+    ) -> crate::Result<Self>
+    where
+        F: Fn() -> Vec<MenuItemKind> + 'static,
+    {
+        let interface = gio::DBusNodeInfo::for_xml(INTROSPECTION_XML)?
+            .lookup_interface(INTERFACE)
+            .unwrap();
+
+        // the closures have to be `Send`, so they only capture the key of the
+        // export, which is looked up on the thread that owns the menu
+        let key = COUNTER.next();
+        let registration = connection.register_object(
+            object_path,
+            &interface,
+            move |_, _, _, _, method, parameters, invocation| {
+                handle_method_call(key, method, &parameters, invocation)
+            },
+            |_, _, _, _, property| get_property(property),
+            |_, _, _, _, _, _| false,
+        )?;
+
+        let mut export = Export {
+            connection: connection.clone(),
+            object_path: object_path.to_string(),
+            root,
+            items: Box::new(items),
+            revision: 0,
+            ids: HashMap::new(),
+            next_id: 0,
+        };
+        export.tree();
+        EXPORTS.with(|exports| exports.borrow_mut().insert(key, export));
+
+        Ok(Self {
+            key,
+            connection: connection.clone(),
+            object_path: object_path.to_string(),
+            registration: Some(registration),
+        })
+    }
+
+    /// Returns the D-Bus connection the menu is exported on.
+    pub fn connection(&self) -> &gio::DBusConnection {
+        &self.connection
+    }
+
+    /// Returns the object path the menu is exported at.
+    pub fn object_path(&self) -> &str {
+        &self.object_path
+    }
+}
+
+impl Drop for DbusMenuExport {
+    fn drop(&mut self) {
+        if let Some(registration) = self.registration.take() {
+            let _ = self.connection.unregister_object(registration);
+        }
+        let _ = EXPORTS.try_with(|exports| exports.borrow_mut().remove(&self.key));
+    }
+}
+
+/// Report that the properties of the item with `id` changed.
+pub fn item_changed(id: &MenuId) {
+    schedule(|pending| pending.items.insert(id.clone()));
+}
+
+/// Report that the children of the menu or submenu with `id` changed.
+pub fn layout_changed(id: &MenuId) {
+    schedule(|pending| pending.layouts.insert(id.clone()));
+}
+
+/// Record a change and signal it once the current changes are done,
+/// which also groups all the changes made at once into a few signals.
+fn schedule<F: FnOnce(&mut Pending) -> bool>(f: F) {
+    let exported = EXPORTS
+        .try_with(|exports| exports.try_borrow().map(|e| !e.is_empty()).unwrap_or(true))
+        .unwrap_or(false);
+    if !exported {
+        return;
+    }
+
+    PENDING.with(|pending| {
+        let mut pending = pending.borrow_mut();
+        if pending.is_none() {
+            glib::idle_add_once(flush);
+        }
+        f(pending.get_or_insert_with(Default::default));
+    });
+}
+
+fn flush() {
+    let pending = match PENDING.with(|pending| pending.borrow_mut().take()) {
+        Some(pending) => pending,
+        None => return,
+    };
+
+    EXPORTS.with(|exports| {
+        for export in exports.borrow_mut().values_mut() {
+            export.flush(&pending);
+        }
+    });
+}
+
+fn get_property(property: &str) -> Variant {
+    match property {
+        "Version" => 3u32.to_variant(),
+        "TextDirection" => match gtk::Widget::default_direction() {
+            gtk::TextDirection::Rtl => "rtl",
+            _ => "ltr",
+        }
+        .to_variant(),
+        "Status" => "normal".to_variant(),
+        _ => Vec::<String>::new().to_variant(),
+    }
+}
+
+fn handle_method_call(
+    key: u32,
+    method: &str,
+    parameters: &Variant,
+    invocation: gio::DBusMethodInvocation,
+) {
+    let mut events = Vec::new();
+    let reply = EXPORTS.with(|exports| match exports.borrow_mut().get_mut(&key) {
+        Some(export) => export.call(method, parameters, &mut events),
+        None => Err("This menu is no longer exported"),
+    });
+
+    match reply {
+        Ok(value) => invocation.return_value(value.as_ref()),
+        Err(message) => {
+            invocation.return_dbus_error("org.freedesktop.DBus.Error.InvalidArgs", message)
+        }
+    }
+
+    // sent after the exports are released, as event handlers may change the menu
+    for (item, event, root) in events {
+        send_event(item, &event, root);
+    }
+}
+
+/// The items of an export as seen by D-Bus clients, the root has the id `0`.
+struct Node {
+    id: i32,
+    item: Option<MenuItemKind>,
+    children: Vec<Node>,
+}
+
+impl Node {
+    fn find(&self, id: i32) -> Option<&Node> {
+        if self.id == id {
+            return Some(self);
+        }
+        self.children.iter().find_map(|c| c.find(id))
+    }
+
+    /// Returns the nodes from this one down to the node with `id`.
+    fn path(&self, id: i32) -> Option<Vec<&Node>> {
+        if self.id == id {
+            return Some(vec![self]);
+        }
+        self.children.iter().find_map(|c| {
+            let mut path = c.path(id)?;
+            path.insert(0, self);
+            Some(path)
+        })
+    }
+
+    fn all(&self) -> Vec<&Node> {
+        let mut nodes = vec![self];
+        for child in &self.children {
+            nodes.extend(child.all());
+        }
+        nodes
+    }
+
+    fn properties(&self, names: &[String]) -> HashMap<String, Variant> {
+        let mut properties = match &self.item {
+            Some(item) => item_properties(item),
+            None => HashMap::from([("children-display".to_string(), "submenu".to_variant())]),
+        };
+        if !names.is_empty() {
+            properties.retain(|name, _| names.contains(name));
+        }
+        properties
+    }
+
+    /// Returns the `(ia{sv}av)` layout of this node and its children up to `depth`, `-1` being all of them.
+    fn layout(&self, depth: i32, names: &[String]) -> Variant {
+        let children = if depth == 0 {
+            Vec::new()
+        } else {
+            let depth = if depth < 0 { depth } else { depth - 1 };
+            self.children
+                .iter()
+                .map(|c| c.layout(depth, names))
+                .collect::<Vec<_>>()
+        };
+
+        Variant::tuple_from_iter([
+            self.id.to_variant(),
+            self.properties(names).to_variant(),
+            children.to_variant(),
+        ])
+    }
+}
+
+/// The D-Bus id of the parent of a node and the inner item of the node.
+type NodeKey = (i32, *const MenuChild);
+
+struct Export {
+    connection: gio::DBusConnection,
+    object_path: String,
+    root: MenuId,
+    items: Box<dyn Fn() -> Vec<MenuItemKind>>,
+    revision: u32,
+    /// The D-Bus ids of the menu items by the D-Bus id of their parent and their inner item,
+    /// so an item added in more than one place has an id for each of them.
+    /// Ids are never reused, and the weak references keep the addresses of the items from being reused.
+    ids: HashMap<NodeKey, (i32, Weak<RefCell<MenuChild>>)>,
+    next_id: i32,
+}
+
+impl Export {
+    /// Returns the current items, giving an id to the ones that are new
+    /// and forgetting the ids of the ones that were removed.
+    fn tree(&mut self) -> Node {
+        let items = (self.items)();
+        let mut seen = HashSet::new();
+        let tree = Node {
+            id: 0,
+            item: None,
+            children: self.nodes(0, items, &mut seen),
+        };
+        self.ids.retain(|key, _| seen.contains(key));
+        tree
+    }
+
+    fn nodes(
+        &mut self,
+        parent: i32,
+        items: Vec<MenuItemKind>,
+        seen: &mut HashSet<NodeKey>,
+    ) -> Vec<Node> {
+        items
+            .into_iter()
+            .filter(|i| i.child().is_supported())
+            .map(|item| {
+                let key = (parent, item.child_ptr());
+                let next_id = &mut self.next_id;
+                let id = self
+                    .ids
+                    .entry(key)
+                    .or_insert_with(|| {
+                        *next_id += 1;
+                        (*next_id, Rc::downgrade(item.child_rc()))
+                    })
+                    .0;
+                seen.insert(key);
+                let children = match &item {
+                    MenuItemKind::Submenu(s) => self.nodes(id, s.items(), seen),
+                    _ => Vec::new(),
+                };
+                Node {
+                    id,
+                    item: Some(item),
+                    children,
+                }
+            })
+            .collect()
+    }
+
+    fn call(
+        &mut self,
+        method: &str,
+        parameters: &Variant,
+        events: &mut Vec<(MenuItemKind, String, MenuId)>,
+    ) -> Result<Option<Variant>, &'static str> {
+        const INVALID: &str = "Invalid arguments";
+        const UNKNOWN_ID: &str = "Unknown menu item id";
+
+        let tree = self.tree();
+        match method {
+            "GetLayout" => {
+                let (parent, depth, names) =
+                    parameters.get::<(i32, i32, Vec<String>)>().ok_or(INVALID)?;
+                let node = tree.find(parent).ok_or(UNKNOWN_ID)?;
+                Ok(Some(Variant::tuple_from_iter([
+                    self.revision.to_variant(),
+                    node.layout(depth, &names),
+                ])))
+            }
+            "GetGroupProperties" => {
+                let (ids, names) = parameters.get::<(Vec<i32>, Vec<String>)>().ok_or(INVALID)?;
+                let properties = tree
+                    .all()
+                    .into_iter()
+                    .filter(|n| ids.is_empty() || ids.contains(&n.id))
+                    .map(|n| (n.id, n.properties(&names)))
+                    .collect::<Vec<_>>();
+                Ok(Some(Variant::tuple_from_iter([properties.to_variant()])))
+            }
+            "GetProperty" => {
+                let (id, name) = parameters.get::<(i32, String)>().ok_or(INVALID)?;
+                let node = tree.find(id).ok_or(UNKNOWN_ID)?;
+                let value = node
+                    .properties(&[])
+                    .remove(&name)
+                    .ok_or("Unknown property")?;
+                Ok(Some(Variant::tuple_from_iter([value.to_variant()])))
+            }
+            "Event" => {
+                let (id, event, _, _) = parameters
+                    .get::<(i32, String, Variant, u32)>()
+                    .ok_or(INVALID)?;
+                let event = self.event(&tree, id, event).ok_or(UNKNOWN_ID)?;
+                events.extend(event);
+                Ok(None)
+            }
+            "EventGroup" => {
+                let (group,) = parameters
+                    .get::<(Vec<(i32, String, Variant, u32)>,)>()
+                    .ok_or(INVALID)?;
+                let mut errors = Vec::new();
+                for (id, event, _, _) in group {
+                    match self.event(&tree, id, event) {
+                        Some(event) => events.extend(event),
+                        None => errors.push(id),
+                    }
+                }
+                Ok(Some(Variant::tuple_from_iter([errors.to_variant()])))
+            }
+            "AboutToShow" => {
+                let (id,) = parameters.get::<(i32,)>().ok_or(INVALID)?;
+                tree.find(id).ok_or(UNKNOWN_ID)?;
+                // the layout is always up to date
+                Ok(Some(Variant::tuple_from_iter([false.to_variant()])))
+            }
+            "AboutToShowGroup" => {
+                let (ids,) = parameters.get::<(Vec<i32>,)>().ok_or(INVALID)?;
+                let errors = ids
+                    .into_iter()
+                    .filter(|id| tree.find(*id).is_none())
+                    .collect::<Vec<_>>();
+                Ok(Some(Variant::tuple_from_iter([
+                    Vec::<i32>::new().to_variant(),
+                    errors.to_variant(),
+                ])))
+            }
+            _ => Err("Unknown method"),
+        }
+    }
+
+    /// Returns the event to send for the node with `id`, which is `None` if it is ignored,
+    /// or `None` if there is no such node.
+    ///
+    /// Clicks are ignored unless the item and all the submenus it is in are enabled and visible,
+    /// like the items of a native menu.
+    #[allow(clippy::type_complexity)]
+    fn event(
+        &self,
+        tree: &Node,
+        id: i32,
+        event: String,
+    ) -> Option<Option<(MenuItemKind, String, MenuId)>> {
+        let path = tree.path(id)?;
+        let item = match path.last().and_then(|n| n.item.clone()) {
+            Some(item) => item,
+            None => return Some(None),
+        };
+
+        let active = path.iter().filter_map(|n| n.item.as_ref()).all(|i| {
+            let child = i.child();
+            child.is_enabled() && child.is_visible()
+        });
+        if event == "clicked" && !active {
+            return Some(None);
+        }
+
+        Some(Some((item, event, self.root.clone())))
+    }
+
+    fn flush(&mut self, pending: &Pending) {
+        // an id may be shared by several items, or an item added in several places,
+        // so the changes apply to every node of the current tree for that id
+        let tree = self.tree();
+        let nodes_of = |ids: &HashSet<MenuId>| {
+            tree.all()
+                .into_iter()
+                .filter(|n| {
+                    n.item
+                        .as_ref()
+                        .map(|i| ids.contains(i.id()))
+                        .unwrap_or(false)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut parents = nodes_of(&pending.layouts)
+            .into_iter()
+            .map(|n| n.id)
+            .collect::<Vec<_>>();
+        if pending.layouts.contains(&self.root) {
+            parents.push(0);
+        }
+
+        if !parents.is_empty() {
+            self.revision += 1;
+            let parent = if parents.len() == 1 { parents[0] } else { 0 };
+            let _ = self.connection.emit_signal(
+                None,
+                &self.object_path,
+                INTERFACE,
+                "LayoutUpdated",
+                Some(&(self.revision, parent).to_variant()),
+            );
+        }
+
+        let mut updated = Vec::new();
+        let mut removed = Vec::new();
+        for node in nodes_of(&pending.items) {
+            let properties = node.properties(&[]);
+            let missing = OPTIONAL_PROPERTIES
+                .iter()
+                .filter(|p| !properties.contains_key(**p))
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                removed.push((node.id, missing));
+            }
+            updated.push((node.id, properties));
+        }
+
+        if !updated.is_empty() {
+            let _ = self.connection.emit_signal(
+                None,
+                &self.object_path,
+                INTERFACE,
+                "ItemsPropertiesUpdated",
+                Some(&Variant::tuple_from_iter([
+                    updated.to_variant(),
+                    removed.to_variant(),
+                ])),
+            );
+        }
+    }
+}
+
+fn item_properties(item: &MenuItemKind) -> HashMap<String, Variant> {
+    let child = item.child();
+    let mut properties = HashMap::new();
+    properties.insert("visible".to_string(), child.is_visible().to_variant());
+
+    if let Some(PredefinedMenuItemType::Separator) = child.predefined_item_type {
+        properties.insert("type".to_string(), "separator".to_variant());
+        return properties;
+    }
+
+    properties.insert(
+        "label".to_string(),
+        to_gtk_mnemonic(child.text()).to_variant(),
+    );
+    properties.insert("enabled".to_string(), child.is_enabled().to_variant());
+
+    match child.item_type() {
+        MenuItemType::Submenu => {
+            properties.insert("children-display".to_string(), "submenu".to_variant());
+        }
+        MenuItemType::Check | MenuItemType::Radio => {
+            let toggle_type = if child.item_type() == MenuItemType::Check {
+                "checkmark"
+            } else {
+                "radio"
+            };
+            properties.insert("toggle-type".to_string(), toggle_type.to_variant());
+            properties.insert(
+                "toggle-state".to_string(),
+                (child.is_checked() as i32).to_variant(),
+            );
+        }
+        _ => {}
+    }
+
+    if let Some(shortcut) = child.accelerator().as_ref().and_then(shortcut) {
+        properties.insert("shortcut".to_string(), vec![shortcut].to_variant());
+    }
+
+    if let Some(icon) = &child.icon {
+        if let Ok(png) = icon.inner.to_pixbuf().save_to_bufferv("png", &[]) {
+            properties.insert("icon-data".to_string(), png.to_variant());
+        }
+    }
+
+    properties
+}
+
+/// Returns the keys of `accelerator` using the names dbusmenu expects, like `["Control", "q"]`.
+fn shortcut(accelerator: &Accelerator) -> Option<Vec<String>> {
+    let (mods, key) = parse_accelerator(accelerator).ok()?;
+
+    let mut shortcut = Vec::new();
+    for (mask, name) in [
+        (gdk::ModifierType::CONTROL_MASK, "Control"),
+        (gdk::ModifierType::MOD1_MASK, "Alt"),
+        (gdk::ModifierType::SHIFT_MASK, "Shift"),
+        (gdk::ModifierType::META_MASK, "Super"),
+    ] {
+        if mods.contains(mask) {
+            shortcut.push(name.to_string());
+        }
+    }
+    shortcut.push(gdk::keys::Key::from(key).name()?.to_string());
+
+    Some(shortcut)
+}
+
+fn send_event(item: MenuItemKind, event: &str, root: MenuId) {
+    let kind = match event {
+        "clicked" => match super::activate_item(&item, super::active_window) {
+            Some(kind) => kind,
+            None => return,
+        },
+        "hovered" => MenuEventKind::Highlighted,
+        "opened" if matches!(item, MenuItemKind::Submenu(_)) => MenuEventKind::SubmenuOpened,
+        "closed" if matches!(item, MenuItemKind::Submenu(_)) => MenuEventKind::SubmenuClosed,
+        _ => return,
+    };
+
+    MenuEvent::send(MenuEvent {
+        id: item.id().clone(),
+        kind,
+        source: MenuEventSource::Mouse,
+        root: Some(root),
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashMap,
+        io::{BufRead, BufReader},
+        process::{Child, Command, Stdio},
+        time::{Duration, Instant},
+    };
+
+    use gtk::{
+        gio,
+        glib::{self, ToVariant, Variant},
+    };
+
+    use crate::{CheckMenuItem, Menu, MenuEventKind, MenuItem, Submenu};
+
+    /// Stops the bus when the test ends, even if it fails.
+    struct Daemon(Child);
+
+    impl Drop for Daemon {
+        fn drop(&mut self) {
+            let _ = self.0.kill();
+        }
+    }
+
+    fn connect(address: &str) -> gio::DBusConnection {
+        gio::DBusConnection::for_address_sync(
+            address,
+            gio::DBusConnectionFlags::AUTHENTICATION_CLIENT
+                | gio::DBusConnectionFlags::MESSAGE_BUS_CONNECTION,
+            None,
+            gio::Cancellable::NONE,
+        )
+        .unwrap()
+    }
+
+    /// Call `method` from another thread, as the export answers on this one.
+    fn call(client: &gio::DBusConnection, name: &str, method: &str, args: Variant) -> Variant {
+        let (client, name, method) = (client.clone(), name.to_string(), method.to_string());
+        let handle = std::thread::spawn(move || {
+            client.call_sync(
+                Some(&name),
+                "/MenuBar",
+                "com.canonical.dbusmenu",
+                &method,
+                Some(&args),
+                None,
+                gio::DBusCallFlags::NONE,
+                1000,
+                gio::Cancellable::NONE,
+            )
+        });
+        while !handle.is_finished() {
+            glib::MainContext::default().iteration(false);
+        }
+        handle.join().unwrap().unwrap()
+    }
+
+    /// Iterate the main context until `f` returns something.
+    fn wait_for<T>(mut f: impl FnMut() -> Option<T>) -> T {
+        let start = Instant::now();
+        loop {
+            glib::MainContext::default().iteration(false);
+            if let Some(value) = f() {
+                return value;
+            }
+            assert!(start.elapsed() < Duration::from_secs(1), "timed out");
+        }
+    }
+
+    fn properties(layout: &Variant) -> HashMap<String, Variant> {
+        layout.child_value(1).get().unwrap()
+    }
+
+    fn children(layout: &Variant) -> Vec<Variant> {
+        let children = layout.child_value(2);
+        (0..children.n_children())
+            .map(|i| children.child_value(i).as_variant().unwrap())
+            .collect()
+    }
+
+    #[test]
+    #[ignore = "needs dbus-daemon"]
+    fn export_menu_over_dbus() {
+        // use a private session bus
+        let mut daemon = Daemon(
+            Command::new("dbus-daemon")
+                .args(["--session", "--nofork", "--print-address"])
+                .stdout(Stdio::piped())
+                .stderr(Stdio::null())
+                .spawn()
+                .expect("failed to start dbus-daemon"),
+        );
+        let mut address = String::new();
+        BufReader::new(daemon.0.stdout.take().unwrap())
+            .read_line(&mut address)
+            .unwrap();
+        let connection = connect(address.trim());
+        let client = connect(address.trim());
+        let name = connection.unique_name().unwrap().to_string();
+
+        let (signals_tx, signals_rx) = std::sync::mpsc::channel();
+        client.signal_subscribe(
+            Some(&name),
+            Some("com.canonical.dbusmenu"),
+            None,
+            Some("/MenuBar"),
+            None,
+            gio::DBusSignalFlags::NONE,
+            move |_, _, _, _, signal, parameters| {
+                let _ = signals_tx.send((signal.to_string(), parameters.clone()));
+            },
+        );
+
+        let new = MenuItem::with_id("new", "&New", true, None);
+        let wrap = CheckMenuItem::with_id("wrap", "Wrap", true, false, None);
+        let file = Submenu::with_items("File", true, &[&new, &wrap]).unwrap();
+        let menu = Menu::with_items(&[&file]).unwrap();
+        let events = menu.events();
+        let export = menu.export_dbusmenu(&connection, "/MenuBar").unwrap();
+        assert_eq!(export.object_path(), "/MenuBar");
+
+        let args = (0i32, -1i32, Vec::<String>::new()).to_variant();
+        let reply = call(&client, &name, "GetLayout", args);
+        assert_eq!(reply.child_value(0).get::<u32>(), Some(0));
+        let root = reply.child_value(1);
+        let file_layout = &children(&root)[0];
+        assert_eq!(
+            properties(file_layout)["children-display"].get::<String>(),
+            Some("submenu".into())
+        );
+        let items = children(file_layout);
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            properties(&items[0])["label"].get::<String>(),
+            Some("_New".into())
+        );
+        assert_eq!(properties(&items[1])["toggle-state"].get::<i32>(), Some(0));
+
+        // clicks are reported as menu events of the exported menu
+        let click = |layout: &Variant| {
+            let id = layout.child_value(0).get::<i32>().unwrap();
+            let args = Variant::tuple_from_iter([
+                id.to_variant(),
+                "clicked".to_variant(),
+                0i32.to_variant().to_variant(),
+                0u32.to_variant(),
+            ]);
+            call(&client, &name, "Event", args);
+        };
+        click(&items[0]);
+        let event = wait_for(|| events.try_recv().ok());
+        assert_eq!(event.id(), new.id());
+        assert_eq!(event.kind(), MenuEventKind::Activated);
+        assert_eq!(event.root(), Some(menu.id()));
+
+        click(&items[1]);
+        let event = wait_for(|| events.try_recv().ok());
+        assert_eq!(event.kind(), MenuEventKind::Toggled { checked: true });
+        assert!(wrap.is_checked());
+
+        // changes are signaled to the clients
+        let (signal, parameters) = wait_for(|| signals_rx.try_recv().ok());
+        assert_eq!(signal, "ItemsPropertiesUpdated");
+        let (id, props) = parameters
+            .child_value(0)
+            .get::<Vec<(i32, HashMap<String, Variant>)>>()
+            .unwrap()
+            .remove(0);
+        assert_eq!(id, items[1].child_value(0).get::<i32>().unwrap());
+        assert_eq!(props["toggle-state"].get::<i32>(), Some(1));
+
+        // items sharing an id still get their own D-Bus ids
+        file.append(&MenuItem::with_id("new", "New Window", true, None))
+            .unwrap();
+        let (signal, parameters) = wait_for(|| signals_rx.try_recv().ok());
+        assert_eq!(signal, "LayoutUpdated");
+        assert_eq!(parameters.get::<(u32, i32)>(), Some((1, 1)));
+
+        let args = (1i32, -1i32, Vec::<String>::new()).to_variant();
+        let reply = call(&client, &name, "GetLayout", args);
+        let items = children(&reply.child_value(1));
+        let ids = items
+            .iter()
+            .map(|i| i.child_value(0).get::<i32>().unwrap())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(ids.len(), 3);
+
+        // items of a disabled submenu can't be clicked
+        file.set_enabled(false);
+        click(&items[0]);
+        assert!(events.try_recv().is_err());
+
+        drop(export);
+    }
+}
@@ -0,0 +1,847 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Converting menus to a `gio::Menu` model backed by the actions of a `gtk::Application`,
+//! which is how `GtkApplication` based apps describe their menu bars and app menus.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+};
+
+use gtk::{
+    gdk, gio,
+    glib::{self, ToVariant, Variant},
+    prelude::*,
+};
+
+use super::{accelerator::parse_accelerator, accelerator::to_gtk_mnemonic, COUNTER};
+use crate::{
+    items::PredefinedMenuItemType, MenuEvent, MenuEventKind, MenuEventSource, MenuId, MenuItemKind,
+    RadioGroup,
+};
+
+thread_local! {
+    static EXPORTS: RefCell<HashMap<u32, Export>> = RefCell::new(HashMap::new());
+    /// Whether a sync of the exports is scheduled.
+    static SCHEDULED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A [`Menu`](crate::Menu) or [`Submenu`](crate::Submenu) converted to a [`gio::Menu`]
+/// whose items activate actions of a [`gtk::Application`].
+///
+/// The model and the actions stay in sync with the menu until this is dropped,
+/// then the model is emptied and the actions are removed from the application.
+pub struct GioMenu {
+    key: u32,
+    menu: gio::Menu,
+}
+
+impl GioMenu {
+    pub(crate) fn new<F>(application: &gtk::Application, root: MenuId, items: F) -> Self
+    where
+        F: Fn() -> Vec<MenuItemKind> + 'static,
+    {
+        Self::with_application(
+            application.upcast_ref(),
+            Some(application.clone()),
+            root,
+            items,
+        )
+    }
+
+    /// The actions are added to `application`, and the accelerators are registered
+    /// with `gtk_application` if any.
+    fn with_application<F>(
+        application: &gio::Application,
+        gtk_application: Option<gtk::Application>,
+        root: MenuId,
+        items: F,
+    ) -> Self
+    where
+        F: Fn() -> Vec<MenuItemKind> + 'static,
+    {
+        // the actions only capture the key of the export, like the ones of `DbusMenuExport`
+        let key = COUNTER.next();
+        let menu = gio::Menu::new();
+
+        let mut export = Export {
+            application: application.clone(),
+            gtk_application,
+            root,
+            items: Box::new(items),
+            menu: menu.clone(),
+            entries: Vec::new(),
+            names: HashMap::new(),
+            groups: Vec::new(),
+            actions: HashMap::new(),
+            accels: HashMap::new(),
+        };
+        export.sync(key);
+        EXPORTS.with(|exports| exports.borrow_mut().insert(key, export));
+
+        Self { key, menu }
+    }
+
+    /// Returns the menu model, to be used with [`set_menubar`](gtk::prelude::GtkApplicationExt::set_menubar),
+    /// [`gtk::MenuBar::from_model`] or [`gtk::Menu::from_model`].
+    pub fn menu_model(&self) -> &gio::Menu {
+        &self.menu
+    }
+
+    /// Returns the detailed name of the application action of the item with `id`, like `app.muda-4`.
+    ///
+    /// Radio items share the action of their group and are told apart by its target,
+    /// like `app.muda-7::muda-8`.
+    pub fn action_name(&self, id: &MenuId) -> Option<String> {
+        EXPORTS.with(|exports| exports.borrow().get(&self.key)?.action_name(id))
+    }
+}
+
+impl Drop for GioMenu {
+    fn drop(&mut self) {
+        let export = EXPORTS
+            .try_with(|exports| exports.borrow_mut().remove(&self.key))
+            .ok()
+            .flatten();
+        if let Some(export) = export {
+            export.clear();
+        }
+    }
+}
+
+/// Report that the item or menu with `id` changed, the exports containing it
+/// are synced once the current changes are done.
+pub fn changed(id: &MenuId) {
+    let exported = EXPORTS
+        .try_with(|exports| {
+            exports
+                .try_borrow()
+                .map(|e| {
+                    e.values()
+                        .any(|e| e.root == *id || e.names.contains_key(id))
+                })
+                .unwrap_or(true)
+        })
+        .unwrap_or(false);
+    if !exported || SCHEDULED.with(|scheduled| scheduled.replace(true)) {
+        return;
+    }
+
+    glib::idle_add_once(flush);
+}
+
+fn flush() {
+    SCHEDULED.with(|scheduled| scheduled.set(false));
+    EXPORTS.with(|exports| {
+        for (key, export) in exports.borrow_mut().iter_mut() {
+            export.sync(*key);
+        }
+    });
+}
+
+/// The items of the model, compared to the previous ones to only update the parts of the model that changed.
+#[derive(PartialEq)]
+enum Entry {
+    Separator,
+    Item {
+        label: String,
+        action: String,
+        target: Option<String>,
+        icon: Option<Vec<u8>>,
+    },
+    Submenu {
+        label: String,
+        action: String,
+        entries: Vec<Entry>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ActionKind {
+    Normal,
+    /// Stateful with a boolean state, which gtk shows as a check mark.
+    Check,
+    /// Stateful with the name of the checked item of the group as its state.
+    Radio,
+    /// Stateful with whether the submenu is open as its state.
+    Submenu,
+}
+
+/// What the items of an export need, collected while syncing it.
+#[derive(Default)]
+struct Visit {
+    actions: HashSet<String>,
+    accels: HashMap<String, String>,
+    /// Whether any item of a radio group is enabled, and its checked item.
+    groups: HashMap<String, (bool, Option<String>)>,
+}
+
+struct Export {
+    application: gio::Application,
+    gtk_application: Option<gtk::Application>,
+    root: MenuId,
+    items: Box<dyn Fn() -> Vec<MenuItemKind>>,
+    menu: gio::Menu,
+    entries: Vec<Entry>,
+    /// The action names of the items, these are never reused.
+    names: HashMap<MenuId, String>,
+    groups: Vec<(RadioGroup, String)>,
+    actions: HashMap<String, (ActionKind, gio::SimpleAction)>,
+    /// The accelerators registered with the application, by detailed action name.
+    accels: HashMap<String, String>,
+}
+
+impl Export {
+    /// Update the actions, the accelerators and the model.
+    fn sync(&mut self, key: u32) {
+        let mut visit = Visit::default();
+        let entries = self.entries(key, (self.items)(), &mut visit);
+
+        for (name, (enabled, checked)) in &visit.groups {
+            if let Some((_, action)) = self.actions.get(name) {
+                action.set_enabled(*enabled);
+                action.set_state(&checked.clone().unwrap_or_default().to_variant());
+            }
+        }
+
+        let removed = self
+            .actions
+            .keys()
+            .filter(|name| !visit.actions.contains(*name))
+            .cloned()
+            .collect::<Vec<_>>();
+        for name in removed {
+            self.actions.remove(&name);
+            self.application.remove_action(&name);
+        }
+
+        if let Some(application) = &self.gtk_application {
+            for name in self.accels.keys() {
+                if !visit.accels.contains_key(name) {
+                    application.set_accels_for_action(name, &[]);
+                }
+            }
+            for (name, accel) in &visit.accels {
+                if self.accels.get(name) != Some(accel) {
+                    application.set_accels_for_action(name, &[accel]);
+                }
+            }
+        }
+        self.accels = visit.accels;
+
+        if entries != self.entries {
+            update(&self.menu, &self.entries, &entries);
+            self.entries = entries;
+        }
+    }
+
+    fn entries(&mut self, key: u32, items: Vec<MenuItemKind>, visit: &mut Visit) -> Vec<Entry> {
+        items
+            .iter()
+            .filter_map(|item| self.entry(key, item, visit))
+            .collect()
+    }
+
+    fn entry(&mut self, key: u32, item: &MenuItemKind, visit: &mut Visit) -> Option<Entry> {
+        let child = item.child();
+        if !child.is_supported() || !child.is_visible() {
+            return None;
+        }
+        if let Some(PredefinedMenuItemType::Separator) = child.predefined_item_type {
+            return Some(Entry::Separator);
+        }
+
+        let label = to_gtk_mnemonic(child.text());
+        let enabled = child.is_enabled();
+        let name = self.name(item.id());
+
+        let (action, target) = match item {
+            MenuItemKind::Submenu(submenu) => {
+                drop(child);
+                self.action(key, &name, ActionKind::Submenu)
+                    .set_enabled(enabled);
+                visit.actions.insert(name.clone());
+                return Some(Entry::Submenu {
+                    label,
+                    entries: self.entries(key, submenu.items(), visit),
+                    action: name,
+                });
+            }
+            MenuItemKind::Check(_) => {
+                let action = self.action(key, &name, ActionKind::Check);
+                action.set_enabled(enabled);
+                action.set_state(&child.is_checked().to_variant());
+                (name.clone(), None)
+            }
+            // gtk shows items as radio items when their action has their target as its state
+            MenuItemKind::Radio(_) => {
+                let group = self.group_name(child.radio_group());
+                self.action(key, &group, ActionKind::Radio);
+                let (group_enabled, checked) = visit.groups.entry(group.clone()).or_default();
+                *group_enabled |= enabled;
+                if child.is_checked() {
+                    *checked = Some(name.clone());
+                }
+                (group, Some(name.clone()))
+            }
+            _ => {
+                self.action(key, &name, ActionKind::Normal)
+                    .set_enabled(enabled);
+                (name.clone(), None)
+            }
+        };
+        visit.actions.insert(action.clone());
+
+        if self.gtk_application.is_some() {
+            let accel = child
+                .accelerator()
+                .and_then(|a| parse_accelerator(&a).ok())
+                .and_then(|(mods, key)| gtk::accelerator_name(key, mods));
+            if let Some(accel) = accel {
+                let detailed = match &target {
+                    Some(target) => format!("app.{action}::{target}"),
+                    None => format!("app.{action}"),
+                };
+                visit.accels.insert(detailed, accel.to_string());
+            }
+        }
+
+        let icon = child
+            .icon
+            .as_ref()
+            .and_then(|icon| icon.inner.to_pixbuf().save_to_bufferv("png", &[]).ok());
+
+        Some(Entry::Item {
+            label,
+            action,
+            target,
+            icon,
+        })
+    }
+
+    fn name(&mut self, id: &MenuId) -> String {
+        self.names
+            .entry(id.clone())
+            .or_insert_with(|| format!("muda-{}", COUNTER.next()))
+            .clone()
+    }
+
+    fn group_name(&mut self, group: RadioGroup) -> String {
+        if let Some((_, name)) = self.groups.iter().find(|(g, _)| g.ptr_eq(&group)) {
+            return name.clone();
+        }
+        let name = format!("muda-{}", COUNTER.next());
+        self.groups.push((group, name.clone()));
+        name
+    }
+
+    /// Returns the action named `name`, adding it to the application if it doesn't exist yet.
+    fn action(&mut self, key: u32, name: &str, kind: ActionKind) -> gio::SimpleAction {
+        if let Some((k, action)) = self.actions.get(name) {
+            if *k == kind {
+                return action.clone();
+            }
+        }
+
+        let action = match kind {
+            ActionKind::Normal => gio::SimpleAction::new(name, None),
+            ActionKind::Check | ActionKind::Submenu => {
+                gio::SimpleAction::new_stateful(name, None, &false.to_variant())
+            }
+            ActionKind::Radio => gio::SimpleAction::new_stateful(
+                name,
+                Some(glib::VariantTy::STRING),
+                &"".to_variant(),
+            ),
+        };
+
+        let action_name = name.to_string();
+        if kind == ActionKind::Submenu {
+            // gtk changes the state when it opens or closes the submenu
+            action.connect_change_state(move |action, value| {
+                if let Some(open) = value.and_then(|v| v.get::<bool>()) {
+                    action.set_state(&open.to_variant());
+                    submenu_toggled(key, &action_name, open);
+                }
+            });
+        } else {
+            action.connect_activate(move |_, parameter| activated(key, &action_name, parameter));
+        }
+
+        self.application.add_action(&action);
+        self.actions
+            .insert(name.to_string(), (kind, action.clone()));
+        action
+    }
+
+    fn action_name(&self, id: &MenuId) -> Option<String> {
+        let name = self.names.get(id)?;
+        if self.actions.contains_key(name) {
+            return Some(format!("app.{name}"));
+        }
+
+        let (_, group) = self
+            .groups
+            .iter()
+            .find(|(g, _)| g.items().iter().any(|i| i.id() == id))?;
+        Some(format!("app.{group}::{name}"))
+    }
+
+    /// Returns the item whose action, or target for radio items, is `name`.
+    fn find(&self, name: &str) -> Option<MenuItemKind> {
+        let id = self
+            .names
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(id, _)| id.clone())?;
+        find_item((self.items)(), &id)
+    }
+
+    /// Remove the actions and accelerators from the application, and empty the model.
+    fn clear(self) {
+        for name in self.actions.keys() {
+            self.application.remove_action(name);
+        }
+        if let Some(application) = &self.gtk_application {
+            for name in self.accels.keys() {
+                application.set_accels_for_action(name, &[]);
+            }
+        }
+        self.menu.remove_all();
+    }
+}
+
+fn find_item(items: Vec<MenuItemKind>, id: &MenuId) -> Option<MenuItemKind> {
+    for item in items {
+        if item.id() == id {
+            return Some(item);
+        }
+        if let MenuItemKind::Submenu(submenu) = &item {
+            if let Some(item) = find_item(submenu.items(), id) {
+                return Some(item);
+            }
+        }
+    }
+    None
+}
+
+/// Returns the entries between separators, which become the sections of a menu
+/// when there is more than one.
+fn sections(entries: &[Entry]) -> Vec<&[Entry]> {
+    entries
+        .split(|e| matches!(e, Entry::Separator))
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Fill `menu` with `entries`, the items between separators become sections.
+fn fill(menu: &gio::Menu, entries: &[Entry]) {
+    let sections = sections(entries);
+
+    if sections.len() == 1 {
+        append(menu, sections[0]);
+        return;
+    }
+
+    for entries in sections {
+        let section = gio::Menu::new();
+        append(&section, entries);
+        menu.append_section(None, &section);
+    }
+}
+
+fn append(menu: &gio::Menu, entries: &[Entry]) {
+    for item in entries.iter().filter_map(menu_item) {
+        menu.append_item(&item);
+    }
+}
+
+/// Update `menu`, which was filled with `old`, to show `new`.
+///
+/// The items that didn't change are kept and submenus are updated in place,
+/// so the menus built from the model, and the submenus that are open, stay as they are.
+fn update(menu: &gio::Menu, old: &[Entry], new: &[Entry]) {
+    let (old_sections, new_sections) = (sections(old), sections(new));
+    match (old_sections.len() > 1, new_sections.len() > 1) {
+        (false, false) => update_items(
+            menu,
+            old_sections.first().copied().unwrap_or(&[]),
+            new_sections.first().copied().unwrap_or(&[]),
+        ),
+        (true, true) => {
+            for (position, entries) in new_sections.iter().enumerate() {
+                let section = menu
+                    .item_link(position as i32, "section")
+                    .and_then(|s| s.downcast::<gio::Menu>().ok());
+                match (old_sections.get(position), section) {
+                    (Some(old), Some(section)) => update_items(&section, old, entries),
+                    _ => {
+                        let section = gio::Menu::new();
+                        append(&section, entries);
+                        menu.append_section(None, &section);
+                    }
+                }
+            }
+            for position in (new_sections.len()..old_sections.len()).rev() {
+                menu.remove(position as i32);
+            }
+        }
+        // separators were added or removed, so the items move in or out of sections
+        _ => {
+            menu.remove_all();
+            fill(menu, new);
+        }
+    }
+}
+
+/// Update the items of `menu`, which are `old`, to be `new`,
+/// only replacing the ones between the unchanged items at the start and at the end.
+fn update_items(menu: &gio::Menu, old: &[Entry], new: &[Entry]) {
+    let len = old.len().min(new.len());
+    let start = (0..len)
+        .take_while(|&i| keep(menu, i, &old[i], &new[i]))
+        .count();
+    let end = (0..len - start)
+        .take_while(|&i| {
+            let (old_i, new_i) = (old.len() - 1 - i, new.len() - 1 - i);
+            keep(menu, old_i, &old[old_i], &new[new_i])
+        })
+        .count();
+
+    for _ in start..old.len() - end {
+        menu.remove(start as i32);
+    }
+    for (position, entry) in new[start..new.len() - end].iter().enumerate() {
+        if let Some(item) = menu_item(entry) {
+            menu.insert_item((start + position) as i32, &item);
+        }
+    }
+}
+
+/// Whether the item at `position` of `menu`, which is `old`, can stay for `new`,
+/// updating its submenu in place if both are the same submenu with different items.
+fn keep(menu: &gio::Menu, position: usize, old: &Entry, new: &Entry) -> bool {
+    if old == new {
+        return true;
+    }
+
+    match (old, new) {
+        (
+            Entry::Submenu {
+                label,
+                action,
+                entries: old,
+            },
+            Entry::Submenu {
+                label: new_label,
+                action: new_action,
+                entries: new,
+            },
+        ) if label == new_label && action == new_action => {
+            let submenu = menu
+                .item_link(position as i32, "submenu")
+                .and_then(|s| s.downcast::<gio::Menu>().ok());
+            match submenu {
+                Some(submenu) => {
+                    update(&submenu, old, new);
+                    true
+                }
+                None => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn menu_item(entry: &Entry) -> Option<gio::MenuItem> {
+    let item = match entry {
+        Entry::Separator => return None,
+        Entry::Item {
+            label,
+            action,
+            target,
+            icon,
+        } => {
+            let item = gio::MenuItem::new(Some(label), None);
+            let target = target.as_ref().map(|t| t.to_variant());
+            item.set_action_and_target_value(Some(&format!("app.{action}")), target.as_ref());
+            if let Some(icon) = icon {
+                item.set_icon(&gio::BytesIcon::new(&glib::Bytes::from(icon)));
+            }
+            item
+        }
+        Entry::Submenu {
+            label,
+            action,
+            entries,
+        } => {
+            let submenu = gio::Menu::new();
+            fill(&submenu, entries);
+            let item = gio::MenuItem::new_submenu(Some(label), &submenu);
+            item.set_attribute_value(
+                "submenu-action",
+                Some(&format!("app.{action}").to_variant()),
+            );
+            item
+        }
+    };
+    Some(item)
+}
+
+/// Returns the item of an action, its root menu, and whether the export has a gtk application.
+fn lookup(key: u32, name: &str) -> Option<(MenuItemKind, MenuId, Option<gtk::Application>)> {
+    EXPORTS.with(|exports| {
+        let exports = exports.borrow();
+        let export = exports.get(&key)?;
+        Some((
+            export.find(name)?,
+            export.root.clone(),
+            export.gtk_application.clone(),
+        ))
+    })
+}
+
+fn activated(key: u32, name: &str, parameter: Option<&Variant>) {
+    // radio items share the action of their group, and are told apart by their target
+    let target = parameter.and_then(|p| p.str()).unwrap_or(name);
+    let (item, root, application) = match lookup(key, target) {
+        Some(found) => found,
+        None => return,
+    };
+
+    let source = event_source(&item, application.as_ref());
+    let window = || {
+        application
+            .as_ref()
+            .and_then(|a| a.active_window())
+            .or_else(super::active_window)
+    };
+
+    if let Some(kind) = super::activate_item(&item, window) {
+        MenuEvent::send(MenuEvent {
+            id: item.id().clone(),
+            kind,
+            source,
+            root: Some(root),
+        });
+    }
+}
+
+fn submenu_toggled(key: u32, name: &str, open: bool) {
+    let (item, root, application) = match lookup(key, name) {
+        Some(found) => found,
+        None => return,
+    };
+
+    MenuEvent::send(MenuEvent {
+        id: item.id().clone(),
+        kind: if open {
+            MenuEventKind::SubmenuOpened
+        } else {
+            MenuEventKind::SubmenuClosed
+        },
+        source: event_source(&item, application.as_ref()),
+        root: Some(root),
+    });
+}
+
+/// Guess what triggered an event for `item` from the event gtk is currently processing,
+/// activations that don't come from a gtk event, like the ones of an exported model,
+/// are reported as programmatic.
+fn event_source(item: &MenuItemKind, application: Option<&gtk::Application>) -> MenuEventSource {
+    if application.is_none() {
+        return MenuEventSource::Programmatic;
+    }
+
+    match super::event_source(None) {
+        MenuEventSource::Keyboard if is_accelerator_event(item) => MenuEventSource::Accelerator,
+        source => source,
+    }
+}
+
+/// Whether the key event gtk is currently processing is the accelerator of `item`.
+fn is_accelerator_event(item: &MenuItemKind) -> bool {
+    let event = match gtk::current_event() {
+        Some(event) => event,
+        None => return false,
+    };
+    let accelerator = item.child().accelerator();
+    let (mods, key) = match accelerator.as_ref().and_then(|a| parse_accelerator(a).ok()) {
+        Some(accelerator) => accelerator,
+        None => return false,
+    };
+
+    let keyval = gdk::keys::Key::from(event.keyval().unwrap_or_default()).to_lower();
+    let state = event.state().unwrap_or_else(gdk::ModifierType::empty)
+        & gtk::accelerator_get_default_mod_mask();
+    gdk::keys::Key::from(key).to_lower() == keyval && state == mods
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use gtk::{
+        gio,
+        glib::{self, ToVariant},
+        prelude::*,
+    };
+
+    use super::GioMenu;
+    use crate::{
+        accelerator::{Accelerator, Code, Modifiers},
+        CheckMenuItem, Menu, MenuEventKind, MenuItem, PredefinedMenuItem, RadioGroup,
+        RadioMenuItem, Submenu,
+    };
+
+    /// Iterate the main context until `f` returns something.
+    fn wait_for<T>(mut f: impl FnMut() -> Option<T>) -> T {
+        let start = Instant::now();
+        loop {
+            glib::MainContext::default().iteration(false);
+            if let Some(value) = f() {
+                return value;
+            }
+            assert!(start.elapsed() < Duration::from_secs(1), "timed out");
+        }
+    }
+
+    fn label(model: &gio::MenuModel, index: i32) -> Option<String> {
+        model
+            .item_attribute_value(index, "label", None)
+            .and_then(|l| l.get())
+    }
+
+    #[test]
+    fn convert_menu_to_gio_menu() {
+        let new = MenuItem::with_id("new", "&New", true, None);
+        let wrap = CheckMenuItem::with_id("wrap", "Wrap", true, false, None);
+        let group = RadioGroup::new();
+        let light = RadioMenuItem::with_id("light", "Light", &group, true, true, None);
+        let dark = RadioMenuItem::with_id("dark", "Dark", &group, true, false, None);
+        let file = Submenu::with_items(
+            "&File",
+            true,
+            &[&new, &wrap, &PredefinedMenuItem::separator(), &light, &dark],
+        )
+        .unwrap();
+        let menu = Menu::with_items(&[&file]).unwrap();
+        let events = menu.events();
+
+        let application = gio::Application::new(None, gio::ApplicationFlags::NON_UNIQUE);
+        let items = menu.clone();
+        let gio_menu =
+            GioMenu::with_application(&application, None, menu.id().clone(), move || items.items());
+
+        let model = gio_menu.menu_model().clone().upcast::<gio::MenuModel>();
+        assert_eq!(model.n_items(), 1);
+        assert_eq!(label(&model, 0), Some("_File".into()));
+
+        // the separator splits the submenu into sections
+        let submenu = model.item_link(0, "submenu").unwrap();
+        assert_eq!(submenu.n_items(), 2);
+        let section = submenu.item_link(0, "section").unwrap();
+        assert_eq!(section.n_items(), 2);
+        assert_eq!(label(&section, 0), Some("_New".into()));
+
+        let action = |id| {
+            let name = gio_menu.action_name(id).unwrap();
+            let (name, target) = match name.split_once("::") {
+                Some((name, target)) => (name.to_string(), Some(target.to_variant())),
+                None => (name, None),
+            };
+            let action = application
+                .lookup_action(name.trim_start_matches("app."))
+                .unwrap();
+            (action, target)
+        };
+
+        // activating the actions is reported as menu events of the converted menu
+        let (new_action, _) = action(new.id());
+        new_action.activate(None);
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.id(), new.id());
+        assert_eq!(event.kind(), MenuEventKind::Activated);
+        assert_eq!(event.root(), Some(menu.id()));
+
+        let (wrap_action, _) = action(wrap.id());
+        assert_eq!(wrap_action.state().and_then(|s| s.get()), Some(false));
+        wrap_action.activate(None);
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.kind(), MenuEventKind::Toggled { checked: true });
+        assert!(wrap.is_checked());
+        wait_for(|| (wrap_action.state().and_then(|s| s.get()) == Some(true)).then_some(()));
+
+        // radio items share the action of their group
+        let (group_action, target) = action(dark.id());
+        let (light_action, light_target) = action(light.id());
+        assert_eq!(group_action.name(), light_action.name());
+        assert_eq!(group_action.state(), light_target);
+        group_action.activate(target.as_ref());
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.id(), dark.id());
+        assert!(dark.is_checked());
+        wait_for(|| (group_action.state() == target).then_some(()));
+
+        // changes to the menu are synced, and the model is updated in place
+        new.set_enabled(false);
+        wait_for(|| (!new_action.is_enabled()).then_some(()));
+        file.append(&MenuItem::new("Open", true, None)).unwrap();
+        wait_for(|| (submenu.item_link(1, "section")?.n_items() == 3).then_some(()));
+        file.remove(&wrap).unwrap();
+        wait_for(|| (section.n_items() == 1).then_some(()));
+        assert_eq!(model.item_link(0, "submenu"), Some(submenu));
+        assert_eq!(label(&section, 0), Some("_New".into()));
+
+        drop(gio_menu);
+        assert!(application
+            .lookup_action(new_action.name().as_str())
+            .is_none());
+        assert_eq!(model.n_items(), 0);
+    }
+
+    #[test]
+    #[ignore = "needs a display"]
+    fn register_accelerators_with_gtk_application() {
+        gtk::init().expect("failed to initialize gtk");
+
+        let ctrl = |code| Some(Accelerator::new(Some(Modifiers::CONTROL), code));
+        let quit = MenuItem::with_id("quit", "Quit", true, ctrl(Code::KeyQ));
+        let wrap = CheckMenuItem::with_id("wrap", "Wrap", true, false, ctrl(Code::KeyW));
+        let group = RadioGroup::new();
+        let light =
+            RadioMenuItem::with_id("light", "Light", &group, true, true, ctrl(Code::Digit1));
+        let menu = Menu::with_items(&[&quit, &wrap, &light]).unwrap();
+
+        let application = gtk::Application::new(None, gio::ApplicationFlags::NON_UNIQUE);
+        let gio_menu = menu.to_gio_menu(&application);
+        let action = |id| gio_menu.action_name(id).unwrap();
+        let accels = |id| {
+            application
+                .accels_for_action(&action(id))
+                .iter()
+                .map(|a| gtk::accelerator_parse(a))
+                .collect::<Vec<_>>()
+        };
+        let parsed = |accel| vec![gtk::accelerator_parse(accel)];
+
+        assert_eq!(accels(quit.id()), parsed("<Control>q"));
+        assert_eq!(accels(wrap.id()), parsed("<Control>w"));
+        assert_eq!(accels(light.id()), parsed("<Control>1"));
+        assert_eq!(
+            application.actions_for_accel("<Control>q"),
+            [action(quit.id())]
+        );
+
+        // changed accelerators are registered again
+        quit.set_accelerator(ctrl(Code::KeyX)).unwrap();
+        wait_for(|| (accels(quit.id()) == parsed("<Control>x")).then_some(()));
+        assert!(application.actions_for_accel("<Control>q").is_empty());
+
+        drop(gio_menu);
+        assert!(application.actions_for_accel("<Control>x").is_empty());
+        assert!(application.actions_for_accel("<Control>w").is_empty());
+    }
+}
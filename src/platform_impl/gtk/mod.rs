@@ -3,20 +3,28 @@
 // SPDX-License-Identifier: MIT
 
 mod accelerator;
+mod actions;
+#[cfg(feature = "dbusmenu")]
+mod dbusmenu;
+mod gmenu;
 mod icon;
 
+#[cfg(feature = "dbusmenu")]
+pub use dbusmenu::DbusMenuExport;
+pub use gmenu::GioMenu;
 pub(crate) use icon::PlatformIcon;
 
+use super::RootMenu;
 use crate::{
-    accelerator::Accelerator,
+    accelerator::{Accelerator, KeySequence},
     dpi::Position,
     icon::{Icon, NativeIcon},
     items::*,
     util::{AddOp, Counter},
-    IsMenuItem, MenuEvent, MenuId, MenuItemKind, MenuItemType,
+    IsMenuItem, MenuEvent, MenuEventKind, MenuEventSource, MenuId, MenuItemKind, MenuItemType,
 };
 use accelerator::{from_gtk_mnemonic, parse_accelerator, to_gtk_mnemonic};
-use gtk::{gdk, prelude::*, AboutDialog, Container, Orientation};
+use gtk::{gdk, prelude::*, Container, Orientation};
 use std::{
     cell::RefCell,
     collections::HashMap,
@@ -30,19 +38,11 @@ macro_rules! is_item_supported {
     ($item:tt) => {{
         let child = $item.child();
         let child_ = child.borrow();
-        let supported = if let Some(predefined_item_type) = &child_.predefined_item_type {
-            matches!(
-                predefined_item_type,
-                PredefinedMenuItemType::Separator
-                    | PredefinedMenuItemType::Copy
-                    | PredefinedMenuItemType::Cut
-                    | PredefinedMenuItemType::Paste
-                    | PredefinedMenuItemType::SelectAll
-                    | PredefinedMenuItemType::About(_)
-            )
-        } else {
-            true
-        };
+        let supported = child_
+            .predefined_item_type
+            .as_ref()
+            .map(actions::is_supported)
+            .unwrap_or(true);
         drop(child_);
         supported
     }};
@@ -63,10 +63,14 @@ pub struct Menu {
     gtk_menubars: HashMap<u32, gtk::MenuBar>,
     accel_group: Option<gtk::AccelGroup>,
     gtk_menu: (u32, Option<gtk::Menu>), // dedicated menu for tray or context menus
+    strict_ids: bool,
+    strict_accelerators: bool,
 }
 
 impl Drop for Menu {
     fn drop(&mut self) {
+        MenuEvent::remove_root_senders(self as *const Self as usize);
+
         for (id, menu) in &self.gtk_menubars {
             drop_children_from_menu_and_destroy(*id, menu, &self.children);
             unsafe { menu.destroy() }
@@ -84,6 +88,8 @@ impl Menu {
         Self {
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             children: Vec::new(),
+            strict_ids: false,
+            strict_accelerators: false,
             gtk_menubars: HashMap::new(),
             accel_group: None,
             gtk_menu: (COUNTER.next(), None),
@@ -94,6 +100,22 @@ impl Menu {
         &self.id
     }
 
+    pub fn is_strict_ids(&self) -> bool {
+        self.strict_ids
+    }
+
+    pub fn set_strict_ids(&mut self, strict: bool) {
+        self.strict_ids = strict;
+    }
+
+    pub fn is_strict_accelerators(&self) -> bool {
+        self.strict_accelerators
+    }
+
+    pub fn set_strict_accelerators(&mut self, strict: bool) {
+        self.strict_accelerators = strict;
+    }
+
     pub fn add_menu_item(&mut self, item: &dyn crate::IsMenuItem, op: AddOp) -> crate::Result<()> {
         if is_item_supported!(item) {
             for (menu_id, menu_bar) in &self.gtk_menubars {
@@ -103,7 +125,7 @@ impl Menu {
                     AddOp::Append => menu_bar.append(&gtk_item),
                     AddOp::Insert(position) => menu_bar.insert(&gtk_item, position as i32),
                 }
-                gtk_item.show();
+                show_gtk_item(&gtk_item);
             }
 
             {
@@ -114,7 +136,7 @@ impl Menu {
                         AddOp::Append => menu.append(&gtk_item),
                         AddOp::Insert(position) => menu.insert(&gtk_item, position as i32),
                     }
-                    gtk_item.show();
+                    show_gtk_item(&gtk_item);
                 }
             }
         }
@@ -124,6 +146,8 @@ impl Menu {
             AddOp::Insert(position) => self.children.insert(position, item.child()),
         }
 
+        layout_changed(&self.id);
+
         Ok(())
     }
 
@@ -133,7 +157,7 @@ impl Menu {
         for (menu_id, menu_bar) in self.gtk_menubars.iter().filter(|m| *m.0 == id) {
             let gtk_item = item.make_gtk_menu_item(*menu_id, self.accel_group.as_ref(), true)?;
             menu_bar.append(&gtk_item);
-            gtk_item.show();
+            show_gtk_item(&gtk_item);
         }
 
         Ok(())
@@ -146,14 +170,18 @@ impl Menu {
         if let Some(menu) = menu {
             let gtk_item = item.make_gtk_menu_item(*menu_id, self.accel_group.as_ref(), true)?;
             menu.append(&gtk_item);
-            gtk_item.show();
+            show_gtk_item(&gtk_item);
         }
 
         Ok(())
     }
 
     pub fn remove(&mut self, item: &dyn crate::IsMenuItem) -> crate::Result<()> {
-        self.remove_inner(item, true, None)
+        self.remove_inner(item, true, None)?;
+
+        layout_changed(&self.id);
+
+        Ok(())
     }
 
     fn remove_inner(
@@ -266,6 +294,7 @@ impl Menu {
         // so we need to create the menubar and its parent box
         if self.gtk_menubars.get(&id).is_none() {
             let menu_bar = gtk::MenuBar::new();
+            set_root_id(&menu_bar, &self.id);
             self.gtk_menubars.insert(id, menu_bar);
         } else {
             return Err(crate::Error::AlreadyInitialized);
@@ -374,7 +403,9 @@ impl Menu {
 
         {
             if self.gtk_menu.1.is_none() {
-                self.gtk_menu.1 = Some(gtk::Menu::new());
+                let menu = gtk::Menu::new();
+                set_root_id(&menu, &self.id);
+                self.gtk_menu.1 = Some(menu);
                 add_items = true;
             }
         }
@@ -396,12 +427,20 @@ pub struct MenuChild {
     item_type: MenuItemType,
     text: String,
     enabled: bool,
+    visible: bool,
+    tooltip: Option<String>,
+    secondary_text: Option<String>,
     id: MenuId,
+    /// The menus whose tree this item was added to, see [`crate::Menu::set_strict_ids`].
+    root_menus: Vec<RootMenu>,
 
     gtk_menu_items: Rc<RefCell<HashMap<u32, Vec<gtk::MenuItem>>>>,
 
     // menu item fields
     accelerator: Option<Accelerator>,
+    /// The key sequence this item had before a keymap changed it, see [`crate::Menu::revert_keymap`].
+    keymap_default: Option<Option<KeySequence>>,
+    key_sequence: Option<KeySequence>,
     gtk_accelerator: Option<(gdk::ModifierType, u32)>,
 
     // predefined menu item fields
@@ -411,6 +450,9 @@ pub struct MenuChild {
     checked: Option<Rc<AtomicBool>>,
     is_syncing_checked_state: Option<Rc<AtomicBool>>,
 
+    // radio menu item fields
+    radio_group: Option<RadioGroup>,
+
     // icon menu item fields
     icon: Option<Icon>,
 
@@ -424,6 +466,8 @@ pub struct MenuChild {
 impl Drop for MenuChild {
     fn drop(&mut self) {
         if self.item_type == MenuItemType::Submenu {
+            MenuEvent::remove_root_senders(self as *const Self as usize);
+
             for menus in self.gtk_menus.as_ref().unwrap().values() {
                 for (id, menu) in menus {
                     drop_children_from_menu_and_destroy(*id, menu, self.children.as_ref().unwrap());
@@ -495,7 +539,13 @@ impl MenuChild {
         Self {
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             item_type: MenuItemType::MenuItem,
             gtk_menu_items: Rc::new(RefCell::new(HashMap::new())),
@@ -507,6 +557,7 @@ impl MenuChild {
             gtk_menus: None,
             icon: None,
             is_syncing_checked_state: None,
+            radio_group: None,
             predefined_item_type: None,
         }
     }
@@ -515,6 +566,9 @@ impl MenuChild {
         Self {
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             children: Some(Vec::new()),
             item_type: MenuItemType::Submenu,
@@ -525,8 +579,12 @@ impl MenuChild {
             gtk_accelerator: None,
             icon: None,
             is_syncing_checked_state: None,
+            radio_group: None,
             predefined_item_type: None,
             accelerator: None,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             checked: None,
         }
     }
@@ -535,7 +593,13 @@ impl MenuChild {
         Self {
             text: text.unwrap_or_else(|| item_type.text().to_string()),
             enabled: true,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             accelerator: item_type.accelerator(),
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             id: MenuId(COUNTER.next().to_string()),
             item_type: MenuItemType::Predefined,
             predefined_item_type: Some(item_type),
@@ -548,6 +612,7 @@ impl MenuChild {
             gtk_menus: None,
             icon: None,
             is_syncing_checked_state: None,
+            radio_group: None,
         }
     }
 
@@ -561,9 +626,16 @@ impl MenuChild {
         Self {
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             checked: Some(Rc::new(AtomicBool::new(checked))),
             is_syncing_checked_state: Some(Rc::new(AtomicBool::new(false))),
+            radio_group: None,
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             item_type: MenuItemType::Check,
             gtk_menu_items: Rc::new(RefCell::new(HashMap::new())),
@@ -587,8 +659,14 @@ impl MenuChild {
         Self {
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             icon,
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             item_type: MenuItemType::Icon,
             gtk_menu_items: Rc::new(RefCell::new(HashMap::new())),
@@ -599,6 +677,7 @@ impl MenuChild {
             gtk_menu: None,
             gtk_menus: None,
             is_syncing_checked_state: None,
+            radio_group: None,
             predefined_item_type: None,
         }
     }
@@ -613,7 +692,13 @@ impl MenuChild {
         Self {
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             item_type: MenuItemType::Icon,
             gtk_menu_items: Rc::new(RefCell::new(HashMap::new())),
@@ -625,6 +710,41 @@ impl MenuChild {
             gtk_menus: None,
             icon: None,
             is_syncing_checked_state: None,
+            radio_group: None,
+            predefined_item_type: None,
+        }
+    }
+
+    pub fn new_radio(
+        text: &str,
+        enabled: bool,
+        checked: bool,
+        accelerator: Option<Accelerator>,
+        radio_group: RadioGroup,
+        id: Option<MenuId>,
+    ) -> Self {
+        Self {
+            text: text.to_string(),
+            enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
+            checked: Some(Rc::new(AtomicBool::new(checked))),
+            is_syncing_checked_state: Some(Rc::new(AtomicBool::new(false))),
+            radio_group: Some(radio_group),
+            accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
+            id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
+            item_type: MenuItemType::Radio,
+            gtk_menu_items: Rc::new(RefCell::new(HashMap::new())),
+            accel_group: None,
+            children: None,
+            gtk_accelerator: None,
+            gtk_menu: None,
+            gtk_menus: None,
+            icon: None,
             predefined_item_type: None,
         }
     }
@@ -648,21 +768,43 @@ impl MenuChild {
             .collect::<Vec<_>>()
             .first()
             .map(|v| v.first())
-            .map(|e| e.map(|i| i.label().map(from_gtk_mnemonic)))
-        {
+            .map(|e| {
+                e.map(|i| {
+                    item_label(i).map(|l| {
+                        from_gtk_mnemonic(l.label().split('\t').next().unwrap_or_default())
+                    })
+                })
+            }) {
             Some(Some(Some(text))) => text,
             _ => self.text.clone(),
         }
     }
 
+    pub fn text_with_mnemonic(&self) -> String {
+        self.text()
+    }
+
+    /// Returns the label of the gtk menu item, which shows the text followed by the key sequence if any.
+    fn gtk_label(&self) -> String {
+        let label = to_gtk_mnemonic(&self.text);
+        match &self.key_sequence {
+            Some(key_sequence) => format!("{label}\t{}", key_sequence.label()),
+            None => label,
+        }
+    }
+
     pub fn set_text(&mut self, text: &str) {
         self.text = text.to_string();
-        let text = to_gtk_mnemonic(text);
+        let text = self.gtk_label();
         for items in self.gtk_menu_items.borrow().values() {
             for i in items {
-                i.set_label(&text);
+                if let Some(label) = item_label(i) {
+                    label.set_label(&text);
+                }
             }
         }
+
+        item_changed(&self.id);
     }
 
     pub fn is_enabled(&self) -> bool {
@@ -687,6 +829,70 @@ impl MenuChild {
                 i.set_sensitive(enabled);
             }
         }
+
+        item_changed(&self.id);
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        for items in self.gtk_menu_items.borrow().values() {
+            for i in items {
+                i.set_no_show_all(!visible);
+                i.set_visible(visible);
+            }
+        }
+
+        item_changed(&self.id);
+    }
+
+    pub fn tooltip(&self) -> Option<String> {
+        self.tooltip.clone()
+    }
+
+    pub fn set_tooltip(&mut self, tooltip: Option<&str>) {
+        self.tooltip = tooltip.map(|t| t.to_string());
+        for items in self.gtk_menu_items.borrow().values() {
+            for i in items {
+                i.set_tooltip_text(tooltip);
+            }
+        }
+    }
+
+    pub fn secondary_text(&self) -> Option<String> {
+        self.secondary_text.clone()
+    }
+
+    pub fn set_secondary_text(&mut self, text: Option<&str>) {
+        self.secondary_text = text.map(|t| t.to_string());
+        for items in self.gtk_menu_items.borrow().values() {
+            for i in items {
+                set_secondary_label(i, text);
+            }
+        }
+    }
+
+    pub fn accelerator(&self) -> Option<Accelerator> {
+        self.accelerator
+    }
+
+    pub fn keymap_default(&self) -> Option<Option<KeySequence>> {
+        self.keymap_default.clone()
+    }
+
+    pub fn set_keymap_default(&mut self, key_sequence: Option<Option<KeySequence>>) {
+        self.keymap_default = key_sequence;
+    }
+
+    pub fn root_menus(&self) -> &[RootMenu] {
+        &self.root_menus
+    }
+
+    pub fn root_menus_mut(&mut self) -> &mut Vec<RootMenu> {
+        &mut self.root_menus
     }
 
     pub fn set_accelerator(&mut self, accelerator: Option<Accelerator>) -> crate::Result<()> {
@@ -717,8 +923,22 @@ impl MenuChild {
         self.gtk_accelerator = new_accel;
         self.accelerator = accelerator;
 
+        item_changed(&self.id);
+
         Ok(())
     }
+
+    pub fn key_sequence(&self) -> Option<KeySequence> {
+        self.key_sequence.clone()
+    }
+
+    pub fn set_key_sequence(&mut self, key_sequence: Option<KeySequence>) {
+        if self.key_sequence == key_sequence {
+            return;
+        }
+        self.key_sequence = key_sequence;
+        self.set_text(&self.text.clone());
+    }
 }
 
 /// CheckMenuItem methods
@@ -747,12 +967,39 @@ impl MenuChild {
         is_syncing.store(true, Ordering::Release);
         for items in self.gtk_menu_items.borrow().values() {
             for i in items {
-                i.downcast_ref::<gtk::CheckMenuItem>()
-                    .unwrap()
-                    .set_active(checked);
+                if self.item_type == MenuItemType::Radio {
+                    set_radio_active(i, checked);
+                } else {
+                    i.downcast_ref::<gtk::CheckMenuItem>()
+                        .unwrap()
+                        .set_active(checked);
+                }
             }
         }
         is_syncing.store(false, Ordering::Release);
+
+        item_changed(&self.id);
+    }
+}
+
+/// PredefinedMenuItem methods
+impl MenuChild {
+    pub(crate) fn predefined_item_type(&self) -> Option<PredefinedMenuItemType> {
+        self.predefined_item_type.clone()
+    }
+
+    pub fn is_supported(&self) -> bool {
+        self.predefined_item_type
+            .as_ref()
+            .map(actions::is_supported)
+            .unwrap_or(true)
+    }
+}
+
+/// RadioMenuItem methods
+impl MenuChild {
+    pub fn radio_group(&self) -> RadioGroup {
+        self.radio_group.clone().unwrap()
     }
 }
 
@@ -771,6 +1018,8 @@ impl MenuChild {
                     .set_pixbuf(pixbuf.as_ref())
             }
         }
+
+        item_changed(&self.id);
     }
 }
 
@@ -786,7 +1035,7 @@ impl MenuChild {
                         AddOp::Append => menu.append(&gtk_item),
                         AddOp::Insert(position) => menu.insert(&gtk_item, position as i32),
                     }
-                    gtk_item.show();
+                    show_gtk_item(&gtk_item);
                 }
             }
 
@@ -798,7 +1047,7 @@ impl MenuChild {
                         AddOp::Append => menu.append(&gtk_item),
                         AddOp::Insert(position) => menu.insert(&gtk_item, position as i32),
                     }
-                    gtk_item.show();
+                    show_gtk_item(&gtk_item);
                 }
             }
         }
@@ -812,6 +1061,8 @@ impl MenuChild {
                 .insert(position, item.child()),
         }
 
+        layout_changed(&self.id);
+
         Ok(())
     }
 
@@ -823,7 +1074,7 @@ impl MenuChild {
                 let gtk_item =
                     item.make_gtk_menu_item(*menu_id, self.accel_group.as_ref(), true)?;
                 menu.append(&gtk_item);
-                gtk_item.show();
+                show_gtk_item(&gtk_item);
             }
         }
 
@@ -837,14 +1088,18 @@ impl MenuChild {
         if let Some(menu) = menu {
             let gtk_item = item.make_gtk_menu_item(*menu_id, self.accel_group.as_ref(), true)?;
             menu.append(&gtk_item);
-            gtk_item.show();
+            show_gtk_item(&gtk_item);
         }
 
         Ok(())
     }
 
     pub fn remove(&mut self, item: &dyn crate::IsMenuItem) -> crate::Result<()> {
-        self.remove_inner(item, true, None)
+        self.remove_inner(item, true, None)?;
+
+        layout_changed(&self.id);
+
+        Ok(())
     }
 
     fn remove_inner(
@@ -957,7 +1212,10 @@ impl MenuChild {
         {
             let gtk_menu = self.gtk_menu.as_mut().unwrap();
             if gtk_menu.1.is_none() {
-                gtk_menu.1 = Some(gtk::Menu::new());
+                let menu = gtk::Menu::new();
+                set_root_id(&menu, &self.id);
+                connect_submenu_events(&menu, &self.id);
+                gtk_menu.1 = Some(menu);
                 add_items = true;
             }
         }
@@ -1010,9 +1268,12 @@ impl MenuChild {
             .sensitive(self.enabled)
             .build();
 
-        item.show();
+        item.set_visible(self.visible);
         item.set_submenu(Some(&submenu));
 
+        connect_submenu_events(&submenu, &self.id);
+        connect_highlight_event(&item, &self.id);
+
         self.accel_group = accel_group.cloned();
 
         let mut id = 0;
@@ -1051,7 +1312,7 @@ impl MenuChild {
         add_to_cache: bool,
     ) -> crate::Result<gtk::MenuItem> {
         let item = gtk::MenuItem::builder()
-            .label(to_gtk_mnemonic(&self.text))
+            .label(self.gtk_label())
             .use_underline(true)
             .sensitive(self.enabled)
             .build();
@@ -1061,9 +1322,10 @@ impl MenuChild {
         register_accel!(self, item, accel_group);
 
         let id = self.id.clone();
-        item.connect_activate(move |_| {
-            MenuEvent::send(crate::MenuEvent { id: id.clone() });
+        item.connect_activate(move |i| {
+            send_event(i, &id, MenuEventKind::Activated);
         });
+        connect_highlight_event(&item, &self.id);
 
         if add_to_cache {
             self.gtk_menu_items
@@ -1118,69 +1380,31 @@ impl MenuChild {
             PredefinedMenuItemType::Copy
             | PredefinedMenuItemType::Cut
             | PredefinedMenuItemType::Paste
-            | PredefinedMenuItemType::SelectAll => {
+            | PredefinedMenuItemType::SelectAll
+            | PredefinedMenuItemType::Undo
+            | PredefinedMenuItemType::Redo => {
                 let item = make_item();
-                let (mods, key) =
-                    parse_accelerator(&predefined_item_type.accelerator().unwrap()).unwrap();
-                item.child()
-                    .unwrap()
-                    .downcast::<gtk::AccelLabel>()
-                    .unwrap()
-                    .set_accel(key, mods);
-                item.connect_activate(move |_| {
-                    // TODO: wayland
-                    #[cfg(feature = "libxdo")]
-                    if let Ok(xdo) = libxdo::XDo::new(None) {
-                        let _ = xdo.send_keysequence(predefined_item_type.xdo_keys(), 0);
-                    }
+                // only displayed, the focused widget handles the shortcut itself
+                if let Some((mods, key)) = self.gtk_accelerator {
+                    item.child()
+                        .unwrap()
+                        .downcast::<gtk::AccelLabel>()
+                        .unwrap()
+                        .set_accel(key, mods);
+                }
+                item.connect_activate(move |i| {
+                    actions::perform(&predefined_item_type, item_window(i).as_ref())
                 });
                 item
             }
-            PredefinedMenuItemType::About(metadata) => {
+            _ => {
                 let item = make_item();
                 register_accel(&item);
-                item.connect_activate(move |_| {
-                    if let Some(metadata) = &metadata {
-                        let mut builder = AboutDialog::builder().modal(true).resizable(false);
-
-                        if let Some(name) = &metadata.name {
-                            builder = builder.program_name(name);
-                        }
-                        if let Some(version) = &metadata.full_version() {
-                            builder = builder.version(version);
-                        }
-                        if let Some(authors) = &metadata.authors {
-                            builder = builder.authors(authors.clone());
-                        }
-                        if let Some(comments) = &metadata.comments {
-                            builder = builder.comments(comments);
-                        }
-                        if let Some(copyright) = &metadata.copyright {
-                            builder = builder.copyright(copyright);
-                        }
-                        if let Some(license) = &metadata.license {
-                            builder = builder.license(license);
-                        }
-                        if let Some(website) = &metadata.website {
-                            builder = builder.website(website);
-                        }
-                        if let Some(website_label) = &metadata.website_label {
-                            builder = builder.website_label(website_label);
-                        }
-                        if let Some(icon) = &metadata.icon {
-                            builder = builder.logo(&icon.inner.to_pixbuf());
-                        }
-
-                        let about = builder.build();
-                        about.run();
-                        unsafe {
-                            about.destroy();
-                        }
-                    }
+                item.connect_activate(move |i| {
+                    actions::perform(&predefined_item_type, item_window(i).as_ref())
                 });
                 item
             }
-            _ => unreachable!(),
         };
 
         if add_to_cache {
@@ -1200,7 +1424,7 @@ impl MenuChild {
         add_to_cache: bool,
     ) -> crate::Result<gtk::MenuItem> {
         let item = gtk::CheckMenuItem::builder()
-            .label(to_gtk_mnemonic(&self.text))
+            .label(self.gtk_label())
             .use_underline(true)
             .sensitive(self.enabled)
             .active(self.checked.as_ref().unwrap().load(Ordering::Relaxed))
@@ -1233,11 +1457,84 @@ impl MenuChild {
 
                 is_syncing_checked_state.store(false, Ordering::Release);
 
-                MenuEvent::send(crate::MenuEvent { id: id.clone() });
+                send_event(i.upcast_ref(), &id, MenuEventKind::Toggled { checked: c });
             }
         });
 
         let item = item.upcast::<gtk::MenuItem>();
+        connect_highlight_event(&item, &self.id);
+
+        if add_to_cache {
+            self.gtk_menu_items
+                .borrow_mut()
+                .entry(menu_id)
+                .or_default()
+                .push(item.clone());
+        }
+
+        Ok(item)
+    }
+
+    fn create_gtk_item_for_radio_menu_item(
+        &mut self,
+        menu_id: u32,
+        accel_group: Option<&gtk::AccelGroup>,
+        add_to_cache: bool,
+    ) -> crate::Result<gtk::MenuItem> {
+        let item = gtk::RadioMenuItem::builder()
+            .label(self.gtk_label())
+            .use_underline(true)
+            .sensitive(self.enabled)
+            .build();
+
+        // A gtk radio item can't be unchecked unless another item in its gtk group
+        // gets checked, so each item gets a hidden sentinel item to hand its state to.
+        // Exclusivity between the items of a `RadioGroup` is handled by us instead.
+        let sentinel = gtk::RadioMenuItem::new();
+        item.join_group(Some(&sentinel));
+        unsafe { item.set_data(RADIO_SENTINEL_KEY, sentinel) };
+        set_radio_active(
+            item.upcast_ref(),
+            self.checked.as_ref().unwrap().load(Ordering::Relaxed),
+        );
+
+        self.accel_group = accel_group.cloned();
+
+        register_accel!(self, item, accel_group);
+
+        let id = self.id.clone();
+        let is_syncing_checked_state = self.is_syncing_checked_state.clone().unwrap();
+        let checked = self.checked.clone().unwrap();
+        let store = self.gtk_menu_items.clone();
+        let radio_group = self.radio_group.clone().unwrap();
+        let this = self as *const MenuChild;
+        item.connect_toggled(move |i| {
+            let should_dispatch = is_syncing_checked_state
+                .compare_exchange(false, true, Ordering::Release, Ordering::Relaxed)
+                .is_ok();
+
+            if should_dispatch {
+                let c = i.is_active();
+                checked.store(c, Ordering::Release);
+
+                for items in store.borrow().values() {
+                    for i in items {
+                        set_radio_active(i, c);
+                    }
+                }
+
+                is_syncing_checked_state.store(false, Ordering::Release);
+
+                // only report the item that became the selected item of its group
+                if c {
+                    radio_group.uncheck_others(this);
+                    send_event(i.upcast_ref(), &id, MenuEventKind::Toggled { checked: c });
+                }
+            }
+        });
+
+        let item = item.upcast::<gtk::MenuItem>();
+        connect_highlight_event(&item, &self.id);
 
         if add_to_cache {
             self.gtk_menu_items
@@ -1265,7 +1562,7 @@ impl MenuChild {
         self.accel_group = accel_group.cloned();
 
         let label = gtk::AccelLabel::builder()
-            .label(to_gtk_mnemonic(&self.text))
+            .label(self.gtk_label())
             .use_underline(true)
             .xalign(0.0)
             .build();
@@ -1292,9 +1589,10 @@ impl MenuChild {
         register_accel!(self, item, accel_group);
 
         let id = self.id.clone();
-        item.connect_activate(move |_| {
-            MenuEvent::send(crate::MenuEvent { id: id.clone() });
+        item.connect_activate(move |i| {
+            send_event(i, &id, MenuEventKind::Activated);
         });
+        connect_highlight_event(&item, &self.id);
 
         if add_to_cache {
             self.gtk_menu_items
@@ -1316,7 +1614,7 @@ impl MenuItemKind {
         add_to_cache: bool,
     ) -> crate::Result<gtk::MenuItem> {
         let mut child = self.child_mut();
-        match child.item_type() {
+        let item = match child.item_type() {
             MenuItemType::Submenu => {
                 child.create_gtk_item_for_submenu(menu_id, accel_group, add_to_cache)
             }
@@ -1332,7 +1630,17 @@ impl MenuItemKind {
             MenuItemType::Icon => {
                 child.create_gtk_item_for_icon_menu_item(menu_id, accel_group, add_to_cache)
             }
+            MenuItemType::Radio => {
+                child.create_gtk_item_for_radio_menu_item(menu_id, accel_group, add_to_cache)
+            }
+        }?;
+        // keep hidden items hidden when their menu is shown with `show_all`
+        item.set_no_show_all(!child.visible);
+        item.set_tooltip_text(child.tooltip.as_deref());
+        if child.secondary_text.is_some() {
+            set_secondary_label(&item, child.secondary_text.as_deref());
         }
+        Ok(item)
     }
 }
 
@@ -1348,6 +1656,298 @@ impl dyn IsMenuItem + '_ {
     }
 }
 
+/// Show a newly added `item`, unless its menu item is hidden.
+fn show_gtk_item(item: &gtk::MenuItem) {
+    if !item.is_no_show_all() {
+        item.show();
+    }
+}
+
+const RADIO_SENTINEL_KEY: &str = "muda-radio-sentinel";
+const ROOT_ID_KEY: &str = "muda-root-id";
+const CONTEXT_WINDOW_KEY: &str = "muda-context-window";
+
+/// Mark `widget` as the top-level widget of the menu with `id`, see [`event_root`].
+fn set_root_id(widget: &impl IsA<gtk::Widget>, id: &MenuId) {
+    unsafe { widget.set_data(ROOT_ID_KEY, id.clone()) };
+}
+
+/// Find the id of the root menu `widget` is part of, by walking up
+/// its parent menus until we reach a menubar or a context menu.
+fn event_root(widget: &gtk::Widget) -> Option<MenuId> {
+    let mut widget = Some(widget.clone());
+    while let Some(w) = widget {
+        if let Some(id) = unsafe { w.data::<MenuId>(ROOT_ID_KEY) } {
+            return Some(unsafe { id.as_ref() }.clone());
+        }
+        widget = match w.downcast_ref::<gtk::Menu>() {
+            Some(menu) => menu.attach_widget(),
+            None => w.parent(),
+        };
+    }
+    None
+}
+
+/// Find the window `item` acts on: the window its menubar is in or its context menu
+/// was shown for, falling back to the active window.
+fn item_window(item: &gtk::MenuItem) -> Option<gtk::Window> {
+    let mut widget = Some(item.clone().upcast::<gtk::Widget>());
+    while let Some(w) = widget {
+        if let Some(window) = unsafe { w.data::<gtk::Window>(CONTEXT_WINDOW_KEY) } {
+            return Some(unsafe { window.as_ref() }.clone());
+        }
+        if let Some(window) = w.downcast_ref::<gtk::Window>() {
+            return Some(window.clone());
+        }
+        widget = match w.downcast_ref::<gtk::Menu>() {
+            Some(menu) => menu.attach_widget(),
+            None => w.parent(),
+        };
+    }
+
+    active_window()
+}
+
+/// Report to the exported menus that the properties of the item with `id` changed.
+fn item_changed(id: &MenuId) {
+    #[cfg(feature = "dbusmenu")]
+    dbusmenu::item_changed(id);
+    gmenu::changed(id);
+}
+
+/// Report to the exported menus that the children of the menu or submenu with `id` changed.
+fn layout_changed(id: &MenuId) {
+    #[cfg(feature = "dbusmenu")]
+    dbusmenu::layout_changed(id);
+    gmenu::changed(id);
+}
+
+/// Activate `item` of an exported menu like a click on its gtk menu item would,
+/// returns the kind of event to report if any.
+///
+/// `window` returns the window predefined items act on.
+fn activate_item<F>(item: &MenuItemKind, window: F) -> Option<MenuEventKind>
+where
+    F: FnOnce() -> Option<gtk::Window>,
+{
+    if !item.child().is_enabled() {
+        return None;
+    }
+
+    match item {
+        MenuItemKind::MenuItem(_) | MenuItemKind::Icon(_) => Some(MenuEventKind::Activated),
+        MenuItemKind::Check(i) => {
+            let checked = !i.is_checked();
+            i.set_checked(checked);
+            Some(MenuEventKind::Toggled { checked })
+        }
+        // only report the item that became the selected item of its group
+        MenuItemKind::Radio(i) if !i.is_checked() => {
+            i.set_checked(true);
+            Some(MenuEventKind::Toggled { checked: true })
+        }
+        MenuItemKind::Predefined(_) => {
+            let item_type = item.child().predefined_item_type.clone();
+            if let Some(item_type) = item_type {
+                actions::perform(&item_type, window().as_ref());
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+fn active_window() -> Option<gtk::Window> {
+    gtk::Window::list_toplevels()
+        .into_iter()
+        .filter_map(|w| w.downcast::<gtk::Window>().ok())
+        .find(|w| w.is_active())
+}
+
+/// Guess what triggered an event for `item` from the event gtk is currently processing.
+fn event_source(item: Option<&gtk::MenuItem>) -> MenuEventSource {
+    let event = gtk::current_event().map(|event| match event.event_type() {
+        gdk::EventType::ButtonPress
+        | gdk::EventType::ButtonRelease
+        | gdk::EventType::MotionNotify
+        | gdk::EventType::EnterNotify
+        | gdk::EventType::LeaveNotify
+        | gdk::EventType::TouchBegin
+        | gdk::EventType::TouchEnd => CurrentEvent::Pointer,
+        gdk::EventType::KeyPress | gdk::EventType::KeyRelease => CurrentEvent::Key {
+            keyval: *gdk::keys::Key::from(event.keyval().unwrap_or_default()).to_lower(),
+            mods: event.state().unwrap_or_else(gdk::ModifierType::empty)
+                & gtk::accelerator_get_default_mod_mask(),
+        },
+        _ => CurrentEvent::Other,
+    });
+
+    let keys = match (&event, item) {
+        (Some(CurrentEvent::Key { .. }), Some(item)) => Some(item_keys(item)),
+        _ => None,
+    };
+    classify_event_source(event, keys)
+}
+
+/// The parts of the event gtk is currently processing that [`classify_event_source`] looks at.
+#[derive(Debug, Clone, Copy)]
+enum CurrentEvent {
+    Pointer,
+    /// A key event, `keyval` is lowercase and `mods` only has the default modifiers.
+    Key {
+        keyval: u32,
+        mods: gdk::ModifierType,
+    },
+    Other,
+}
+
+/// The keys that activate a gtk menu item, with lowercase keyvals.
+#[derive(Debug, Clone, Copy)]
+struct ItemKeys {
+    /// Whether the menu containing the item is shown.
+    menu_shown: bool,
+    accel: Option<(u32, gdk::ModifierType)>,
+    mnemonic: Option<u32>,
+}
+
+fn item_keys(item: &gtk::MenuItem) -> ItemKeys {
+    let label = item_label(item);
+    ItemKeys {
+        menu_shown: item.parent().map(|p| p.is_mapped()).unwrap_or(false),
+        accel: label
+            .as_ref()
+            .and_then(|l| l.downcast_ref::<gtk::AccelLabel>().map(|l| l.accel()))
+            .map(|(key, mods)| (*gdk::keys::Key::from(key).to_lower(), mods)),
+        mnemonic: label.map(|l| *gdk::keys::Key::from(l.mnemonic_keyval()).to_lower()),
+    }
+}
+
+fn classify_event_source(event: Option<CurrentEvent>, item: Option<ItemKeys>) -> MenuEventSource {
+    let (keyval, mods) = match event {
+        None | Some(CurrentEvent::Other) => return MenuEventSource::Programmatic,
+        Some(CurrentEvent::Pointer) => return MenuEventSource::Mouse,
+        Some(CurrentEvent::Key { keyval, mods }) => (keyval, mods),
+    };
+    let item = match item {
+        Some(item) => item,
+        None => return MenuEventSource::Keyboard,
+    };
+
+    // accelerators are the only way to activate an item while its menu is closed
+    if !item.menu_shown || item.accel == Some((keyval, mods)) {
+        MenuEventSource::Accelerator
+    } else if item.mnemonic == Some(keyval) {
+        MenuEventSource::Mnemonic
+    } else {
+        MenuEventSource::Keyboard
+    }
+}
+
+fn send_event(item: &gtk::MenuItem, id: &MenuId, kind: MenuEventKind) {
+    MenuEvent::send(MenuEvent {
+        id: id.clone(),
+        kind,
+        source: event_source(Some(item)),
+        root: event_root(item.upcast_ref()),
+    });
+}
+
+/// Report `menu`, the menu of the submenu with `id`, being shown or hidden.
+fn connect_submenu_events(menu: &gtk::Menu, id: &MenuId) {
+    let send = |menu: &gtk::Menu, id: &MenuId, kind: MenuEventKind| {
+        let item = menu
+            .attach_widget()
+            .and_then(|w| w.downcast::<gtk::MenuItem>().ok());
+        MenuEvent::send(MenuEvent {
+            id: id.clone(),
+            kind,
+            source: event_source(item.as_ref()),
+            root: event_root(menu.upcast_ref()),
+        });
+    };
+
+    let id_ = id.clone();
+    menu.connect_show(move |m| send(m, &id_, MenuEventKind::SubmenuOpened));
+    let id = id.clone();
+    menu.connect_hide(move |m| send(m, &id, MenuEventKind::SubmenuClosed));
+}
+
+fn connect_highlight_event(item: &gtk::MenuItem, id: &MenuId) {
+    let id = id.clone();
+    item.connect_select(move |i| send_event(i, &id, MenuEventKind::Highlighted));
+}
+
+/// Get the label of `item`, which is either its child or inside the box used by icon menu items.
+fn item_label(item: &gtk::MenuItem) -> Option<gtk::Label> {
+    match item.child()?.downcast::<gtk::Label>() {
+        Ok(label) => Some(label),
+        Err(child) => child
+            .downcast::<gtk::Box>()
+            .ok()?
+            .children()
+            .into_iter()
+            .find_map(|c| c.downcast::<gtk::Label>().ok()),
+    }
+}
+
+const SECONDARY_LABEL_KEY: &str = "muda-secondary-label";
+
+/// Show `text` at the end of `item`, after its label.
+///
+/// The secondary label is packed in the box of icon items, other items get their label
+/// moved in a new box the first time they have a secondary text.
+fn set_secondary_label(item: &gtk::MenuItem, text: Option<&str>) {
+    if let Some(label) = unsafe { item.data::<gtk::Label>(SECONDARY_LABEL_KEY) } {
+        let label = unsafe { label.as_ref() };
+        label.set_text(text.unwrap_or_default());
+        label.set_visible(text.is_some());
+        return;
+    }
+
+    let text = match text {
+        Some(text) => text,
+        None => return,
+    };
+
+    // separators don't have a label
+    let box_container = match item.child().map(|c| c.downcast::<gtk::Box>()) {
+        Some(Ok(box_container)) => box_container,
+        Some(Err(child)) => {
+            item.remove(&child);
+            if let Some(label) = child.downcast_ref::<gtk::AccelLabel>() {
+                label.set_accel_widget(Some(item));
+            }
+            let box_container = gtk::Box::new(Orientation::Horizontal, 6);
+            box_container.pack_start(&child, true, true, 0);
+            item.add(&box_container);
+            box_container.show();
+            box_container
+        }
+        None => return,
+    };
+
+    let label = gtk::Label::new(Some(text));
+    label.style_context().add_class("dim-label");
+    // keep the label hidden when its menu is shown with `show_all` after its text is removed
+    label.set_no_show_all(true);
+    label.show();
+    box_container.pack_end(&label, false, false, 0);
+    unsafe { item.set_data(SECONDARY_LABEL_KEY, label) };
+}
+
+fn set_radio_active(item: &gtk::MenuItem, active: bool) {
+    let item = item.downcast_ref::<gtk::RadioMenuItem>().unwrap();
+    if active {
+        item.set_active(true);
+    } else if item.is_active() {
+        for i in item.group() {
+            if &i != item {
+                i.set_active(true);
+            }
+        }
+    }
+}
+
 fn show_context_menu(
     gtk_menu: gtk::Menu,
     widget: &impl IsA<gtk::Widget>,
@@ -1379,6 +1979,13 @@ fn show_context_menu(
         )
     };
 
+    if let Some(window) = widget
+        .toplevel()
+        .and_then(|w| w.downcast::<gtk::Window>().ok())
+    {
+        unsafe { gtk_menu.set_data(CONTEXT_WINDOW_KEY, window) };
+    }
+
     if let Some(window) = window {
         let mut event = gdk::Event::new(gdk::EventType::ButtonPress);
         event.set_device(
@@ -1410,3 +2017,66 @@ impl PredefinedMenuItemType {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use gtk::gdk::{self, keys::constants as key};
+
+    use super::{classify_event_source, CurrentEvent, ItemKeys};
+    use crate::MenuEventSource;
+
+    #[test]
+    fn classify_event_sources() {
+        let ctrl = gdk::ModifierType::CONTROL_MASK;
+        let key_o = |mods| CurrentEvent::Key {
+            keyval: *key::o,
+            mods,
+        };
+        let item = |menu_shown| ItemKeys {
+            menu_shown,
+            accel: Some((*key::o, ctrl)),
+            mnemonic: Some(*key::o),
+        };
+
+        assert_eq!(
+            classify_event_source(None, None),
+            MenuEventSource::Programmatic
+        );
+        assert_eq!(
+            classify_event_source(Some(CurrentEvent::Other), Some(item(true))),
+            MenuEventSource::Programmatic
+        );
+        assert_eq!(
+            classify_event_source(Some(CurrentEvent::Pointer), Some(item(true))),
+            MenuEventSource::Mouse
+        );
+        // key events without an item, like a submenu shown from the keyboard
+        assert_eq!(
+            classify_event_source(Some(key_o(ctrl)), None),
+            MenuEventSource::Keyboard
+        );
+        // the menu of the item is closed, so only its accelerator could have activated it
+        assert_eq!(
+            classify_event_source(Some(key_o(gdk::ModifierType::empty())), Some(item(false))),
+            MenuEventSource::Accelerator
+        );
+        assert_eq!(
+            classify_event_source(Some(key_o(ctrl)), Some(item(true))),
+            MenuEventSource::Accelerator
+        );
+        assert_eq!(
+            classify_event_source(Some(key_o(gdk::ModifierType::empty())), Some(item(true))),
+            MenuEventSource::Mnemonic
+        );
+        assert_eq!(
+            classify_event_source(
+                Some(CurrentEvent::Key {
+                    keyval: *key::Return,
+                    mods: gdk::ModifierType::empty(),
+                }),
+                Some(item(true))
+            ),
+            MenuEventSource::Keyboard
+        );
+    }
+}
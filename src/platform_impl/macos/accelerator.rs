@@ -12,6 +12,11 @@ impl Accelerator {
     ///
     /// Returns the empty string if no key equivalent is known.
     pub fn key_equivalent(self) -> Result<String, AcceleratorParseError> {
+        // key equivalents are characters which macOS resolves using the active keyboard layout
+        if let Some(character) = self.character {
+            return Ok(character.to_string());
+        }
+
         Ok(match self.key {
             Code::KeyA => "a".into(),
             Code::KeyB => "b".into(),
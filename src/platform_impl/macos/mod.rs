@@ -11,8 +11,11 @@ pub(crate) use icon::PlatformIcon;
 use std::{cell::RefCell, collections::HashMap, rc::Rc, sync::Once};
 
 use cocoa::{
-    appkit::{self, CGFloat, NSApp, NSApplication, NSEventModifierFlags, NSMenu, NSMenuItem},
-    base::{id, nil, selector, NO, YES},
+    appkit::{
+        self, CGFloat, NSApp, NSApplication, NSEvent, NSEventModifierFlags, NSEventType, NSMenu,
+        NSMenuItem,
+    },
+    base::{id, nil, selector, BOOL, NO, YES},
     foundation::{
         NSArray, NSAutoreleasePool, NSDictionary, NSInteger, NSPoint, NSRect, NSSize, NSString,
     },
@@ -23,17 +26,26 @@ use objc::{
 };
 
 use self::util::{app_name_string, strip_mnemonic};
+use super::RootMenu;
 use crate::{
-    accelerator::Accelerator,
+    accelerator::{Accelerator, KeySequence},
     dpi::{LogicalPosition, Position},
     icon::{Icon, NativeIcon},
     items::*,
     util::{AddOp, Counter},
-    IsMenuItem, MenuEvent, MenuId, MenuItemKind, MenuItemType,
+    IsMenuItem, MenuEvent, MenuEventKind, MenuEventSource, MenuId, MenuItemKind, MenuItemType,
 };
 
 static COUNTER: Counter = Counter::new();
 static BLOCK_PTR: &str = "mudaMenuItemBlockPtr";
+static MENU_ID_PTR: &str = "mudaMenuIdPtr";
+static MENU_DELEGATE_KEY: u8 = 0;
+
+thread_local! {
+    /// The ids of the menus that own an `NSMenu` which can be at the top of a menu hierarchy,
+    /// either set as the app menu or shown as a context menu, keyed by the `NSMenu` pointer.
+    static ROOT_MENUS: RefCell<HashMap<usize, MenuId>> = RefCell::new(HashMap::new());
+}
 
 #[link(name = "AppKit", kind = "framework")]
 extern "C" {
@@ -44,6 +56,13 @@ extern "C" {
     static NSAboutPanelOptionVersion: id;
 }
 
+extern "C" {
+    fn objc_setAssociatedObject(object: id, key: *const std::ffi::c_void, value: id, policy: usize);
+}
+
+/// https://developer.apple.com/documentation/objectivec/objc_associationpolicy/objc_association_retain
+const OBJC_ASSOCIATION_RETAIN: usize = 0x301;
+
 /// https://developer.apple.com/documentation/appkit/nsapplication/1428479-orderfrontstandardaboutpanelwith#discussion
 #[allow(non_upper_case_globals)]
 const NSAboutPanelOptionCopyright: &str = "Copyright";
@@ -75,10 +94,16 @@ pub struct Menu {
     id: MenuId,
     ns_menu: NsMenuRef,
     children: Vec<Rc<RefCell<MenuChild>>>,
+    strict_ids: bool,
+    strict_accelerators: bool,
 }
 
 impl Drop for Menu {
     fn drop(&mut self) {
+        MenuEvent::remove_root_senders(self as *const Self as usize);
+
+        ROOT_MENUS.with(|menus| menus.borrow_mut().remove(&(self.ns_menu.1 as usize)));
+
         for child in &self.children {
             let mut child_ = child.borrow_mut();
             child_.ns_menu_items.remove(&self.ns_menu.0);
@@ -91,7 +116,7 @@ impl Drop for Menu {
 
 impl Menu {
     pub fn new(id: Option<MenuId>) -> Self {
-        Self {
+        let menu = Self {
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             ns_menu: NsMenuRef(COUNTER.next(), unsafe {
                 let ns_menu = NSMenu::new(nil);
@@ -100,13 +125,34 @@ impl Menu {
                 ns_menu
             }),
             children: Vec::new(),
-        }
+            strict_ids: false,
+            strict_accelerators: false,
+        };
+        register_root_menu(menu.ns_menu.1, &menu.id);
+        unsafe { set_menu_delegate(menu.ns_menu.1, None) };
+        menu
     }
 
     pub fn id(&self) -> &MenuId {
         &self.id
     }
 
+    pub fn is_strict_ids(&self) -> bool {
+        self.strict_ids
+    }
+
+    pub fn set_strict_ids(&mut self, strict: bool) {
+        self.strict_ids = strict;
+    }
+
+    pub fn is_strict_accelerators(&self) -> bool {
+        self.strict_accelerators
+    }
+
+    pub fn set_strict_accelerators(&mut self, strict: bool) {
+        self.strict_accelerators = strict;
+    }
+
     pub fn add_menu_item(&mut self, item: &dyn crate::IsMenuItem, op: AddOp) -> crate::Result<()> {
         let ns_menu_item: id = item.make_ns_item_for_menu(self.ns_menu.0)?;
         let child = item.child();
@@ -193,13 +239,21 @@ pub struct MenuChild {
     // shared fields between submenus and menu items
     item_type: MenuItemType,
     id: MenuId,
+    /// The menus whose tree this item was added to, see [`crate::Menu::set_strict_ids`].
+    root_menus: Vec<RootMenu>,
     text: String,
     enabled: bool,
+    visible: bool,
+    tooltip: Option<String>,
+    secondary_text: Option<String>,
 
     ns_menu_items: HashMap<u32, Vec<NsMenuItemRef>>,
 
     // menu item fields
     accelerator: Option<Accelerator>,
+    /// The key sequence this item had before a keymap changed it, see [`crate::Menu::revert_keymap`].
+    keymap_default: Option<Option<KeySequence>>,
+    key_sequence: Option<KeySequence>,
 
     // predefined menu item fields
     predefined_item_type: Option<PredefinedMenuItemType>,
@@ -207,6 +261,9 @@ pub struct MenuChild {
     // check menu item fields
     checked: bool,
 
+    // radio menu item fields
+    radio_group: Option<RadioGroup>,
+
     // icon menu item fields
     icon: Option<Icon>,
     native_icon: Option<NativeIcon>,
@@ -235,6 +292,12 @@ impl Drop for MenuChild {
         }
 
         if self.item_type == MenuItemType::Submenu {
+            MenuEvent::remove_root_senders(self as *const Self as usize);
+
+            if let Some(menu) = &self.ns_menu {
+                ROOT_MENUS.with(|menus| menus.borrow_mut().remove(&(menu.1 as usize)));
+            }
+
             for menus in self.ns_menus.as_ref().unwrap().values() {
                 for menu in menus {
                     drop_children(menu.0, self.children.as_ref().unwrap())
@@ -258,11 +321,18 @@ impl MenuChild {
     ) -> Self {
         Self {
             item_type: MenuItemType::MenuItem,
-            text: strip_mnemonic(text),
+            text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             checked: false,
+            radio_group: None,
             children: None,
             icon: None,
             native_icon: None,
@@ -274,11 +344,14 @@ impl MenuChild {
     }
 
     pub fn new_submenu(text: &str, enabled: bool, id: Option<MenuId>) -> Self {
-        Self {
+        let submenu = Self {
             item_type: MenuItemType::Submenu,
-            text: strip_mnemonic(text),
+            text: text.to_string(),
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             children: Some(Vec::new()),
             ns_menu: Some(NsMenuRef(COUNTER.next(), unsafe {
                 let menu = NSMenu::new(nil);
@@ -287,46 +360,58 @@ impl MenuChild {
                 menu
             })),
             accelerator: None,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             checked: false,
+            radio_group: None,
             icon: None,
             native_icon: None,
             ns_menu_items: HashMap::new(),
             ns_menus: Some(HashMap::new()),
             predefined_item_type: None,
-        }
+        };
+        register_root_menu(submenu.ns_menu.as_ref().unwrap().1, &submenu.id);
+        unsafe { set_menu_delegate(submenu.ns_menu.as_ref().unwrap().1, Some(&submenu.id)) };
+        submenu
     }
 
     pub(crate) fn new_predefined(item_type: PredefinedMenuItemType, text: Option<String>) -> Self {
-        let text = strip_mnemonic(text.unwrap_or_else(|| {
-            match item_type {
-                PredefinedMenuItemType::About(_) => {
-                    format!("About {}", unsafe { app_name_string() }.unwrap_or_default())
-                        .trim()
-                        .to_string()
-                }
-                PredefinedMenuItemType::Hide => {
-                    format!("Hide {}", unsafe { app_name_string() }.unwrap_or_default())
-                        .trim()
-                        .to_string()
-                }
-                PredefinedMenuItemType::Quit => {
-                    format!("Quit {}", unsafe { app_name_string() }.unwrap_or_default())
-                        .trim()
-                        .to_string()
-                }
-                _ => item_type.text().to_string(),
+        let text = text.unwrap_or_else(|| match item_type {
+            PredefinedMenuItemType::About(_) => {
+                format!("About {}", unsafe { app_name_string() }.unwrap_or_default())
+                    .trim()
+                    .to_string()
             }
-        }));
+            PredefinedMenuItemType::Hide => {
+                format!("Hide {}", unsafe { app_name_string() }.unwrap_or_default())
+                    .trim()
+                    .to_string()
+            }
+            PredefinedMenuItemType::Quit => {
+                format!("Quit {}", unsafe { app_name_string() }.unwrap_or_default())
+                    .trim()
+                    .to_string()
+            }
+            _ => item_type.text().to_string(),
+        });
         let accelerator = item_type.accelerator();
 
         Self {
             item_type: MenuItemType::Predefined,
             text,
             enabled: true,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             id: MenuId(COUNTER.next().to_string()),
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             predefined_item_type: Some(item_type),
             checked: false,
+            radio_group: None,
             children: None,
             icon: None,
             native_icon: None,
@@ -347,9 +432,16 @@ impl MenuChild {
             item_type: MenuItemType::Check,
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             checked,
+            radio_group: None,
             children: None,
             icon: None,
             native_icon: None,
@@ -371,10 +463,17 @@ impl MenuChild {
             item_type: MenuItemType::Icon,
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             icon,
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             checked: false,
+            radio_group: None,
             children: None,
             native_icon: None,
             ns_menu: None,
@@ -395,10 +494,17 @@ impl MenuChild {
             item_type: MenuItemType::Icon,
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
             native_icon,
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             checked: false,
+            radio_group: None,
             children: None,
             icon: None,
             ns_menu: None,
@@ -407,6 +513,38 @@ impl MenuChild {
             predefined_item_type: None,
         }
     }
+
+    pub fn new_radio(
+        text: &str,
+        enabled: bool,
+        checked: bool,
+        accelerator: Option<Accelerator>,
+        radio_group: RadioGroup,
+        id: Option<MenuId>,
+    ) -> Self {
+        Self {
+            item_type: MenuItemType::Radio,
+            text: text.to_string(),
+            enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
+            id: id.unwrap_or_else(|| MenuId(COUNTER.next().to_string())),
+            accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
+            checked,
+            radio_group: Some(radio_group),
+            children: None,
+            icon: None,
+            native_icon: None,
+            ns_menu: None,
+            ns_menu_items: HashMap::new(),
+            ns_menus: None,
+            predefined_item_type: None,
+        }
+    }
 }
 
 /// Shared methods
@@ -420,13 +558,25 @@ impl MenuChild {
     }
 
     pub fn text(&self) -> String {
+        strip_mnemonic(&self.text)
+    }
+
+    pub fn text_with_mnemonic(&self) -> String {
         self.text.clone()
     }
 
+    /// Returns the title of the native menu item, which shows the text followed by the key sequence if any.
+    fn title(&self) -> String {
+        match &self.key_sequence {
+            Some(key_sequence) => format!("{}\t{}", self.text(), key_sequence.label()),
+            None => self.text(),
+        }
+    }
+
     pub fn set_text(&mut self, text: &str) {
-        self.text = strip_mnemonic(text);
+        self.text = text.to_string();
         unsafe {
-            let title = NSString::alloc(nil).init_str(&self.text).autorelease();
+            let title = NSString::alloc(nil).init_str(&self.title()).autorelease();
             for ns_items in self.ns_menu_items.values() {
                 for ns_item in ns_items {
                     let () = msg_send![ns_item.0, setTitle: title];
@@ -454,6 +604,67 @@ impl MenuChild {
         }
     }
 
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+        for ns_items in self.ns_menu_items.values() {
+            for ns_item in ns_items {
+                unsafe {
+                    let () = msg_send![ns_item.0, setHidden: if visible { NO } else { YES }];
+                }
+            }
+        }
+    }
+
+    pub fn tooltip(&self) -> Option<String> {
+        self.tooltip.clone()
+    }
+
+    pub fn set_tooltip(&mut self, tooltip: Option<&str>) {
+        self.tooltip = tooltip.map(|t| t.to_string());
+        for ns_items in self.ns_menu_items.values() {
+            for ns_item in ns_items {
+                menuitem_set_tooltip(ns_item.0, tooltip);
+            }
+        }
+    }
+
+    pub fn secondary_text(&self) -> Option<String> {
+        self.secondary_text.clone()
+    }
+
+    pub fn set_secondary_text(&mut self, text: Option<&str>) {
+        self.secondary_text = text.map(|t| t.to_string());
+        for ns_items in self.ns_menu_items.values() {
+            for ns_item in ns_items {
+                menuitem_set_badge(ns_item.0, text);
+            }
+        }
+    }
+
+    pub fn accelerator(&self) -> Option<Accelerator> {
+        self.accelerator
+    }
+
+    pub fn keymap_default(&self) -> Option<Option<KeySequence>> {
+        self.keymap_default.clone()
+    }
+
+    pub fn set_keymap_default(&mut self, key_sequence: Option<Option<KeySequence>>) {
+        self.keymap_default = key_sequence;
+    }
+
+    pub fn root_menus(&self) -> &[RootMenu] {
+        &self.root_menus
+    }
+
+    pub fn root_menus_mut(&mut self) -> &mut Vec<RootMenu> {
+        &mut self.root_menus
+    }
+
     pub fn set_accelerator(&mut self, accelerator: Option<Accelerator>) -> crate::Result<()> {
         let key_equivalent = (accelerator)
             .as_ref()
@@ -486,6 +697,25 @@ impl MenuChild {
 
         Ok(())
     }
+
+    pub fn key_sequence(&self) -> Option<KeySequence> {
+        self.key_sequence.clone()
+    }
+
+    pub fn set_key_sequence(&mut self, key_sequence: Option<KeySequence>) {
+        if self.key_sequence == key_sequence {
+            return;
+        }
+        self.key_sequence = key_sequence;
+        unsafe {
+            let title = NSString::alloc(nil).init_str(&self.title()).autorelease();
+            for ns_items in self.ns_menu_items.values() {
+                for ns_item in ns_items {
+                    let () = msg_send![ns_item.0, setTitle: title];
+                }
+            }
+        }
+    }
 }
 
 /// CheckMenuItem methods
@@ -506,8 +736,33 @@ impl MenuChild {
     }
 }
 
+/// PredefinedMenuItem methods
+impl MenuChild {
+    pub(crate) fn predefined_item_type(&self) -> Option<PredefinedMenuItemType> {
+        self.predefined_item_type.clone()
+    }
+
+    pub fn is_supported(&self) -> bool {
+        !matches!(
+            self.predefined_item_type,
+            Some(PredefinedMenuItemType::None)
+        )
+    }
+}
+
+/// RadioMenuItem methods
+impl MenuChild {
+    pub fn radio_group(&self) -> RadioGroup {
+        self.radio_group.clone().unwrap()
+    }
+}
+
 /// IconMenuItem methods
 impl MenuChild {
+    pub fn native_icon(&self) -> Option<NativeIcon> {
+        self.native_icon
+    }
+
     pub fn set_icon(&mut self, icon: Option<Icon>) {
         self.icon = icon.clone();
         self.native_icon = None;
@@ -683,13 +938,22 @@ impl MenuChild {
             ns_menu_item = NSMenuItem::alloc(nil);
             ns_submenu = NSMenu::alloc(nil);
 
-            let title = NSString::alloc(nil).init_str(&self.text).autorelease();
+            let title = NSString::alloc(nil).init_str(&self.text()).autorelease();
             let () = msg_send![ns_submenu, setTitle: title];
             let () = msg_send![ns_menu_item, setTitle: title];
             let () = msg_send![ns_menu_item, setSubmenu: ns_submenu];
             let () = msg_send![ns_submenu, setAutoenablesItems: NO];
+            set_menu_delegate(ns_submenu, Some(&self.id));
 
             let () = msg_send![ns_menu_item, setEnabled: if self.enabled { YES } else { NO }];
+
+            let () = msg_send![ns_menu_item, setHidden: if self.visible { NO } else { YES }];
+
+            menuitem_set_tooltip(ns_menu_item, self.tooltip.as_deref());
+
+            if self.secondary_text.is_some() {
+                menuitem_set_badge(ns_menu_item, self.secondary_text.as_deref());
+            }
         }
 
         let id = COUNTER.next();
@@ -716,7 +980,7 @@ impl MenuChild {
 
     pub fn create_ns_item_for_menu_item(&mut self, menu_id: u32) -> crate::Result<id> {
         let ns_menu_item = create_ns_menu_item(
-            &self.text,
+            &self.title(),
             Some(sel!(fireMenuItemAction:)),
             &self.accelerator,
         )?;
@@ -729,6 +993,14 @@ impl MenuChild {
             (*ns_menu_item).set_ivar(BLOCK_PTR, ptr as usize);
 
             let () = msg_send![ns_menu_item, setEnabled: if self.enabled { YES } else { NO }];
+
+            let () = msg_send![ns_menu_item, setHidden: if self.visible { NO } else { YES }];
+
+            menuitem_set_tooltip(ns_menu_item, self.tooltip.as_deref());
+
+            if self.secondary_text.is_some() {
+                menuitem_set_badge(ns_menu_item, self.secondary_text.as_deref());
+            }
         }
 
         self.ns_menu_items
@@ -747,7 +1019,7 @@ impl MenuChild {
                 let _: () = msg_send![separator, retain];
                 separator
             },
-            _ => create_ns_menu_item(&self.text, item_type.selector(), &self.accelerator)?,
+            _ => create_ns_menu_item(&self.text(), item_type.selector(), &self.accelerator)?,
         };
 
         if let PredefinedMenuItemType::About(_) = item_type {
@@ -762,6 +1034,11 @@ impl MenuChild {
 
         unsafe {
             let () = msg_send![ns_menu_item, setEnabled: if self.enabled { YES } else { NO }];
+            let () = msg_send![ns_menu_item, setHidden: if self.visible { NO } else { YES }];
+            menuitem_set_tooltip(ns_menu_item, self.tooltip.as_deref());
+            if self.secondary_text.is_some() {
+                menuitem_set_badge(ns_menu_item, self.secondary_text.as_deref());
+            }
 
             if let PredefinedMenuItemType::Services = item_type {
                 // we have to assign an empty menu as the app's services menu, and macOS will populate it
@@ -781,7 +1058,7 @@ impl MenuChild {
 
     pub fn create_ns_item_for_check_menu_item(&mut self, menu_id: u32) -> crate::Result<id> {
         let ns_menu_item = create_ns_menu_item(
-            &self.text,
+            &self.title(),
             Some(sel!(fireMenuItemAction:)),
             &self.accelerator,
         )?;
@@ -794,6 +1071,14 @@ impl MenuChild {
             (*ns_menu_item).set_ivar(BLOCK_PTR, ptr as usize);
 
             let () = msg_send![ns_menu_item, setEnabled: if self.enabled { YES } else { NO }];
+
+            let () = msg_send![ns_menu_item, setHidden: if self.visible { NO } else { YES }];
+
+            menuitem_set_tooltip(ns_menu_item, self.tooltip.as_deref());
+
+            if self.secondary_text.is_some() {
+                menuitem_set_badge(ns_menu_item, self.secondary_text.as_deref());
+            }
             if self.checked {
                 let () = msg_send![ns_menu_item, setState: 1_isize];
             }
@@ -809,7 +1094,7 @@ impl MenuChild {
 
     pub fn create_ns_item_for_icon_menu_item(&mut self, menu_id: u32) -> crate::Result<id> {
         let ns_menu_item = create_ns_menu_item(
-            &self.text,
+            &self.title(),
             Some(sel!(fireMenuItemAction:)),
             &self.accelerator,
         )?;
@@ -823,6 +1108,14 @@ impl MenuChild {
 
             let () = msg_send![ns_menu_item, setEnabled: if self.enabled { YES } else { NO }];
 
+            let () = msg_send![ns_menu_item, setHidden: if self.visible { NO } else { YES }];
+
+            menuitem_set_tooltip(ns_menu_item, self.tooltip.as_deref());
+
+            if self.secondary_text.is_some() {
+                menuitem_set_badge(ns_menu_item, self.secondary_text.as_deref());
+            }
+
             if self.icon.is_some() {
                 menuitem_set_icon(ns_menu_item, self.icon.as_ref());
             } else if self.native_icon.is_some() {
@@ -844,6 +1137,8 @@ impl MenuChild {
             MenuItemType::MenuItem => self.create_ns_item_for_menu_item(menu_id),
             MenuItemType::Predefined => self.create_ns_item_for_predefined_menu_item(menu_id),
             MenuItemType::Check => self.create_ns_item_for_check_menu_item(menu_id),
+            // radio items are presented with a check mark, just like check items
+            MenuItemType::Radio => self.create_ns_item_for_check_menu_item(menu_id),
             MenuItemType::Icon => self.create_ns_item_for_icon_menu_item(menu_id),
         }
     }
@@ -893,6 +1188,10 @@ impl dyn IsMenuItem + '_ {
                 .inner
                 .borrow_mut()
                 .create_ns_item_for_icon_menu_item(menu_id),
+            MenuItemKind::Radio(i) => i
+                .inner
+                .borrow_mut()
+                .create_ns_item_for_check_menu_item(menu_id),
         }
     }
 }
@@ -1002,8 +1301,226 @@ extern "C" fn fire_menu_item_click(this: &Object, _: Sel, _item: id) {
             (*item).set_checked(!(*item).is_checked());
         }
 
-        let id = (*item).id().clone();
-        MenuEvent::send(crate::MenuEvent { id });
+        if (*item).item_type == MenuItemType::Radio {
+            // only report the item that became the selected item of its group
+            if (*item).is_checked() {
+                return;
+            }
+            (*item).radio_group().uncheck_others(&**item);
+            (*item).set_checked(true);
+        }
+
+        let kind = match (*item).item_type {
+            MenuItemType::Check | MenuItemType::Radio => MenuEventKind::Toggled {
+                checked: (*item).is_checked(),
+            },
+            _ => MenuEventKind::Activated,
+        };
+
+        MenuEvent::send(crate::MenuEvent {
+            id: (*item).id().clone(),
+            kind,
+            source: event_source(Some(this)),
+            root: event_root(this),
+        });
+    }
+}
+
+fn make_menu_delegate_class() -> *const Class {
+    static mut DELEGATE_CLASS: *const Class = 0 as *const Class;
+    static INIT: Once = Once::new();
+
+    INIT.call_once(|| unsafe {
+        let superclass = class!(NSObject);
+        let mut decl = ClassDecl::new("MudaMenuDelegate", superclass).unwrap();
+
+        // An instance variable which will hold a pointer to the `MenuId` of the submenu
+        // that owns the delegated `NSMenu`, or `0` for root menus.
+        decl.add_ivar::<usize>(MENU_ID_PTR);
+
+        decl.add_method(
+            sel!(dealloc),
+            dealloc_menu_delegate as extern "C" fn(&Object, _),
+        );
+        decl.add_method(
+            sel!(menuWillOpen:),
+            menu_will_open as extern "C" fn(&Object, _, id),
+        );
+        decl.add_method(
+            sel!(menuDidClose:),
+            menu_did_close as extern "C" fn(&Object, _, id),
+        );
+        decl.add_method(
+            sel!(menu:willHighlightItem:),
+            menu_will_highlight_item as extern "C" fn(&Object, _, id, id),
+        );
+
+        DELEGATE_CLASS = decl.register();
+    });
+
+    unsafe { DELEGATE_CLASS }
+}
+
+/// Set a `MudaMenuDelegate` on `ns_menu` which reports submenu and highlight events,
+/// `id` is the id of the submenu that owns `ns_menu`, if any.
+unsafe fn set_menu_delegate(ns_menu: id, id: Option<&MenuId>) {
+    let delegate: id = msg_send![make_menu_delegate_class(), alloc];
+    let delegate: id = msg_send![delegate, init];
+
+    let ptr = id.map_or(0, |id| Box::into_raw(Box::new(id.clone())) as usize);
+    (*delegate).set_ivar(MENU_ID_PTR, ptr);
+
+    let () = msg_send![ns_menu, setDelegate: delegate];
+    // `NSMenu` doesn't retain its delegate, so tie its lifetime to the menu instead
+    objc_setAssociatedObject(
+        ns_menu,
+        &MENU_DELEGATE_KEY as *const u8 as *const _,
+        delegate,
+        OBJC_ASSOCIATION_RETAIN,
+    );
+    let () = msg_send![delegate, release];
+}
+
+/// Returns the id stored in `delegate` if it is a `MudaMenuDelegate` of a submenu.
+unsafe fn menu_delegate_id(delegate: id) -> Option<MenuId> {
+    if delegate == nil {
+        return None;
+    }
+    let is_muda: BOOL = msg_send![delegate, isKindOfClass: &*make_menu_delegate_class()];
+    if is_muda == NO {
+        return None;
+    }
+    let ptr: usize = *(*delegate).get_ivar(MENU_ID_PTR);
+    (ptr != 0).then(|| (*(ptr as *const MenuId)).clone())
+}
+
+extern "C" fn dealloc_menu_delegate(this: &Object, _: Sel) {
+    unsafe {
+        let ptr: usize = *this.get_ivar(MENU_ID_PTR);
+        if ptr != 0 {
+            drop(Box::from_raw(ptr as *mut MenuId));
+        }
+        let _: () = msg_send![super(this, class!(NSObject)), dealloc];
+    }
+}
+
+extern "C" fn menu_will_open(this: &Object, _: Sel, menu: id) {
+    unsafe { send_submenu_event(this, menu, MenuEventKind::SubmenuOpened) }
+}
+
+extern "C" fn menu_did_close(this: &Object, _: Sel, menu: id) {
+    unsafe { send_submenu_event(this, menu, MenuEventKind::SubmenuClosed) }
+}
+
+unsafe fn send_submenu_event(this: &Object, menu: id, kind: MenuEventKind) {
+    if let Some(id) = menu_delegate_id(this as *const Object as id) {
+        MenuEvent::send(crate::MenuEvent {
+            id,
+            kind,
+            source: event_source(None),
+            root: menu_root(menu),
+        });
+    }
+}
+
+extern "C" fn menu_will_highlight_item(_this: &Object, _: Sel, menu: id, item: id) {
+    unsafe {
+        if item == nil {
+            return;
+        }
+
+        let is_muda: BOOL = msg_send![item, isKindOfClass: &*make_menu_item_class()];
+        let id = if is_muda == YES {
+            let ptr: usize = *(*item).get_ivar(BLOCK_PTR);
+            if ptr == 0 {
+                return;
+            }
+            let child = ptr as *mut &mut MenuChild;
+            if (*child).item_type == MenuItemType::Predefined {
+                return;
+            }
+            Some((*child).id().clone())
+        } else {
+            let submenu: id = msg_send![item, submenu];
+            if submenu == nil {
+                return;
+            }
+            let delegate: id = msg_send![submenu, delegate];
+            menu_delegate_id(delegate)
+        };
+
+        if let Some(id) = id {
+            MenuEvent::send(crate::MenuEvent {
+                id,
+                kind: MenuEventKind::Highlighted,
+                source: event_source(None),
+                root: menu_root(menu),
+            });
+        }
+    }
+}
+
+fn register_root_menu(ns_menu: id, id: &MenuId) {
+    ROOT_MENUS.with(|menus| menus.borrow_mut().insert(ns_menu as usize, id.clone()));
+}
+
+/// Find the id of the root menu `ns_menu_item` is part of, by walking up its supermenus.
+unsafe fn event_root(ns_menu_item: &Object) -> Option<MenuId> {
+    menu_root(msg_send![ns_menu_item, menu])
+}
+
+/// Find the id of the root menu `menu` is part of, by walking up its supermenus.
+unsafe fn menu_root(mut menu: id) -> Option<MenuId> {
+    if menu == nil {
+        return None;
+    }
+    loop {
+        let supermenu: id = msg_send![menu, supermenu];
+        if supermenu == nil {
+            break;
+        }
+        menu = supermenu;
+    }
+    ROOT_MENUS.with(|menus| menus.borrow().get(&(menu as usize)).cloned())
+}
+
+/// Guess what triggered the action of `ns_menu_item`, or the menu interaction when `None`,
+/// from the event the app is currently processing.
+unsafe fn event_source(ns_menu_item: Option<&Object>) -> MenuEventSource {
+    let event: id = msg_send![NSApp(), currentEvent];
+    if event == nil {
+        return MenuEventSource::Programmatic;
+    }
+
+    match event.eventType() {
+        NSEventType::NSLeftMouseDown
+        | NSEventType::NSLeftMouseUp
+        | NSEventType::NSRightMouseDown
+        | NSEventType::NSRightMouseUp
+        | NSEventType::NSOtherMouseDown
+        | NSEventType::NSOtherMouseUp
+        | NSEventType::NSMouseMoved
+        | NSEventType::NSLeftMouseDragged
+        | NSEventType::NSRightMouseDragged
+        | NSEventType::NSOtherMouseDragged
+        | NSEventType::NSMouseEntered
+        | NSEventType::NSMouseExited => MenuEventSource::Mouse,
+        NSEventType::NSKeyDown | NSEventType::NSKeyUp => {
+            let ns_menu_item = match ns_menu_item {
+                Some(item) => item,
+                None => return MenuEventSource::Keyboard,
+            };
+            let key_equivalent: id = msg_send![ns_menu_item, keyEquivalent];
+            let chars = event.charactersIgnoringModifiers();
+            let same: NSInteger = msg_send![chars, caseInsensitiveCompare: key_equivalent];
+            // NSOrderedSame
+            if key_equivalent.len() > 0 && same == 0 {
+                MenuEventSource::Accelerator
+            } else {
+                MenuEventSource::Keyboard
+            }
+        }
+        _ => MenuEventSource::Programmatic,
     }
 }
 
@@ -1051,6 +1568,38 @@ fn menuitem_set_icon(menuitem: id, icon: Option<&Icon>) {
     }
 }
 
+fn menuitem_set_tooltip(menuitem: id, tooltip: Option<&str>) {
+    unsafe {
+        let tooltip = match tooltip {
+            Some(tooltip) => NSString::alloc(nil).init_str(tooltip).autorelease(),
+            None => nil,
+        };
+        let _: () = msg_send![menuitem, setToolTip: tooltip];
+    }
+}
+
+/// Shows `text` in the badge of `menuitem`, which is only available from macOS 14.
+fn menuitem_set_badge(menuitem: id, text: Option<&str>) {
+    unsafe {
+        let supported: BOOL = msg_send![menuitem, respondsToSelector: sel!(setBadge:)];
+        if supported == NO {
+            return;
+        }
+
+        let badge = match (text, Class::get("NSMenuItemBadge")) {
+            (Some(text), Some(class)) => {
+                let text = NSString::alloc(nil).init_str(text).autorelease();
+                let badge: id = msg_send![class, alloc];
+                let badge: id = msg_send![badge, initWithString: text];
+                let _: id = msg_send![badge, autorelease];
+                badge
+            }
+            _ => nil,
+        };
+        let _: () = msg_send![menuitem, setBadge: badge];
+    }
+}
+
 fn menuitem_set_native_icon(menuitem: id, icon: Option<NativeIcon>) {
     if let Some(icon) = icon {
         unsafe {
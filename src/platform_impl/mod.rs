@@ -14,21 +14,29 @@ mod platform;
 
 use std::{
     cell::{Ref, RefCell, RefMut},
-    rc::Rc,
+    rc::{Rc, Weak},
 };
 
-use crate::{items::*, IsMenuItem, MenuItemKind, MenuItemType};
+use crate::{items::*, IsMenuItem, MenuId, MenuItemKind, MenuItemType};
 
+#[cfg(all(target_os = "linux", feature = "dbusmenu"))]
+pub use self::platform::DbusMenuExport;
+#[cfg(target_os = "linux")]
+pub use self::platform::GioMenu;
 pub(crate) use self::platform::*;
 
+/// A menu whose tree an item was added to, along with the id of the public [`crate::Menu`].
+pub(crate) type RootMenu = (Rc<MenuId>, Weak<RefCell<Menu>>);
+
 impl dyn IsMenuItem + '_ {
     fn child(&self) -> Rc<RefCell<MenuChild>> {
         match self.kind() {
             MenuItemKind::MenuItem(i) => i.inner,
-            MenuItemKind::Submenu(i) => i.inner,
+            MenuItemKind::Submenu(i) => i.inner.clone(),
             MenuItemKind::Predefined(i) => i.inner,
             MenuItemKind::Check(i) => i.inner,
             MenuItemKind::Icon(i) => i.inner,
+            MenuItemKind::Radio(i) => i.inner,
         }
     }
 }
@@ -72,6 +80,13 @@ impl MenuChild {
                     inner: c,
                 })
             }
+            MenuItemType::Radio => {
+                let id = c.borrow().id().clone();
+                MenuItemKind::Radio(RadioMenuItem {
+                    id: Rc::new(id),
+                    inner: c,
+                })
+            }
         }
     }
 }
@@ -85,6 +100,31 @@ impl MenuItemKind {
             MenuItemKind::Predefined(i) => i,
             MenuItemKind::Check(i) => i,
             MenuItemKind::Icon(i) => i,
+            MenuItemKind::Radio(i) => i,
+        }
+    }
+
+    /// Returns a pointer to the inner item, which identifies this item regardless of its id.
+    pub(crate) fn child_ptr(&self) -> *const MenuChild {
+        match self {
+            MenuItemKind::MenuItem(i) => i.inner.as_ptr(),
+            MenuItemKind::Submenu(i) => i.inner.as_ptr(),
+            MenuItemKind::Predefined(i) => i.inner.as_ptr(),
+            MenuItemKind::Check(i) => i.inner.as_ptr(),
+            MenuItemKind::Icon(i) => i.inner.as_ptr(),
+            MenuItemKind::Radio(i) => i.inner.as_ptr(),
+        }
+    }
+
+    /// Returns the shared inner item.
+    pub(crate) fn child_rc(&self) -> &Rc<RefCell<MenuChild>> {
+        match self {
+            MenuItemKind::MenuItem(i) => &i.inner,
+            MenuItemKind::Submenu(i) => &i.inner,
+            MenuItemKind::Predefined(i) => &i.inner,
+            MenuItemKind::Check(i) => &i.inner,
+            MenuItemKind::Icon(i) => &i.inner,
+            MenuItemKind::Radio(i) => &i.inner,
         }
     }
 
@@ -95,6 +135,7 @@ impl MenuItemKind {
             MenuItemKind::Predefined(i) => i.inner.borrow(),
             MenuItemKind::Check(i) => i.inner.borrow(),
             MenuItemKind::Icon(i) => i.inner.borrow(),
+            MenuItemKind::Radio(i) => i.inner.borrow(),
         }
     }
 
@@ -105,6 +146,7 @@ impl MenuItemKind {
             MenuItemKind::Predefined(i) => i.inner.borrow_mut(),
             MenuItemKind::Check(i) => i.inner.borrow_mut(),
             MenuItemKind::Icon(i) => i.inner.borrow_mut(),
+            MenuItemKind::Radio(i) => i.inner.borrow_mut(),
         }
     }
 }
@@ -2,8 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::fmt;
-
 use keyboard_types::{Code, Modifiers};
 use windows_sys::Win32::UI::{
     Input::KeyboardAndMouse::*,
@@ -27,7 +25,10 @@ impl Accelerator {
             virt_key |= FSHIFT;
         }
 
-        let vk_code = key_to_vk(&self.key)?;
+        let vk_code = match self.character {
+            Some(character) => char_to_vk(character)?,
+            None => key_to_vk(&self.key)?,
+        };
         let mod_code = vk_code >> 8;
         if mod_code & 0x1 != 0 {
             virt_key |= FSHIFT;
@@ -48,6 +49,21 @@ impl Accelerator {
     }
 }
 
+// resolves a character to a virtual key and the modifiers needed to type it
+// in the high byte, using the keyboard layout of the current thread
+fn char_to_vk(character: char) -> Result<VIRTUAL_KEY, AcceleratorParseError> {
+    let mut buf = [0u16; 2];
+    let encoded = character.encode_utf16(&mut buf);
+    let vk_code = match encoded {
+        [c] => unsafe { VkKeyScanW(*c) },
+        _ => -1,
+    };
+    if vk_code == -1 {
+        return Err(AcceleratorParseError::UnsupportedKey(character.to_string()));
+    }
+    Ok(vk_code as VIRTUAL_KEY)
+}
+
 // used to build accelerators table from Key
 fn key_to_vk(key: &Code) -> Result<VIRTUAL_KEY, AcceleratorParseError> {
     Ok(match key {
@@ -165,83 +181,3 @@ fn key_to_vk(key: &Code) -> Result<VIRTUAL_KEY, AcceleratorParseError> {
         key => return Err(AcceleratorParseError::UnsupportedKey(key.to_string())),
     })
 }
-
-impl fmt::Display for Accelerator {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let key_mods: Modifiers = self.mods;
-        if key_mods.contains(Modifiers::CONTROL) {
-            write!(f, "Ctrl+")?;
-        }
-        if key_mods.contains(Modifiers::SHIFT) {
-            write!(f, "Shift+")?;
-        }
-        if key_mods.contains(Modifiers::ALT) {
-            write!(f, "Alt+")?;
-        }
-        if key_mods.contains(Modifiers::SUPER) {
-            write!(f, "Windows+")?;
-        }
-        match &self.key {
-            Code::KeyA => write!(f, "A"),
-            Code::KeyB => write!(f, "B"),
-            Code::KeyC => write!(f, "C"),
-            Code::KeyD => write!(f, "D"),
-            Code::KeyE => write!(f, "E"),
-            Code::KeyF => write!(f, "F"),
-            Code::KeyG => write!(f, "G"),
-            Code::KeyH => write!(f, "H"),
-            Code::KeyI => write!(f, "I"),
-            Code::KeyJ => write!(f, "J"),
-            Code::KeyK => write!(f, "K"),
-            Code::KeyL => write!(f, "L"),
-            Code::KeyM => write!(f, "M"),
-            Code::KeyN => write!(f, "N"),
-            Code::KeyO => write!(f, "O"),
-            Code::KeyP => write!(f, "P"),
-            Code::KeyQ => write!(f, "Q"),
-            Code::KeyR => write!(f, "R"),
-            Code::KeyS => write!(f, "S"),
-            Code::KeyT => write!(f, "T"),
-            Code::KeyU => write!(f, "U"),
-            Code::KeyV => write!(f, "V"),
-            Code::KeyW => write!(f, "W"),
-            Code::KeyX => write!(f, "X"),
-            Code::KeyY => write!(f, "Y"),
-            Code::KeyZ => write!(f, "Z"),
-            Code::Digit0 => write!(f, "0"),
-            Code::Digit1 => write!(f, "1"),
-            Code::Digit2 => write!(f, "2"),
-            Code::Digit3 => write!(f, "3"),
-            Code::Digit4 => write!(f, "4"),
-            Code::Digit5 => write!(f, "5"),
-            Code::Digit6 => write!(f, "6"),
-            Code::Digit7 => write!(f, "7"),
-            Code::Digit8 => write!(f, "8"),
-            Code::Digit9 => write!(f, "9"),
-            Code::Comma => write!(f, ","),
-            Code::Minus => write!(f, "-"),
-            Code::Period => write!(f, "."),
-            Code::Space => write!(f, "Space"),
-            Code::Equal => write!(f, "="),
-            Code::Semicolon => write!(f, ";"),
-            Code::Slash => write!(f, "/"),
-            Code::Backslash => write!(f, "\\"),
-            Code::Quote => write!(f, "\'"),
-            Code::Backquote => write!(f, "`"),
-            Code::BracketLeft => write!(f, "["),
-            Code::BracketRight => write!(f, "]"),
-            Code::Tab => write!(f, "Tab"),
-            Code::Escape => write!(f, "Esc"),
-            Code::Delete => write!(f, "Del"),
-            Code::Insert => write!(f, "Ins"),
-            Code::PageUp => write!(f, "PgUp"),
-            Code::PageDown => write!(f, "PgDn"),
-            // These names match LibreOffice.
-            Code::ArrowLeft => write!(f, "Left"),
-            Code::ArrowRight => write!(f, "Right"),
-            Code::ArrowUp => write!(f, "Up"),
-            Code::ArrowDown => write!(f, "Down"),
-            _ => write!(f, "{:?}", self.key),
-        }
-    }
-}
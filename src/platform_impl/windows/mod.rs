@@ -10,19 +10,21 @@ mod util;
 use self::dark_menu_bar::{WM_UAHDRAWMENU, WM_UAHDRAWMENUITEM};
 pub(crate) use self::icon::WinIcon as PlatformIcon;
 
+use super::RootMenu;
 use crate::{
-    accelerator::Accelerator,
+    accelerator::{Accelerator, AcceleratorStyle, KeySequence},
     dpi::Position,
     icon::{Icon, NativeIcon},
-    items::PredefinedMenuItemType,
+    items::{PredefinedMenuItemType, RadioGroup},
     util::{AddOp, Counter},
-    AboutMetadata, IsMenuItem, MenuEvent, MenuId, MenuItemKind, MenuItemType,
+    AboutMetadata, IsMenuItem, MenuEvent, MenuEventKind, MenuEventSource, MenuId, MenuItemKind,
+    MenuItemType,
 };
 use std::{
     cell::{RefCell, RefMut},
     collections::HashMap,
     fmt::Debug,
-    rc::Rc,
+    rc::{Rc, Weak},
 };
 use util::{decode_wide, encode_wide, Accel};
 use windows_sys::Win32::{
@@ -34,25 +36,76 @@ use windows_sys::Win32::{
         WindowsAndMessaging::{
             AppendMenuW, CreateAcceleratorTableW, CreateMenu, CreatePopupMenu,
             DestroyAcceleratorTable, DestroyMenu, DrawMenuBar, EnableMenuItem, GetCursorPos,
-            GetMenu, GetMenuItemInfoW, InsertMenuW, PostQuitMessage, RemoveMenu, SendMessageW,
-            SetForegroundWindow, SetMenu, SetMenuItemInfoW, ShowWindow, TrackPopupMenu, HACCEL,
-            HMENU, MENUITEMINFOW, MFS_CHECKED, MFS_DISABLED, MF_BYCOMMAND, MF_BYPOSITION,
-            MF_CHECKED, MF_DISABLED, MF_ENABLED, MF_GRAYED, MF_POPUP, MF_SEPARATOR, MF_STRING,
-            MF_UNCHECKED, MIIM_BITMAP, MIIM_STATE, MIIM_STRING, SW_HIDE, SW_MAXIMIZE, SW_MINIMIZE,
-            TPM_LEFTALIGN, WM_CLOSE, WM_COMMAND, WM_NCACTIVATE, WM_NCPAINT,
+            GetMenu, GetMenuItemInfoW, GetSubMenu, InsertMenuW, PostQuitMessage, RemoveMenu,
+            SendMessageW, SetForegroundWindow, SetMenu, SetMenuItemInfoW, ShowWindow,
+            TrackPopupMenu, HACCEL, HMENU, MENUITEMINFOW, MFS_CHECKED, MFS_DISABLED,
+            MFT_RADIOCHECK, MFT_STRING, MF_BYCOMMAND, MF_BYPOSITION, MF_CHECKED, MF_DISABLED,
+            MF_ENABLED, MF_GRAYED, MF_POPUP, MF_SEPARATOR, MF_STRING, MF_UNCHECKED, MIIM_BITMAP,
+            MIIM_FTYPE, MIIM_STATE, MIIM_STRING, SW_HIDE, SW_MAXIMIZE, SW_MINIMIZE, TPM_LEFTALIGN,
+            WM_CLOSE, WM_COMMAND, WM_INITMENUPOPUP, WM_MENUSELECT, WM_NCACTIVATE, WM_NCPAINT,
+            WM_UNINITMENUPOPUP,
         },
     },
 };
 
 static COUNTER: Counter = Counter::new_with_start(1000);
 
+thread_local! {
+    /// The items of every native menu in order, including the hidden items which are removed
+    /// from their native menus since Windows menus can't hide items, see [`MenuChild::set_visible`].
+    static MENU_ITEMS: RefCell<HashMap<HMENU, Vec<Weak<RefCell<MenuChild>>>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Records `children` as the items of the native menus `hmenus`.
+fn set_menu_items(hmenus: [HMENU; 2], children: &[Rc<RefCell<MenuChild>>]) {
+    MENU_ITEMS.with(|menus| {
+        let mut menus = menus.borrow_mut();
+        for hmenu in hmenus {
+            menus.insert(hmenu, children.iter().map(Rc::downgrade).collect());
+        }
+    })
+}
+
+fn remove_menu_items(hmenus: [HMENU; 2]) {
+    // menus can be dropped while the thread local storage is destroyed
+    let _ = MENU_ITEMS.try_with(|menus| {
+        let mut menus = menus.borrow_mut();
+        for hmenu in hmenus {
+            menus.remove(&hmenu);
+        }
+    });
+}
+
+/// Returns the position in the native menu `hmenu` where the item with `internal_id` should be,
+/// counting only the items before it that are in the native menu.
+fn native_position(hmenu: HMENU, internal_id: u32) -> u32 {
+    MENU_ITEMS.with(|menus| {
+        menus
+            .borrow()
+            .get(&hmenu)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(Weak::upgrade)
+                    // the item being shown is already mutably borrowed
+                    .take_while(
+                        |i| matches!(i.try_borrow(), Ok(i) if i.internal_id() != internal_id),
+                    )
+                    .filter(|i| i.borrow().is_in_native_menu())
+                    .count() as u32
+            })
+            .unwrap_or(0)
+    })
+}
+
 macro_rules! inner_menu_child_and_flags {
     ($item:ident) => {{
         let mut flags = 0;
         let child = match $item.kind() {
             MenuItemKind::Submenu(i) => {
                 flags |= MF_POPUP;
-                i.inner
+                i.inner.clone()
             }
             MenuItemKind::MenuItem(i) => {
                 flags |= MF_STRING;
@@ -86,6 +139,14 @@ macro_rules! inner_menu_child_and_flags {
                 flags |= MF_STRING;
                 i.inner
             }
+            MenuItemKind::Radio(i) => {
+                let child = i.inner;
+                flags |= MF_STRING;
+                if child.borrow().checked {
+                    flags |= MF_CHECKED;
+                }
+                child
+            }
         };
 
         (child, flags)
@@ -103,10 +164,14 @@ pub(crate) struct Menu {
     hwnds: Vec<HWND>,
     haccel_store: Rc<RefCell<AccelWrapper>>,
     children: Vec<Rc<RefCell<MenuChild>>>,
+    strict_ids: bool,
+    strict_accelerators: bool,
 }
 
 impl Drop for Menu {
     fn drop(&mut self) {
+        MenuEvent::remove_root_senders(self as *const Self as usize);
+
         for hwnd in self.hwnds.clone() {
             let _ = self.remove_for_hwnd(hwnd);
         }
@@ -144,6 +209,7 @@ impl Drop for Menu {
             DestroyMenu(self.hmenu);
             DestroyMenu(self.hpopupmenu);
         }
+        remove_menu_items([self.hmenu, self.hpopupmenu]);
     }
 }
 
@@ -157,6 +223,8 @@ impl Menu {
             hpopupmenu: unsafe { CreatePopupMenu() },
             haccel_store: Rc::new(RefCell::new((0, HashMap::new()))),
             children: Vec::new(),
+            strict_ids: false,
+            strict_accelerators: false,
             hwnds: Vec::new(),
         }
     }
@@ -165,6 +233,22 @@ impl Menu {
         &self.id
     }
 
+    pub fn is_strict_ids(&self) -> bool {
+        self.strict_ids
+    }
+
+    pub fn set_strict_ids(&mut self, strict: bool) {
+        self.strict_ids = strict;
+    }
+
+    pub fn is_strict_accelerators(&self) -> bool {
+        self.strict_accelerators
+    }
+
+    pub fn set_strict_accelerators(&mut self, strict: bool) {
+        self.strict_accelerators = strict;
+    }
+
     pub fn add_menu_item(&mut self, item: &dyn IsMenuItem, op: AddOp) -> crate::Result<()> {
         let (child, mut flags) = inner_menu_child_and_flags!(item);
 
@@ -181,31 +265,36 @@ impl Menu {
                 flags |= MF_GRAYED;
             }
 
-            let mut text = child_.text.clone();
-
             if let Some(accelerator) = &child_.accelerator {
-                let accel_str = accelerator.to_string();
-
-                text.push('\t');
-                text.push_str(&accel_str);
-
-                AccelAction::add(
-                    &mut self.haccel_store.borrow_mut(),
-                    child_.internal_id(),
-                    accelerator,
-                )?;
+                if child_.visible {
+                    AccelAction::add(
+                        &mut self.haccel_store.borrow_mut(),
+                        child_.internal_id(),
+                        accelerator,
+                    )?;
+                }
             }
 
+            let text = child_.native_text();
+
             let id = child_.internal_id() as usize;
 
             let text = encode_wide(text);
             unsafe {
                 match op {
+                    _ if !child_.visible => {}
                     AddOp::Append => {
                         AppendMenuW(self.hmenu, flags, id, text.as_ptr());
                         AppendMenuW(self.hpopupmenu, flags, id, text.as_ptr());
                     }
                     AddOp::Insert(position) => {
+                        // hidden items are not in the native menus
+                        let position = self
+                            .children
+                            .iter()
+                            .take(position)
+                            .filter(|c| c.borrow().is_in_native_menu())
+                            .count();
                         InsertMenuW(
                             self.hmenu,
                             position as _,
@@ -241,6 +330,15 @@ impl Menu {
                     SetMenuItemInfoW(self.hpopupmenu, child_.internal_id, false.into(), &info);
                 };
             }
+
+            if child_.item_type() == MenuItemType::Radio {
+                let info = create_radio_item_info();
+
+                unsafe {
+                    SetMenuItemInfoW(self.hmenu, child_.internal_id, false.into(), &info);
+                    SetMenuItemInfoW(self.hpopupmenu, child_.internal_id, false.into(), &info);
+                };
+            }
         }
 
         // redraw the menu bar
@@ -259,6 +357,7 @@ impl Menu {
                 AddOp::Append => self.children.push(child),
                 AddOp::Insert(position) => self.children.insert(position, child),
             }
+            set_menu_items([self.hmenu, self.hpopupmenu], &self.children);
         }
 
         Ok(())
@@ -300,6 +399,7 @@ impl Menu {
             .position(|e| e.borrow().internal_id() == id)
             .ok_or(crate::Error::NotAChildOfThisMenu)?;
         self.children.remove(index);
+        set_menu_items([self.hmenu, self.hpopupmenu], &self.children);
 
         Ok(())
     }
@@ -433,13 +533,21 @@ pub(crate) struct MenuChild {
     item_type: MenuItemType,
     text: String,
     enabled: bool,
+    visible: bool,
+    tooltip: Option<String>,
+    secondary_text: Option<String>,
     parents_hemnu: Vec<HMENU>,
     root_menu_haccel_stores: HashMap<u32, Rc<RefCell<AccelWrapper>>>,
 
     // menu item fields
     internal_id: u32,
     id: MenuId,
+    /// The menus whose tree this item was added to, see [`crate::Menu::set_strict_ids`].
+    root_menus: Vec<RootMenu>,
     accelerator: Option<Accelerator>,
+    /// The key sequence this item had before a keymap changed it, see [`crate::Menu::revert_keymap`].
+    keymap_default: Option<Option<KeySequence>>,
+    key_sequence: Option<KeySequence>,
 
     // predefined menu item fields
     predefined_item_type: Option<PredefinedMenuItemType>,
@@ -447,6 +555,9 @@ pub(crate) struct MenuChild {
     // check menu item fields
     checked: bool,
 
+    // radio menu item fields
+    radio_group: Option<RadioGroup>,
+
     // icon menu item fields
     icon: Option<Icon>,
 
@@ -459,10 +570,13 @@ pub(crate) struct MenuChild {
 impl Drop for MenuChild {
     fn drop(&mut self) {
         if self.item_type == MenuItemType::Submenu {
+            MenuEvent::remove_root_senders(self as *const Self as usize);
+
             unsafe {
                 DestroyMenu(self.hmenu);
                 DestroyMenu(self.hpopupmenu);
             }
+            remove_menu_items([self.hmenu, self.hpopupmenu]);
         }
 
         if self.accelerator.is_some() {
@@ -486,14 +600,21 @@ impl MenuChild {
             item_type: MenuItemType::MenuItem,
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             parents_hemnu: Vec::new(),
             internal_id,
             id: id.unwrap_or_else(|| MenuId::new(internal_id.to_string())),
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             root_menu_haccel_stores: HashMap::new(),
             predefined_item_type: None,
             icon: None,
             checked: false,
+            radio_group: None,
             children: None,
             hmenu: 0,
             hpopupmenu: 0,
@@ -506,6 +627,9 @@ impl MenuChild {
             item_type: MenuItemType::Submenu,
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             parents_hemnu: Vec::new(),
             children: Some(Vec::new()),
             hmenu: unsafe { CreateMenu() },
@@ -516,7 +640,11 @@ impl MenuChild {
             predefined_item_type: None,
             icon: None,
             checked: false,
+            radio_group: None,
             accelerator: None,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
         }
     }
 
@@ -526,14 +654,21 @@ impl MenuChild {
             item_type: MenuItemType::Predefined,
             text: text.unwrap_or_else(|| item_type.text().to_string()),
             enabled: true,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             parents_hemnu: Vec::new(),
             internal_id,
             id: MenuId::new(internal_id.to_string()),
             accelerator: item_type.accelerator(),
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             predefined_item_type: Some(item_type),
             root_menu_haccel_stores: HashMap::new(),
             icon: None,
             checked: false,
+            radio_group: None,
             children: None,
             hmenu: 0,
             hpopupmenu: 0,
@@ -552,11 +687,18 @@ impl MenuChild {
             item_type: MenuItemType::Check,
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             parents_hemnu: Vec::new(),
             internal_id,
             id: id.unwrap_or_else(|| MenuId::new(internal_id.to_string())),
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             checked,
+            radio_group: None,
             root_menu_haccel_stores: HashMap::new(),
             predefined_item_type: None,
             icon: None,
@@ -578,14 +720,21 @@ impl MenuChild {
             item_type: MenuItemType::Icon,
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             parents_hemnu: Vec::new(),
             internal_id,
             id: id.unwrap_or_else(|| MenuId::new(internal_id.to_string())),
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             icon,
             root_menu_haccel_stores: HashMap::new(),
             predefined_item_type: None,
             checked: false,
+            radio_group: None,
             children: None,
             hmenu: 0,
             hpopupmenu: 0,
@@ -604,14 +753,55 @@ impl MenuChild {
             item_type: MenuItemType::Icon,
             text: text.to_string(),
             enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
             parents_hemnu: Vec::new(),
             internal_id,
             id: id.unwrap_or_else(|| MenuId::new(internal_id.to_string())),
             accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
             root_menu_haccel_stores: HashMap::new(),
             predefined_item_type: None,
             icon: None,
             checked: false,
+            radio_group: None,
+            children: None,
+            hmenu: 0,
+            hpopupmenu: 0,
+        }
+    }
+
+    pub fn new_radio(
+        text: &str,
+        enabled: bool,
+        checked: bool,
+        accelerator: Option<Accelerator>,
+        radio_group: RadioGroup,
+        id: Option<MenuId>,
+    ) -> Self {
+        let internal_id = COUNTER.next();
+        Self {
+            item_type: MenuItemType::Radio,
+            text: text.to_string(),
+            enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
+            parents_hemnu: Vec::new(),
+            internal_id,
+            id: id.unwrap_or_else(|| MenuId::new(internal_id.to_string())),
+            accelerator,
+            keymap_default: None,
+            key_sequence: None,
+            root_menus: Vec::new(),
+            checked,
+            radio_group: Some(radio_group),
+            root_menu_haccel_stores: HashMap::new(),
+            predefined_item_type: None,
+            icon: None,
             children: None,
             hmenu: 0,
             hpopupmenu: 0,
@@ -660,13 +850,31 @@ impl MenuChild {
             .unwrap_or_else(|| self.text.clone())
     }
 
+    pub fn text_with_mnemonic(&self) -> String {
+        self.text()
+    }
+
+    /// Returns the text of the native menu item, which shows the text followed by the secondary text
+    /// and the accelerator or the key sequence if any.
+    fn native_text(&self) -> String {
+        let shortcut = match self.accelerator {
+            Some(accelerator) => Some(accelerator.display_label(AcceleratorStyle::Windows)),
+            None => self.key_sequence.as_ref().map(|k| k.label()),
+        };
+        // the text after a tab is right-aligned
+        let detail = match (&self.secondary_text, shortcut) {
+            (Some(secondary_text), Some(shortcut)) => Some(format!("{secondary_text}  {shortcut}")),
+            (secondary_text, shortcut) => secondary_text.clone().or(shortcut),
+        };
+        match detail {
+            Some(detail) => format!("{}\t{detail}", self.text),
+            None => self.text.clone(),
+        }
+    }
+
     pub fn set_text(&mut self, text: &str) {
         self.text = text.to_string();
-        let mut text = if let Some(accelerator) = self.accelerator {
-            encode_wide(format!("{text}\t{}", accelerator))
-        } else {
-            encode_wide(text)
-        };
+        let mut text = encode_wide(self.native_text());
         for parent in &self.parents_hemnu {
             let mut info: MENUITEMINFOW = unsafe { std::mem::zeroed() };
             info.cbSize = std::mem::size_of::<MENUITEMINFOW>() as _;
@@ -689,7 +897,7 @@ impl MenuChild {
 
                 (info.fState & MFS_DISABLED) == 0
             })
-            .unwrap_or(self.enabled)
+            .unwrap_or(self.checked)
     }
 
     pub fn set_enabled(&mut self, enabled: bool) {
@@ -705,21 +913,182 @@ impl MenuChild {
         }
     }
 
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Windows menus can't hide items, so hidden items are removed from their native menus
+    /// and inserted back at their position when shown again.
+    pub fn set_visible(&mut self, visible: bool) {
+        if self.visible == visible {
+            return;
+        }
+        self.visible = visible;
+
+        if matches!(
+            self.predefined_item_type,
+            Some(PredefinedMenuItemType::None)
+        ) {
+            return;
+        }
+
+        for parent in self.parents_hemnu.clone() {
+            if visible {
+                let position = native_position(parent, self.internal_id());
+                self.insert_native_item(parent, position);
+            } else {
+                unsafe { RemoveMenu(parent, self.internal_id(), MF_BYCOMMAND) };
+            }
+        }
+
+        self.update_accelerators(true);
+    }
+
+    pub fn tooltip(&self) -> Option<String> {
+        self.tooltip.clone()
+    }
+
+    /// Windows menus don't show tooltips, so the tooltip is only stored.
+    pub fn set_tooltip(&mut self, tooltip: Option<&str>) {
+        self.tooltip = tooltip.map(|t| t.to_string());
+    }
+
+    pub fn secondary_text(&self) -> Option<String> {
+        self.secondary_text.clone()
+    }
+
+    pub fn set_secondary_text(&mut self, text: Option<&str>) {
+        self.secondary_text = text.map(|t| t.to_string());
+        self.set_text(&self.text.clone());
+    }
+
+    /// Adds the accelerators of this item and the items of its submenus to the accelerator tables
+    /// of their root menus if they are shown, or removes them otherwise.
+    fn update_accelerators(&self, shown: bool) {
+        let shown = shown && self.visible;
+        if let Some(accelerator) = self.accelerator {
+            for store in self.root_menu_haccel_stores.values() {
+                let mut store = store.borrow_mut();
+                if shown {
+                    let _ = AccelAction::add(&mut store, self.internal_id, &accelerator);
+                } else {
+                    AccelAction::remove(&mut store, self.internal_id)
+                }
+            }
+        }
+        for child in self.children.iter().flatten() {
+            child.borrow().update_accelerators(shown);
+        }
+    }
+
+    /// Returns whether this item is currently in the native menus of its parents.
+    fn is_in_native_menu(&self) -> bool {
+        self.visible
+            && !matches!(
+                self.predefined_item_type,
+                Some(PredefinedMenuItemType::None)
+            )
+    }
+
+    /// Inserts this item in the native menu `hmenu` at `position`, like when it was added to it.
+    fn insert_native_item(&self, hmenu: HMENU, position: u32) {
+        let mut flags = MF_BYPOSITION;
+        flags |= match self.item_type {
+            MenuItemType::Submenu => MF_POPUP,
+            MenuItemType::Predefined
+                if matches!(
+                    self.predefined_item_type,
+                    Some(PredefinedMenuItemType::Separator)
+                ) =>
+            {
+                MF_SEPARATOR
+            }
+            _ => MF_STRING,
+        };
+        if !self.enabled {
+            flags |= MF_GRAYED;
+        }
+        if matches!(self.item_type, MenuItemType::Check | MenuItemType::Radio) && self.checked {
+            flags |= MF_CHECKED;
+        }
+
+        let text = encode_wide(self.native_text());
+        unsafe {
+            InsertMenuW(
+                hmenu,
+                position,
+                flags,
+                self.internal_id() as usize,
+                text.as_ptr(),
+            )
+        };
+
+        let info = match self.item_type {
+            MenuItemType::Icon => {
+                let hbitmap = self
+                    .icon
+                    .as_ref()
+                    .map(|i| unsafe { i.inner.to_hbitmap() })
+                    .unwrap_or(0);
+                Some(create_icon_item_info(hbitmap))
+            }
+            MenuItemType::Radio => Some(create_radio_item_info()),
+            _ => None,
+        };
+        if let Some(info) = info {
+            unsafe { SetMenuItemInfoW(hmenu, self.internal_id(), false.into(), &info) };
+        }
+    }
+
+    pub fn accelerator(&self) -> Option<Accelerator> {
+        self.accelerator
+    }
+
+    pub fn keymap_default(&self) -> Option<Option<KeySequence>> {
+        self.keymap_default.clone()
+    }
+
+    pub fn set_keymap_default(&mut self, key_sequence: Option<Option<KeySequence>>) {
+        self.keymap_default = key_sequence;
+    }
+
+    pub fn root_menus(&self) -> &[RootMenu] {
+        &self.root_menus
+    }
+
+    pub fn root_menus_mut(&mut self) -> &mut Vec<RootMenu> {
+        &mut self.root_menus
+    }
+
     pub fn set_accelerator(&mut self, accelerator: Option<Accelerator>) -> crate::Result<()> {
         self.accelerator = accelerator;
         self.set_text(&self.text.clone());
 
         for store in self.root_menu_haccel_stores.values() {
             let mut store = store.borrow_mut();
-            if let Some(accelerator) = self.accelerator {
-                AccelAction::add(&mut store, self.internal_id, &accelerator)?
-            } else {
-                AccelAction::remove(&mut store, self.internal_id)
+            match self.accelerator {
+                // hidden items don't trigger their accelerators
+                Some(accelerator) if self.visible => {
+                    AccelAction::add(&mut store, self.internal_id, &accelerator)?
+                }
+                _ => AccelAction::remove(&mut store, self.internal_id),
             }
         }
 
         Ok(())
     }
+
+    pub fn key_sequence(&self) -> Option<KeySequence> {
+        self.key_sequence.clone()
+    }
+
+    pub fn set_key_sequence(&mut self, key_sequence: Option<KeySequence>) {
+        if self.key_sequence == key_sequence {
+            return;
+        }
+        self.key_sequence = key_sequence;
+        self.set_text(&self.text.clone());
+    }
 }
 
 /// CheckMenuItem methods
@@ -755,6 +1124,40 @@ impl MenuChild {
     }
 }
 
+/// PredefinedMenuItem methods
+impl MenuChild {
+    pub(crate) fn predefined_item_type(&self) -> Option<PredefinedMenuItemType> {
+        self.predefined_item_type.clone()
+    }
+
+    pub fn is_supported(&self) -> bool {
+        match &self.predefined_item_type {
+            Some(item_type) => matches!(
+                item_type,
+                PredefinedMenuItemType::Separator
+                    | PredefinedMenuItemType::Copy
+                    | PredefinedMenuItemType::Cut
+                    | PredefinedMenuItemType::Paste
+                    | PredefinedMenuItemType::SelectAll
+                    | PredefinedMenuItemType::Minimize
+                    | PredefinedMenuItemType::Maximize
+                    | PredefinedMenuItemType::Hide
+                    | PredefinedMenuItemType::CloseWindow
+                    | PredefinedMenuItemType::Quit
+                    | PredefinedMenuItemType::About(_)
+            ),
+            None => true,
+        }
+    }
+}
+
+/// RadioMenuItem methods
+impl MenuChild {
+    pub fn radio_group(&self) -> RadioGroup {
+        self.radio_group.clone().unwrap()
+    }
+}
+
 /// IconMenuItem methods
 impl MenuChild {
     pub fn set_icon(&mut self, icon: Option<Icon>) {
@@ -790,29 +1193,36 @@ impl MenuChild {
                 flags |= MF_GRAYED;
             }
 
-            let mut text = child_.text.clone();
-
             if let Some(accelerator) = &child_.accelerator {
-                let accel_str = accelerator.to_string();
-
-                text.push('\t');
-                text.push_str(&accel_str);
-
-                for root_menu in self.root_menu_haccel_stores.values() {
-                    let mut haccel = root_menu.borrow_mut();
-                    AccelAction::add(&mut haccel, child_.internal_id(), accelerator)?;
+                if child_.visible {
+                    for root_menu in self.root_menu_haccel_stores.values() {
+                        let mut haccel = root_menu.borrow_mut();
+                        AccelAction::add(&mut haccel, child_.internal_id(), accelerator)?;
+                    }
                 }
             }
 
+            let text = child_.native_text();
+
             let id = child_.internal_id() as usize;
             let text = encode_wide(text);
             unsafe {
                 match op {
+                    _ if !child_.visible => {}
                     AddOp::Append => {
                         AppendMenuW(self.hmenu, flags, id, text.as_ptr());
                         AppendMenuW(self.hpopupmenu, flags, id, text.as_ptr());
                     }
                     AddOp::Insert(position) => {
+                        // hidden items are not in the native menus
+                        let position = self
+                            .children
+                            .as_ref()
+                            .unwrap()
+                            .iter()
+                            .take(position)
+                            .filter(|c| c.borrow().is_in_native_menu())
+                            .count();
                         InsertMenuW(
                             self.hmenu,
                             position as _,
@@ -848,6 +1258,15 @@ impl MenuChild {
                     SetMenuItemInfoW(self.hpopupmenu, child_.internal_id, false.into(), &info);
                 };
             }
+
+            if child_.item_type() == MenuItemType::Radio {
+                let info = create_radio_item_info();
+
+                unsafe {
+                    SetMenuItemInfoW(self.hmenu, child_.internal_id, false.into(), &info);
+                    SetMenuItemInfoW(self.hpopupmenu, child_.internal_id, false.into(), &info);
+                };
+            }
         }
 
         {
@@ -862,6 +1281,7 @@ impl MenuChild {
                 AddOp::Append => children.push(child),
                 AddOp::Insert(position) => children.insert(position, child),
             }
+            set_menu_items([self.hmenu, self.hpopupmenu], children);
         }
 
         Ok(())
@@ -898,6 +1318,7 @@ impl MenuChild {
             .position(|e| e.borrow().internal_id() == id)
             .ok_or(crate::Error::NotAChildOfThisMenu)?;
         children.remove(index);
+        set_menu_items([self.hmenu, self.hpopupmenu], children);
 
         Ok(())
     }
@@ -966,6 +1387,23 @@ fn find_by_id(id: u32, children: &Vec<Rc<RefCell<MenuChild>>>) -> Option<Rc<RefC
     None
 }
 
+/// Find the id of the submenu that owns `hmenu`
+fn find_submenu_by_hmenu(hmenu: HMENU, children: &Vec<Rc<RefCell<MenuChild>>>) -> Option<MenuId> {
+    for i in children {
+        let item = i.borrow();
+        if item.item_type() == MenuItemType::Submenu {
+            if item.hmenu == hmenu || item.hpopupmenu == hmenu {
+                return Some(item.id().clone());
+            }
+
+            if let Some(id) = find_submenu_by_hmenu(hmenu, item.children.as_ref().unwrap()) {
+                return Some(id);
+            }
+        }
+    }
+    None
+}
+
 fn show_context_menu(hwnd: HWND, hmenu: HMENU, position: Option<Position>) {
     unsafe {
         let pt = if let Some(pos) = position {
@@ -1025,6 +1463,14 @@ fn create_icon_item_info(hbitmap: HBITMAP) -> MENUITEMINFOW {
     info
 }
 
+fn create_radio_item_info() -> MENUITEMINFOW {
+    let mut info: MENUITEMINFOW = unsafe { std::mem::zeroed() };
+    info.cbSize = std::mem::size_of::<MENUITEMINFOW>() as _;
+    info.fMask = MIIM_FTYPE;
+    info.fType = MFT_STRING | MFT_RADIOCHECK;
+    info
+}
+
 const MENU_SUBCLASS_ID: usize = 200;
 const SUBMENU_SUBCLASS_ID: usize = 201;
 const CONTEXT_MENU_SUBCLASS_ID: usize = 203;
@@ -1042,20 +1488,30 @@ unsafe extern "system" fn menu_subclass_proc(
         WM_COMMAND => {
             let id = util::LOWORD(wparam as _) as u32;
 
-            let item = match uidsubclass {
-                MENU_SUBCLASS_ID | CONTEXT_MENU_SUBCLASS_ID => {
-                    let menu = dwrefdata as *mut Box<Menu>;
-                    (*menu).find_by_id(id)
-                }
-                SUBMENU_SUBCLASS_ID | CONTEXT_SUBMENU_SUBCLASS_ID => {
-                    let menu = dwrefdata as *mut Box<MenuChild>;
-                    (*menu).find_by_id(id)
-                }
-                _ => unreachable!(),
+            let (item, root) = subclass_find_by_id(uidsubclass, dwrefdata, id);
+
+            // the high-order word is 1 when the message comes from an accelerator,
+            // keyboard navigation inside an open menu can't be told apart from a click.
+            let source = if util::HIWORD(wparam as _) == 1 {
+                MenuEventSource::Accelerator
+            } else {
+                MenuEventSource::Mouse
             };
 
             if let Some(item) = item {
                 let (mut dispatch, mut menu_id) = (true, None);
+                let mut kind = MenuEventKind::Activated;
+
+                // uncheck the other items of a radio group before borrowing `item`,
+                // so they are never borrowed while they are unchecked
+                let radio_group = {
+                    let item = item.borrow();
+                    (item.item_type() == MenuItemType::Radio && !item.checked)
+                        .then(|| item.radio_group())
+                };
+                if let Some(radio_group) = radio_group {
+                    radio_group.uncheck_others(item.as_ptr());
+                }
 
                 {
                     let mut item = item.borrow_mut();
@@ -1070,6 +1526,15 @@ unsafe extern "system" fn menu_subclass_proc(
                         MenuItemType::Check => {
                             let checked = !item.checked;
                             item.set_checked(checked);
+                            kind = MenuEventKind::Toggled { checked };
+                        }
+                        MenuItemType::Radio => {
+                            // only report the item that became the selected item of its group
+                            dispatch = !item.checked;
+                            if dispatch {
+                                item.set_checked(true);
+                            }
+                            kind = MenuEventKind::Toggled { checked: true };
                         }
                         MenuItemType::Predefined => {
                             if let Some(predefined_item_type) = &item.predefined_item_type {
@@ -1117,6 +1582,9 @@ unsafe extern "system" fn menu_subclass_proc(
                 if dispatch {
                     MenuEvent::send(MenuEvent {
                         id: menu_id.unwrap(),
+                        kind,
+                        source,
+                        root: Some(root),
                     });
                 }
 
@@ -1126,6 +1594,58 @@ unsafe extern "system" fn menu_subclass_proc(
             }
         }
 
+        WM_INITMENUPOPUP | WM_UNINITMENUPOPUP => {
+            let (submenu, root) = subclass_find_submenu(uidsubclass, dwrefdata, wparam as HMENU);
+
+            if let Some(id) = submenu {
+                MenuEvent::send(MenuEvent {
+                    id,
+                    kind: if msg == WM_INITMENUPOPUP {
+                        MenuEventKind::SubmenuOpened
+                    } else {
+                        MenuEventKind::SubmenuClosed
+                    },
+                    source: MenuEventSource::Mouse,
+                    root: Some(root),
+                });
+            }
+
+            DefSubclassProc(hwnd, msg, wparam, lparam)
+        }
+
+        WM_MENUSELECT => {
+            let id = util::LOWORD(wparam as _) as u32;
+            let flags = util::HIWORD(wparam as _) as u32;
+
+            // `0xFFFF` flags and a null menu handle mean the menu was closed
+            let closed = flags == 0xFFFF && lparam == 0;
+            if !closed && flags & MF_SEPARATOR == 0 {
+                // for submenus, the low-order word is the index of the item instead of its id
+                let (item, root) = if flags & MF_POPUP != 0 {
+                    let hmenu = GetSubMenu(lparam as HMENU, id as _);
+                    subclass_find_submenu(uidsubclass, dwrefdata, hmenu)
+                } else {
+                    let (item, root) = subclass_find_by_id(uidsubclass, dwrefdata, id);
+                    let id = item.and_then(|i| {
+                        let i = i.borrow();
+                        (i.item_type() != MenuItemType::Predefined).then(|| i.id().clone())
+                    });
+                    (id, root)
+                };
+
+                if let Some(id) = item {
+                    MenuEvent::send(MenuEvent {
+                        id,
+                        kind: MenuEventKind::Highlighted,
+                        source: MenuEventSource::Mouse,
+                        root: Some(root),
+                    });
+                }
+            }
+
+            DefSubclassProc(hwnd, msg, wparam, lparam)
+        }
+
         WM_UAHDRAWMENUITEM | WM_UAHDRAWMENU => {
             if dark_menu_bar::should_use_dark_mode(hwnd) {
                 dark_menu_bar::draw(hwnd, msg, wparam, lparam);
@@ -1148,6 +1668,54 @@ unsafe extern "system" fn menu_subclass_proc(
     }
 }
 
+/// Find the item with `id` inside the menu attached to [`menu_subclass_proc`],
+/// along with the id of that menu.
+unsafe fn subclass_find_by_id(
+    uidsubclass: usize,
+    dwrefdata: usize,
+    id: u32,
+) -> (Option<Rc<RefCell<MenuChild>>>, MenuId) {
+    match uidsubclass {
+        MENU_SUBCLASS_ID | CONTEXT_MENU_SUBCLASS_ID => {
+            let menu = dwrefdata as *mut Box<Menu>;
+            ((*menu).find_by_id(id), (*menu).id().clone())
+        }
+        SUBMENU_SUBCLASS_ID | CONTEXT_SUBMENU_SUBCLASS_ID => {
+            let menu = dwrefdata as *mut Box<MenuChild>;
+            ((*menu).find_by_id(id), (*menu).id().clone())
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Find the id of the submenu that owns `hmenu` inside the menu attached to [`menu_subclass_proc`],
+/// along with the id of that menu.
+unsafe fn subclass_find_submenu(
+    uidsubclass: usize,
+    dwrefdata: usize,
+    hmenu: HMENU,
+) -> (Option<MenuId>, MenuId) {
+    match uidsubclass {
+        MENU_SUBCLASS_ID | CONTEXT_MENU_SUBCLASS_ID => {
+            let menu = dwrefdata as *mut Box<Menu>;
+            (
+                find_submenu_by_hmenu(hmenu, &(*menu).children),
+                (*menu).id().clone(),
+            )
+        }
+        SUBMENU_SUBCLASS_ID | CONTEXT_SUBMENU_SUBCLASS_ID => {
+            let menu = dwrefdata as *mut Box<MenuChild>;
+            let id = if (*menu).hpopupmenu == hmenu {
+                Some((*menu).id().clone())
+            } else {
+                find_submenu_by_hmenu(hmenu, (*menu).children.as_ref().unwrap())
+            };
+            (id, (*menu).id().clone())
+        }
+        _ => unreachable!(),
+    }
+}
+
 enum EditCommand {
     Copy,
     Cut,
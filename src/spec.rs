@@ -0,0 +1,918 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! A declarative description of a menu tree.
+
+use std::collections::HashMap;
+
+use crate::{
+    accelerator::{Accelerator, KeySequence},
+    items::{PredefinedMenuItemType, RadioGroup},
+    AboutMetadata, CheckMenuItem, IconMenuItem, MenuId, MenuItem, MenuItemKind, NativeIcon,
+    PredefinedMenuItem, RadioMenuItem, Submenu,
+};
+
+#[cfg(feature = "serde")]
+fn default_true() -> bool {
+    true
+}
+
+/// A serializable description of a [`Menu`](crate::Menu) and all its items.
+///
+/// Build a live menu from it with [`Menu::from_spec`](crate::Menu::from_spec)
+/// and snapshot a live menu back with [`Menu::to_spec`](crate::Menu::to_spec).
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MenuSpec {
+    /// Id of the menu, a new unique id is generated if `None`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub id: Option<MenuId>,
+    /// Items of the menu.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub items: Vec<MenuItemSpec>,
+}
+
+/// A serializable description of a [`Submenu`] and all its items.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubmenuSpec {
+    /// Id of the submenu, a new unique id is generated if `None`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub id: Option<MenuId>,
+    /// Text of the submenu.
+    pub text: String,
+    /// Whether the submenu is enabled, defaults to `true`.
+    #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+    pub enabled: bool,
+    /// Whether the submenu is visible, defaults to `true`.
+    #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+    pub visible: bool,
+    /// Tooltip of the submenu.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tooltip: Option<String>,
+    /// Secondary text of the submenu, see [`Submenu::set_secondary_text`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub secondary_text: Option<String>,
+    /// Items of the submenu.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub items: Vec<MenuItemSpec>,
+}
+
+/// A serializable description of a menu item.
+///
+/// Accelerators are described by strings that can be parsed into a [`KeySequence`],
+/// for example `"CmdOrCtrl+Shift+KeyS"` or `"Ctrl+KeyK Ctrl+KeyC"`.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "snake_case")
+)]
+pub enum MenuItemSpec {
+    /// A [`Submenu`].
+    Submenu(SubmenuSpec),
+    /// A [`MenuItem`].
+    #[cfg_attr(feature = "serde", serde(rename = "item"))]
+    MenuItem {
+        /// Id of the menu item, a new unique id is generated if `None`.
+        #[cfg_attr(feature = "serde", serde(default))]
+        id: Option<MenuId>,
+        /// Text of the menu item.
+        text: String,
+        /// Whether the menu item is enabled, defaults to `true`.
+        #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+        enabled: bool,
+        /// Whether the menu item is visible, defaults to `true`.
+        #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+        visible: bool,
+        /// Tooltip of the menu item.
+        #[cfg_attr(feature = "serde", serde(default))]
+        tooltip: Option<String>,
+        /// Secondary text of the menu item, see [`MenuItem::set_secondary_text`].
+        #[cfg_attr(feature = "serde", serde(default))]
+        secondary_text: Option<String>,
+        /// Accelerator or key sequence of the menu item.
+        #[cfg_attr(feature = "serde", serde(default))]
+        accelerator: Option<String>,
+    },
+    /// A [`CheckMenuItem`].
+    Check {
+        /// Id of the menu item, a new unique id is generated if `None`.
+        #[cfg_attr(feature = "serde", serde(default))]
+        id: Option<MenuId>,
+        /// Text of the menu item.
+        text: String,
+        /// Whether the menu item is enabled, defaults to `true`.
+        #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+        enabled: bool,
+        /// Whether the menu item is visible, defaults to `true`.
+        #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+        visible: bool,
+        /// Tooltip of the menu item.
+        #[cfg_attr(feature = "serde", serde(default))]
+        tooltip: Option<String>,
+        /// Secondary text of the menu item, see [`MenuItem::set_secondary_text`].
+        #[cfg_attr(feature = "serde", serde(default))]
+        secondary_text: Option<String>,
+        /// Whether the menu item is checked, defaults to `false`.
+        #[cfg_attr(feature = "serde", serde(default))]
+        checked: bool,
+        /// Accelerator or key sequence of the menu item.
+        #[cfg_attr(feature = "serde", serde(default))]
+        accelerator: Option<String>,
+    },
+    /// A [`RadioMenuItem`].
+    Radio {
+        /// Id of the menu item, a new unique id is generated if `None`.
+        #[cfg_attr(feature = "serde", serde(default))]
+        id: Option<MenuId>,
+        /// Text of the menu item.
+        text: String,
+        /// Name of the [`RadioGroup`] of the menu item,
+        /// radio items with the same group name anywhere in the spec are put in the same group.
+        ///
+        /// The group is created with [`RadioGroup::with_name`], groups without a name
+        /// get a generated one when snapshotting a menu.
+        group: String,
+        /// Whether the menu item is enabled, defaults to `true`.
+        #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+        enabled: bool,
+        /// Whether the menu item is visible, defaults to `true`.
+        #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+        visible: bool,
+        /// Tooltip of the menu item.
+        #[cfg_attr(feature = "serde", serde(default))]
+        tooltip: Option<String>,
+        /// Secondary text of the menu item, see [`MenuItem::set_secondary_text`].
+        #[cfg_attr(feature = "serde", serde(default))]
+        secondary_text: Option<String>,
+        /// Whether the menu item is checked, defaults to `false`.
+        #[cfg_attr(feature = "serde", serde(default))]
+        checked: bool,
+        /// Accelerator or key sequence of the menu item.
+        #[cfg_attr(feature = "serde", serde(default))]
+        accelerator: Option<String>,
+    },
+    /// An [`IconMenuItem`].
+    ///
+    /// Only native icons can be described, see [`IconMenuItem::with_native_icon`].
+    Icon {
+        /// Id of the menu item, a new unique id is generated if `None`.
+        #[cfg_attr(feature = "serde", serde(default))]
+        id: Option<MenuId>,
+        /// Text of the menu item.
+        text: String,
+        /// Whether the menu item is enabled, defaults to `true`.
+        #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+        enabled: bool,
+        /// Whether the menu item is visible, defaults to `true`.
+        #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+        visible: bool,
+        /// Tooltip of the menu item.
+        #[cfg_attr(feature = "serde", serde(default))]
+        tooltip: Option<String>,
+        /// Secondary text of the menu item, see [`MenuItem::set_secondary_text`].
+        #[cfg_attr(feature = "serde", serde(default))]
+        secondary_text: Option<String>,
+        /// Native icon of the menu item.
+        #[cfg_attr(feature = "serde", serde(default))]
+        native_icon: Option<NativeIcon>,
+        /// Accelerator or key sequence of the menu item.
+        #[cfg_attr(feature = "serde", serde(default))]
+        accelerator: Option<String>,
+    },
+    /// A [`PredefinedMenuItem`].
+    Predefined {
+        /// Which predefined menu item this is.
+        item: PredefinedMenuItemSpec,
+        /// Text of the menu item, the default text of the predefined item is used if `None`.
+        #[cfg_attr(feature = "serde", serde(default))]
+        text: Option<String>,
+        /// Whether the menu item is visible, defaults to `true`.
+        #[cfg_attr(feature = "serde", serde(default = "default_true"))]
+        visible: bool,
+        /// Tooltip of the menu item.
+        #[cfg_attr(feature = "serde", serde(default))]
+        tooltip: Option<String>,
+        /// Secondary text of the menu item, see [`MenuItem::set_secondary_text`].
+        #[cfg_attr(feature = "serde", serde(default))]
+        secondary_text: Option<String>,
+    },
+}
+
+/// Describes which [`PredefinedMenuItem`] a [`MenuItemSpec::Predefined`] is.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum PredefinedMenuItemSpec {
+    /// See [`PredefinedMenuItem::separator`].
+    Separator,
+    /// See [`PredefinedMenuItem::copy`].
+    Copy,
+    /// See [`PredefinedMenuItem::cut`].
+    Cut,
+    /// See [`PredefinedMenuItem::paste`].
+    Paste,
+    /// See [`PredefinedMenuItem::select_all`].
+    SelectAll,
+    /// See [`PredefinedMenuItem::undo`].
+    Undo,
+    /// See [`PredefinedMenuItem::redo`].
+    Redo,
+    /// See [`PredefinedMenuItem::minimize`].
+    Minimize,
+    /// See [`PredefinedMenuItem::maximize`].
+    Maximize,
+    /// See [`PredefinedMenuItem::fullscreen`].
+    Fullscreen,
+    /// See [`PredefinedMenuItem::hide`].
+    Hide,
+    /// See [`PredefinedMenuItem::hide_others`].
+    HideOthers,
+    /// See [`PredefinedMenuItem::show_all`].
+    ShowAll,
+    /// See [`PredefinedMenuItem::close_window`].
+    CloseWindow,
+    /// See [`PredefinedMenuItem::quit`].
+    Quit,
+    /// See [`PredefinedMenuItem::about`].
+    About(Option<Box<AboutMetadata>>),
+    /// See [`PredefinedMenuItem::services`].
+    Services,
+    /// See [`PredefinedMenuItem::bring_all_to_front`].
+    BringAllToFront,
+    /// A predefined menu item that doesn't do anything.
+    None,
+}
+
+/// Parse an optional accelerator string of a spec into the accelerator
+/// the item is created with and the multi-stroke key sequence that is set on it afterwards.
+fn parse_accelerator(
+    accelerator: &Option<String>,
+) -> crate::Result<(Option<Accelerator>, Option<KeySequence>)> {
+    let key_sequence = accelerator
+        .as_deref()
+        .map(str::parse::<KeySequence>)
+        .transpose()?;
+    Ok(match key_sequence {
+        Some(key_sequence) if key_sequence.is_chord() => (None, Some(key_sequence)),
+        key_sequence => (key_sequence.map(|s| s.first()), None),
+    })
+}
+
+/// Collect the named radio groups of `items` and their submenus,
+/// so generated names of unnamed groups don't take the names of groups found later.
+pub(crate) fn radio_group_names(items: &[MenuItemKind]) -> Vec<(RadioGroup, String)> {
+    fn collect(items: &[MenuItemKind], groups: &mut Vec<(RadioGroup, String)>) {
+        for item in items {
+            match item {
+                MenuItemKind::Submenu(i) => collect(&i.items(), groups),
+                MenuItemKind::Radio(i) => {
+                    let group = i.group();
+                    if let Some(name) = group.name() {
+                        if !groups.iter().any(|(g, n)| g.ptr_eq(&group) || n == name) {
+                            let name = name.to_string();
+                            groups.push((group, name));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut groups = Vec::new();
+    collect(items, &mut groups);
+    groups
+}
+
+impl MenuItemSpec {
+    /// Build the live menu item described by this spec,
+    /// `groups` holds the radio groups created so far by their names.
+    pub(crate) fn build(
+        &self,
+        groups: &mut HashMap<String, RadioGroup>,
+    ) -> crate::Result<MenuItemKind> {
+        let mut chord = None;
+        let item = match self {
+            MenuItemSpec::Submenu(spec) => MenuItemKind::Submenu(spec.build(groups)?),
+            MenuItemSpec::MenuItem {
+                id,
+                text,
+                enabled,
+                accelerator,
+                ..
+            } => {
+                let (accelerator, key_sequence) = parse_accelerator(accelerator)?;
+                chord = key_sequence;
+                MenuItemKind::MenuItem(match id {
+                    Some(id) => MenuItem::with_id(id.clone(), text, *enabled, accelerator),
+                    None => MenuItem::new(text, *enabled, accelerator),
+                })
+            }
+            MenuItemSpec::Check {
+                id,
+                text,
+                enabled,
+                checked,
+                accelerator,
+                ..
+            } => {
+                let (accelerator, key_sequence) = parse_accelerator(accelerator)?;
+                chord = key_sequence;
+                MenuItemKind::Check(match id {
+                    Some(id) => {
+                        CheckMenuItem::with_id(id.clone(), text, *enabled, *checked, accelerator)
+                    }
+                    None => CheckMenuItem::new(text, *enabled, *checked, accelerator),
+                })
+            }
+            MenuItemSpec::Radio {
+                id,
+                text,
+                group,
+                enabled,
+                checked,
+                accelerator,
+                ..
+            } => {
+                let (accelerator, key_sequence) = parse_accelerator(accelerator)?;
+                chord = key_sequence;
+                let group = groups
+                    .entry(group.clone())
+                    .or_insert_with(|| RadioGroup::with_name(group));
+                MenuItemKind::Radio(match id {
+                    Some(id) => RadioMenuItem::with_id(
+                        id.clone(),
+                        text,
+                        group,
+                        *enabled,
+                        *checked,
+                        accelerator,
+                    ),
+                    None => RadioMenuItem::new(text, group, *enabled, *checked, accelerator),
+                })
+            }
+            MenuItemSpec::Icon {
+                id,
+                text,
+                enabled,
+                native_icon,
+                accelerator,
+                ..
+            } => {
+                let (accelerator, key_sequence) = parse_accelerator(accelerator)?;
+                chord = key_sequence;
+                MenuItemKind::Icon(match (id, native_icon) {
+                    (Some(id), Some(icon)) => IconMenuItem::with_id_and_native_icon(
+                        id.clone(),
+                        text,
+                        *enabled,
+                        Some(*icon),
+                        accelerator,
+                    ),
+                    (None, Some(icon)) => {
+                        IconMenuItem::with_native_icon(text, *enabled, Some(*icon), accelerator)
+                    }
+                    (Some(id), None) => {
+                        IconMenuItem::with_id(id.clone(), text, *enabled, None, accelerator)
+                    }
+                    (None, None) => IconMenuItem::new(text, *enabled, None, accelerator),
+                })
+            }
+            MenuItemSpec::Predefined { item, text, .. } => {
+                let text = text.as_deref();
+                MenuItemKind::Predefined(match item {
+                    PredefinedMenuItemSpec::Separator => PredefinedMenuItem::separator(),
+                    PredefinedMenuItemSpec::Copy => PredefinedMenuItem::copy(text),
+                    PredefinedMenuItemSpec::Cut => PredefinedMenuItem::cut(text),
+                    PredefinedMenuItemSpec::Paste => PredefinedMenuItem::paste(text),
+                    PredefinedMenuItemSpec::SelectAll => PredefinedMenuItem::select_all(text),
+                    PredefinedMenuItemSpec::Undo => PredefinedMenuItem::undo(text),
+                    PredefinedMenuItemSpec::Redo => PredefinedMenuItem::redo(text),
+                    PredefinedMenuItemSpec::Minimize => PredefinedMenuItem::minimize(text),
+                    PredefinedMenuItemSpec::Maximize => PredefinedMenuItem::maximize(text),
+                    PredefinedMenuItemSpec::Fullscreen => PredefinedMenuItem::fullscreen(text),
+                    PredefinedMenuItemSpec::Hide => PredefinedMenuItem::hide(text),
+                    PredefinedMenuItemSpec::HideOthers => PredefinedMenuItem::hide_others(text),
+                    PredefinedMenuItemSpec::ShowAll => PredefinedMenuItem::show_all(text),
+                    PredefinedMenuItemSpec::CloseWindow => PredefinedMenuItem::close_window(text),
+                    PredefinedMenuItemSpec::Quit => PredefinedMenuItem::quit(text),
+                    PredefinedMenuItemSpec::About(metadata) => {
+                        PredefinedMenuItem::about(text, metadata.as_deref().cloned())
+                    }
+                    PredefinedMenuItemSpec::Services => PredefinedMenuItem::services(text),
+                    PredefinedMenuItemSpec::BringAllToFront => {
+                        PredefinedMenuItem::bring_all_to_front(text)
+                    }
+                    PredefinedMenuItemSpec::None => {
+                        PredefinedMenuItem::new(PredefinedMenuItemType::None, text)
+                    }
+                })
+            }
+        };
+
+        {
+            let (visible, tooltip, secondary_text) = self.display();
+            let mut child = item.child_mut();
+            child.set_visible(visible);
+            child.set_tooltip(tooltip);
+            child.set_secondary_text(secondary_text);
+            // the item isn't in any menu yet, so there is nothing to validate the key sequence against
+            if chord.is_some() {
+                child.set_key_sequence(chord);
+            }
+        }
+
+        Ok(item)
+    }
+
+    /// Returns the visibility, tooltip and secondary text of the described item.
+    fn display(&self) -> (bool, Option<&str>, Option<&str>) {
+        match self {
+            MenuItemSpec::Submenu(SubmenuSpec {
+                visible,
+                tooltip,
+                secondary_text,
+                ..
+            })
+            | MenuItemSpec::MenuItem {
+                visible,
+                tooltip,
+                secondary_text,
+                ..
+            }
+            | MenuItemSpec::Check {
+                visible,
+                tooltip,
+                secondary_text,
+                ..
+            }
+            | MenuItemSpec::Radio {
+                visible,
+                tooltip,
+                secondary_text,
+                ..
+            }
+            | MenuItemSpec::Icon {
+                visible,
+                tooltip,
+                secondary_text,
+                ..
+            }
+            | MenuItemSpec::Predefined {
+                visible,
+                tooltip,
+                secondary_text,
+                ..
+            } => (*visible, tooltip.as_deref(), secondary_text.as_deref()),
+        }
+    }
+
+    /// Snapshot the current state of `item`,
+    /// `groups` holds the radio groups found so far along with their names.
+    pub(crate) fn snapshot(item: &MenuItemKind, groups: &mut Vec<(RadioGroup, String)>) -> Self {
+        let (accelerator, visible, tooltip, secondary_text) = {
+            let child = item.child();
+            let accelerator = child
+                .key_sequence()
+                .map(|s| s.to_string())
+                .or_else(|| child.accelerator().map(|a| a.to_string()));
+            (
+                accelerator,
+                child.is_visible(),
+                child.tooltip(),
+                child.secondary_text(),
+            )
+        };
+
+        match item {
+            MenuItemKind::Submenu(i) => MenuItemSpec::Submenu(SubmenuSpec::snapshot(i, groups)),
+            MenuItemKind::MenuItem(i) => MenuItemSpec::MenuItem {
+                id: Some(i.id().clone()),
+                text: i.text(),
+                enabled: i.is_enabled(),
+                accelerator,
+                visible,
+                tooltip,
+                secondary_text,
+            },
+            MenuItemKind::Check(i) => MenuItemSpec::Check {
+                id: Some(i.id().clone()),
+                text: i.text(),
+                enabled: i.is_enabled(),
+                checked: i.is_checked(),
+                accelerator,
+                visible,
+                tooltip,
+                secondary_text,
+            },
+            MenuItemKind::Radio(i) => {
+                let group = i.group();
+                let name = match groups.iter().find(|(g, _)| g.ptr_eq(&group)) {
+                    Some((_, name)) => name.clone(),
+                    // named groups are collected by `radio_group_names` beforehand,
+                    // so this group has no name or shares it with another group
+                    None => {
+                        let name = (1..)
+                            .map(|i| format!("group-{i}"))
+                            .find(|name| !groups.iter().any(|(_, n)| n == name))
+                            .unwrap();
+                        groups.push((group, name.clone()));
+                        name
+                    }
+                };
+
+                MenuItemSpec::Radio {
+                    id: Some(i.id().clone()),
+                    text: i.text(),
+                    group: name,
+                    enabled: i.is_enabled(),
+                    checked: i.is_checked(),
+                    accelerator,
+                    visible,
+                    tooltip,
+                    secondary_text,
+                }
+            }
+            MenuItemKind::Icon(i) => MenuItemSpec::Icon {
+                id: Some(i.id().clone()),
+                text: i.text(),
+                enabled: i.is_enabled(),
+                #[cfg(target_os = "macos")]
+                native_icon: i.inner.borrow().native_icon(),
+                #[cfg(not(target_os = "macos"))]
+                native_icon: None,
+                accelerator,
+                visible,
+                tooltip,
+                secondary_text,
+            },
+            MenuItemKind::Predefined(i) => {
+                let item_type = i.inner.borrow().predefined_item_type();
+                let text = i.text();
+                let text = match &item_type {
+                    Some(item_type) if item_type.text() == text => None,
+                    _ => Some(text),
+                };
+
+                let item = match item_type {
+                    Some(PredefinedMenuItemType::Copy) => PredefinedMenuItemSpec::Copy,
+                    Some(PredefinedMenuItemType::Cut) => PredefinedMenuItemSpec::Cut,
+                    Some(PredefinedMenuItemType::Paste) => PredefinedMenuItemSpec::Paste,
+                    Some(PredefinedMenuItemType::SelectAll) => PredefinedMenuItemSpec::SelectAll,
+                    Some(PredefinedMenuItemType::Undo) => PredefinedMenuItemSpec::Undo,
+                    Some(PredefinedMenuItemType::Redo) => PredefinedMenuItemSpec::Redo,
+                    Some(PredefinedMenuItemType::Minimize) => PredefinedMenuItemSpec::Minimize,
+                    Some(PredefinedMenuItemType::Maximize) => PredefinedMenuItemSpec::Maximize,
+                    Some(PredefinedMenuItemType::Fullscreen) => PredefinedMenuItemSpec::Fullscreen,
+                    Some(PredefinedMenuItemType::Hide) => PredefinedMenuItemSpec::Hide,
+                    Some(PredefinedMenuItemType::HideOthers) => PredefinedMenuItemSpec::HideOthers,
+                    Some(PredefinedMenuItemType::ShowAll) => PredefinedMenuItemSpec::ShowAll,
+                    Some(PredefinedMenuItemType::CloseWindow) => {
+                        PredefinedMenuItemSpec::CloseWindow
+                    }
+                    Some(PredefinedMenuItemType::Quit) => PredefinedMenuItemSpec::Quit,
+                    Some(PredefinedMenuItemType::About(metadata)) => {
+                        PredefinedMenuItemSpec::About(metadata.map(Box::new))
+                    }
+                    Some(PredefinedMenuItemType::Services) => PredefinedMenuItemSpec::Services,
+                    Some(PredefinedMenuItemType::BringAllToFront) => {
+                        PredefinedMenuItemSpec::BringAllToFront
+                    }
+                    Some(PredefinedMenuItemType::Separator) => PredefinedMenuItemSpec::Separator,
+                    Some(PredefinedMenuItemType::None) | None => PredefinedMenuItemSpec::None,
+                };
+
+                MenuItemSpec::Predefined {
+                    item,
+                    text,
+                    visible,
+                    tooltip,
+                    secondary_text,
+                }
+            }
+        }
+    }
+}
+
+impl SubmenuSpec {
+    pub(crate) fn build(&self, groups: &mut HashMap<String, RadioGroup>) -> crate::Result<Submenu> {
+        let submenu = match &self.id {
+            Some(id) => Submenu::with_id(id.clone(), &self.text, self.enabled),
+            None => Submenu::new(&self.text, self.enabled),
+        };
+        for item in &self.items {
+            submenu.append(item.build(groups)?.as_ref())?;
+        }
+        Ok(submenu)
+    }
+
+    pub(crate) fn snapshot(submenu: &Submenu, groups: &mut Vec<(RadioGroup, String)>) -> Self {
+        Self {
+            id: Some(submenu.id().clone()),
+            text: submenu.text(),
+            enabled: submenu.is_enabled(),
+            visible: submenu.is_visible(),
+            tooltip: submenu.tooltip(),
+            secondary_text: submenu.secondary_text(),
+            items: submenu
+                .items()
+                .iter()
+                .map(|i| MenuItemSpec::snapshot(i, groups))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        Menu, MenuId, MenuItemKind, MenuItemSpec, MenuSpec, PredefinedMenuItemSpec, RadioGroup,
+        RadioMenuItem, SubmenuSpec,
+    };
+
+    fn item(id: &str, enabled: bool, accelerator: Option<&str>) -> MenuItemSpec {
+        MenuItemSpec::MenuItem {
+            id: Some(MenuId::new(id)),
+            text: id.to_string(),
+            enabled,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
+            accelerator: accelerator.map(|a| a.to_string()),
+        }
+    }
+
+    fn radio(id: &str, group: &str, checked: bool) -> MenuItemSpec {
+        MenuItemSpec::Radio {
+            id: Some(MenuId::new(id)),
+            text: id.to_string(),
+            group: group.to_string(),
+            enabled: true,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
+            checked,
+            accelerator: None,
+        }
+    }
+
+    fn predefined(item: PredefinedMenuItemSpec, text: Option<&str>) -> MenuItemSpec {
+        MenuItemSpec::Predefined {
+            item,
+            text: text.map(|t| t.to_string()),
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
+        }
+    }
+
+    fn submenu(id: &str, items: Vec<MenuItemSpec>) -> MenuItemSpec {
+        MenuItemSpec::Submenu(SubmenuSpec {
+            id: Some(MenuId::new(id)),
+            text: id.to_string(),
+            enabled: true,
+            visible: true,
+            tooltip: None,
+            secondary_text: None,
+            items,
+        })
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn menu_from_spec_and_back() {
+        let spec = MenuSpec {
+            id: Some(MenuId::new("main")),
+            items: vec![submenu(
+                "file",
+                vec![
+                    item("save", false, Some("shift+control+KeyS")),
+                    predefined(PredefinedMenuItemSpec::Separator, None),
+                    radio("small", "size", false),
+                    radio("large", "size", true),
+                    submenu("view", vec![radio("medium", "size", true)]),
+                ],
+            )],
+        };
+
+        let menu = Menu::from_spec(&spec).unwrap();
+        assert_eq!(menu.id(), "main");
+
+        let snapshot = menu.to_spec();
+        let file = match &snapshot.items[0] {
+            MenuItemSpec::Submenu(file) => file,
+            _ => panic!("expected a submenu"),
+        };
+        assert_eq!(file.id, Some(MenuId::new("file")));
+        assert_eq!(file.items.len(), 5);
+
+        match &file.items[0] {
+            MenuItemSpec::MenuItem {
+                id,
+                enabled,
+                accelerator,
+                ..
+            } => {
+                assert_eq!(id, &Some(MenuId::new("save")));
+                assert!(!enabled);
+                assert_eq!(accelerator.as_deref(), Some("shift+control+KeyS"));
+            }
+            _ => panic!("expected a menu item"),
+        }
+
+        // radio items in different submenus share the same group by name
+        let groups = file.items[2..4]
+            .iter()
+            .chain(match &file.items[4] {
+                MenuItemSpec::Submenu(view) => &view.items,
+                _ => panic!("expected a submenu"),
+            })
+            .map(|i| match i {
+                MenuItemSpec::Radio { group, checked, .. } => (group.clone(), *checked),
+                _ => panic!("expected a radio item"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![
+                ("size".to_string(), false),
+                ("size".to_string(), false),
+                ("size".to_string(), true)
+            ]
+        );
+
+        assert!(matches!(
+            menu.items()[0],
+            MenuItemKind::Submenu(ref s) if s.items().len() == 5
+        ));
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn spec_round_trip_is_idempotent() {
+        let spec = MenuSpec {
+            id: Some(MenuId::new("main")),
+            items: vec![
+                MenuItemSpec::MenuItem {
+                    id: Some(MenuId::new("save")),
+                    text: "Save".to_string(),
+                    enabled: true,
+                    visible: true,
+                    tooltip: Some("Save the file".to_string()),
+                    secondary_text: Some("Unsaved".to_string()),
+                    accelerator: Some("CmdOrCtrl+shift+KeyS".to_string()),
+                },
+                MenuItemSpec::Check {
+                    id: Some(MenuId::new("comment")),
+                    text: "Comment".to_string(),
+                    enabled: true,
+                    visible: false,
+                    tooltip: None,
+                    secondary_text: None,
+                    checked: true,
+                    accelerator: Some("control+KeyK control+KeyC".to_string()),
+                },
+                radio("small", "size", false),
+                radio("large", "size", true),
+                predefined(PredefinedMenuItemSpec::Copy, None),
+                predefined(PredefinedMenuItemSpec::Cut, Some("Cut Selection")),
+                predefined(PredefinedMenuItemSpec::None, None),
+            ],
+        };
+
+        fn assert_spec(spec: &MenuSpec) {
+            assert_eq!(spec.id, Some(MenuId::new("main")));
+            assert_eq!(spec.items.len(), 7);
+
+            match &spec.items[0] {
+                MenuItemSpec::MenuItem {
+                    id,
+                    text,
+                    enabled,
+                    visible,
+                    tooltip,
+                    secondary_text,
+                    accelerator,
+                } => {
+                    assert_eq!(id, &Some(MenuId::new("save")));
+                    assert_eq!(text, "Save");
+                    assert!(enabled);
+                    assert!(visible);
+                    assert_eq!(tooltip.as_deref(), Some("Save the file"));
+                    assert_eq!(secondary_text.as_deref(), Some("Unsaved"));
+                    assert_eq!(accelerator.as_deref(), Some("CmdOrCtrl+shift+KeyS"));
+                }
+                _ => panic!("expected a menu item"),
+            }
+
+            match &spec.items[1] {
+                MenuItemSpec::Check {
+                    id,
+                    visible,
+                    tooltip,
+                    checked,
+                    accelerator,
+                    ..
+                } => {
+                    assert_eq!(id, &Some(MenuId::new("comment")));
+                    assert!(!visible);
+                    assert_eq!(tooltip, &None);
+                    assert!(checked);
+                    assert_eq!(accelerator.as_deref(), Some("control+KeyK control+KeyC"));
+                }
+                _ => panic!("expected a check menu item"),
+            }
+
+            let radios = spec.items[2..4]
+                .iter()
+                .map(|i| match i {
+                    MenuItemSpec::Radio {
+                        id, group, checked, ..
+                    } => (id.clone().unwrap(), group.as_str(), *checked),
+                    _ => panic!("expected a radio item"),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                radios,
+                vec![
+                    (MenuId::new("small"), "size", false),
+                    (MenuId::new("large"), "size", true)
+                ]
+            );
+
+            // predefined items keep the default text out of the spec
+            assert!(matches!(
+                &spec.items[4],
+                MenuItemSpec::Predefined {
+                    item: PredefinedMenuItemSpec::Copy,
+                    text: None,
+                    visible: true,
+                    ..
+                }
+            ));
+            assert!(matches!(
+                &spec.items[5],
+                MenuItemSpec::Predefined {
+                    item: PredefinedMenuItemSpec::Cut,
+                    text: Some(text),
+                    ..
+                } if text == "Cut Selection"
+            ));
+            assert!(matches!(
+                &spec.items[6],
+                MenuItemSpec::Predefined {
+                    item: PredefinedMenuItemSpec::None,
+                    text: None,
+                    ..
+                }
+            ));
+        }
+
+        let snapshot = Menu::from_spec(&spec).unwrap().to_spec();
+        assert_spec(&snapshot);
+
+        let snapshot = Menu::from_spec(&snapshot).unwrap().to_spec();
+        assert_spec(&snapshot);
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn unnamed_radio_groups_get_unique_names() {
+        let named = RadioGroup::with_name("group-1");
+        let unnamed = RadioGroup::new();
+        let menu = Menu::new();
+        menu.append_items(&[
+            &RadioMenuItem::new("a", &unnamed, true, true, None),
+            &RadioMenuItem::new("b", &named, true, true, None),
+        ])
+        .unwrap();
+
+        let groups = menu
+            .to_spec()
+            .items
+            .iter()
+            .map(|i| match i {
+                MenuItemSpec::Radio { group, .. } => group.clone(),
+                _ => panic!("expected a radio item"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(groups, vec!["group-2".to_string(), "group-1".to_string()]);
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn invalid_accelerator_in_spec() {
+        let spec = MenuSpec {
+            id: None,
+            items: vec![item("save", true, Some("control+shift"))],
+        };
+        assert!(Menu::from_spec(&spec).is_err());
+    }
+}
@@ -0,0 +1,540 @@
+// Copyright 2022-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::rc::Rc;
+
+use crate::{
+    accelerator::{Accelerator, KeySequence},
+    IsMenuItem, Menu, MenuId, MenuItemKind, Submenu,
+};
+
+/// A problem found in a menu tree by [`Menu::validate`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuValidationIssue {
+    /// More than one item in the menu tree has this id.
+    DuplicateId(MenuId),
+    /// The submenu with this id has no items.
+    EmptySubmenu(MenuId),
+    /// The items with these ids share the same accelerator or key sequence,
+    /// see [`AcceleratorConflict`].
+    AcceleratorConflict {
+        accelerator: Accelerator,
+        key_sequence: KeySequence,
+        items: Vec<MenuId>,
+    },
+}
+
+/// A group of items in a menu tree whose key sequences start with the same key sequence,
+/// returned from [`Menu::accelerator_conflicts`].
+///
+/// This is either an accelerator or key sequence used by more than one item,
+/// or the key sequence of one item that is also the start of the key sequences of other items,
+/// for example `Ctrl+K` and `Ctrl+K Ctrl+C`, which makes the longer ones impossible to trigger.
+#[derive(Clone)]
+pub struct AcceleratorConflict {
+    /// The first stroke of [`AcceleratorConflict::key_sequence`].
+    pub accelerator: Accelerator,
+    /// The shared key sequence, which is the key sequence of the first item of [`AcceleratorConflict::items`]
+    /// that is the start of the key sequences of all the other items.
+    pub key_sequence: KeySequence,
+    /// The items using this key sequence, in the order they appear in the menu tree.
+    pub items: Vec<MenuItemKind>,
+}
+
+/// Returns the menus that `item` was added to the tree of, still contain it
+/// and are in the strict mode returned by `strict`.
+fn strict_menus_containing(
+    item: &MenuItemKind,
+    strict: fn(&crate::platform_impl::Menu) -> bool,
+) -> Vec<Menu> {
+    let menus = item
+        .child()
+        .root_menus()
+        .iter()
+        .filter_map(|(id, m)| {
+            m.upgrade().map(|inner| Menu {
+                id: id.clone(),
+                inner,
+            })
+        })
+        .filter(|menu| strict(&menu.inner.borrow()))
+        .collect::<Vec<_>>();
+
+    // items aren't told when they are removed from a menu tree
+    menus
+        .into_iter()
+        .filter(|menu| {
+            menu.iter_all()
+                .any(|(_, i)| i.child_ptr() == item.child_ptr())
+        })
+        .collect()
+}
+
+/// Remember that `item` and the items in its tree were added to the trees of `menus`.
+fn add_root_menus(item: &dyn IsMenuItem, menus: &[crate::platform_impl::RootMenu]) {
+    for item in item_and_descendants(item) {
+        let mut child = item.child_mut();
+        let root_menus = child.root_menus_mut();
+        root_menus.retain(|(_, m)| m.strong_count() > 0);
+        for (id, menu) in menus {
+            if !root_menus.iter().any(|(_, m)| m.ptr_eq(menu)) {
+                root_menus.push((id.clone(), menu.clone()));
+            }
+        }
+    }
+}
+
+/// Remember that `item` was added to `menu`.
+pub(crate) fn added(menu: &Menu, item: &dyn IsMenuItem) {
+    add_root_menus(item, &[(menu.id.clone(), Rc::downgrade(&menu.inner))]);
+}
+
+/// Remember that `item` was added to `submenu`, so to the trees of the menus `submenu` is in.
+pub(crate) fn added_to_submenu(submenu: &Submenu, item: &dyn IsMenuItem) {
+    let menus = submenu.inner.borrow().root_menus().to_vec();
+    if !menus.is_empty() {
+        add_root_menus(item, &menus);
+    }
+}
+
+/// Returns every item of `menu`'s tree once, even if it was added in more than one place.
+fn unique_items(items: impl Iterator<Item = MenuItemKind>) -> Vec<MenuItemKind> {
+    let mut unique: Vec<MenuItemKind> = Vec::new();
+    for item in items {
+        if !unique.iter().any(|i| i.child_ptr() == item.child_ptr()) {
+            unique.push(item);
+        }
+    }
+    unique
+}
+
+/// Returns `item` and, if it is a submenu, all the items in its tree.
+fn item_and_descendants(item: &dyn IsMenuItem) -> Vec<MenuItemKind> {
+    let item = item.kind();
+    let mut items = vec![item.clone()];
+    if let MenuItemKind::Submenu(submenu) = &item {
+        items.extend(submenu.iter_all().map(|(_, i)| i));
+    }
+    items
+}
+
+/// Check that adding `new_items` to `menu`'s tree doesn't introduce an id that already exists in it.
+fn check_duplicate_ids(menu: &Menu, new_items: &[MenuItemKind]) -> crate::Result<()> {
+    let items = unique_items(menu.iter_all().map(|(_, i)| i).chain(new_items.to_vec()));
+    for new_item in new_items {
+        let duplicate = items
+            .iter()
+            .any(|i| i.id() == new_item.id() && i.child_ptr() != new_item.child_ptr());
+        if duplicate {
+            return Err(crate::Error::DuplicateId(new_item.id().clone()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the key sequence that triggers `item`, which is a single-stroke key sequence
+/// if it only has an accelerator.
+fn key_sequence(item: &MenuItemKind) -> Option<KeySequence> {
+    let child = item.child();
+    child
+        .key_sequence()
+        .or_else(|| child.accelerator().map(KeySequence::from))
+}
+
+/// Returns `true` if `prefix` is `key_sequence` or the start of it.
+fn starts_with(key_sequence: &KeySequence, prefix: &KeySequence) -> bool {
+    key_sequence.strokes().starts_with(prefix.strokes())
+}
+
+/// Returns `true` if `a` and `b` can't be used in the same menu tree because
+/// they are the same or one of them is the start of the other.
+fn key_sequences_conflict(a: &KeySequence, b: &KeySequence) -> bool {
+    starts_with(a, b) || starts_with(b, a)
+}
+
+/// Returns the first item of `items` other than `item` whose key sequence conflicts with `sequence`.
+fn find_conflict(
+    items: &[MenuItemKind],
+    item: &MenuItemKind,
+    sequence: &KeySequence,
+) -> Option<MenuId> {
+    items
+        .iter()
+        .find(|i| {
+            i.child_ptr() != item.child_ptr()
+                && key_sequence(i)
+                    .map(|s| key_sequences_conflict(&s, sequence))
+                    .unwrap_or(false)
+        })
+        .map(|i| i.id().clone())
+}
+
+/// Check that none of `new_items` uses an accelerator or key sequence that conflicts
+/// with the one of another item in `menu`'s tree.
+fn check_accelerator_conflicts(menu: &Menu, new_items: &[MenuItemKind]) -> crate::Result<()> {
+    let items = unique_items(menu.iter_all().map(|(_, i)| i).chain(new_items.to_vec()));
+    for new_item in new_items {
+        let key_sequence = match key_sequence(new_item) {
+            Some(key_sequence) => key_sequence,
+            None => continue,
+        };
+        if let Some(conflict) = find_conflict(&items, new_item, &key_sequence) {
+            return Err(crate::Error::AcceleratorConflict(conflict));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that `item` can be added to `menu` according to the strict modes of `menu`.
+pub(crate) fn check_add(menu: &Menu, item: &dyn IsMenuItem) -> crate::Result<()> {
+    let (strict_ids, strict_accelerators) = {
+        let inner = menu.inner.borrow();
+        (inner.is_strict_ids(), inner.is_strict_accelerators())
+    };
+    if !strict_ids && !strict_accelerators {
+        return Ok(());
+    }
+
+    let new_items = item_and_descendants(item);
+    if strict_ids {
+        check_duplicate_ids(menu, &new_items)?;
+    }
+    if strict_accelerators {
+        check_accelerator_conflicts(menu, &new_items)?;
+    }
+
+    Ok(())
+}
+
+/// Check that `item` can be added to `submenu` according to the strict modes
+/// of all the menus that contain `submenu`.
+pub(crate) fn check_add_to_submenu(submenu: &Submenu, item: &dyn IsMenuItem) -> crate::Result<()> {
+    let submenu = MenuItemKind::Submenu(submenu.clone());
+    let id_menus = strict_menus_containing(&submenu, |m| m.is_strict_ids());
+    let accelerator_menus = strict_menus_containing(&submenu, |m| m.is_strict_accelerators());
+    if id_menus.is_empty() && accelerator_menus.is_empty() {
+        return Ok(());
+    }
+
+    let new_items = item_and_descendants(item);
+    for menu in id_menus {
+        check_duplicate_ids(&menu, &new_items)?;
+    }
+    for menu in accelerator_menus {
+        check_accelerator_conflicts(&menu, &new_items)?;
+    }
+
+    Ok(())
+}
+
+/// Check that `item` can use `accelerator` according to the strict accelerator mode
+/// of all the menus that contain `item`.
+pub(crate) fn check_set_accelerator(
+    item: &dyn IsMenuItem,
+    accelerator: Option<Accelerator>,
+) -> crate::Result<()> {
+    check_set_key_sequence(item, accelerator.map(KeySequence::from).as_ref())
+}
+
+/// Check that `item` can use `key_sequence` according to the strict accelerator mode
+/// of all the menus that contain `item`.
+pub(crate) fn check_set_key_sequence(
+    item: &dyn IsMenuItem,
+    key_sequence: Option<&KeySequence>,
+) -> crate::Result<()> {
+    let key_sequence = match key_sequence {
+        Some(key_sequence) => key_sequence,
+        None => return Ok(()),
+    };
+
+    let item = item.kind();
+    for menu in strict_menus_containing(&item, |m| m.is_strict_accelerators()) {
+        let items = unique_items(menu.iter_all().map(|(_, i)| i));
+        if let Some(conflict) = find_conflict(&items, &item, key_sequence) {
+            return Err(crate::Error::AcceleratorConflict(conflict));
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn accelerator_conflicts(menu: &Menu) -> Vec<AcceleratorConflict> {
+    let items = unique_items(menu.iter_all().map(|(_, i)| i))
+        .into_iter()
+        .filter_map(|i| key_sequence(&i).map(|s| (i, s)))
+        .collect::<Vec<_>>();
+
+    // every key sequence that is used more than once or is the start of another one
+    // is reported with all the items whose key sequences start with it
+    let mut conflicts: Vec<AcceleratorConflict> = Vec::new();
+    for (_, prefix) in &items {
+        if conflicts.iter().any(|c| &c.key_sequence == prefix) {
+            continue;
+        }
+        let conflicting = items
+            .iter()
+            .filter(|(_, s)| starts_with(s, prefix))
+            .map(|(i, _)| i.clone())
+            .collect::<Vec<_>>();
+        if conflicting.len() > 1 {
+            conflicts.push(AcceleratorConflict {
+                accelerator: prefix.first(),
+                key_sequence: prefix.clone(),
+                items: conflicting,
+            });
+        }
+    }
+    conflicts
+}
+
+pub(crate) fn validate(menu: &Menu) -> Vec<MenuValidationIssue> {
+    let items = unique_items(menu.iter_all().map(|(_, i)| i));
+    let mut issues = Vec::new();
+
+    let mut ids: Vec<(&MenuId, usize)> = Vec::new();
+    for item in &items {
+        match ids.iter_mut().find(|(id, _)| *id == item.id()) {
+            Some((_, count)) => *count += 1,
+            None => ids.push((item.id(), 1)),
+        }
+    }
+    issues.extend(
+        ids.into_iter()
+            .filter(|(_, count)| *count > 1)
+            .map(|(id, _)| MenuValidationIssue::DuplicateId(id.clone())),
+    );
+
+    issues.extend(items.iter().filter_map(|item| match item {
+        MenuItemKind::Submenu(submenu) if submenu.items().is_empty() => {
+            Some(MenuValidationIssue::EmptySubmenu(submenu.id().clone()))
+        }
+        _ => None,
+    }));
+
+    issues.extend(accelerator_conflicts(menu).into_iter().map(|conflict| {
+        MenuValidationIssue::AcceleratorConflict {
+            accelerator: conflict.accelerator,
+            key_sequence: conflict.key_sequence,
+            items: conflict.items.iter().map(|i| i.id().clone()).collect(),
+        }
+    }));
+
+    issues
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        accelerator::{Accelerator, Code, Modifiers},
+        CheckMenuItem, Error, Menu, MenuId, MenuItem, MenuValidationIssue, Submenu,
+    };
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn strict_ids_reject_duplicates() {
+        let item = MenuItem::with_id("save", "Save", true, None);
+        let submenu = Submenu::with_items("File", true, &[&item]).unwrap();
+        let menu = Menu::with_items(&[&submenu]).unwrap();
+
+        assert!(!menu.is_strict_ids());
+        menu.set_strict_ids(true);
+        assert!(menu.is_strict_ids());
+
+        assert!(matches!(
+            submenu.append(&MenuItem::with_id("save", "Save", true, None)),
+            Err(Error::DuplicateId(id)) if id == "save"
+        ));
+        assert!(matches!(
+            menu.append(
+                &Submenu::with_items(
+                    "Edit",
+                    true,
+                    &[&MenuItem::with_id("save", "Save", true, None)]
+                )
+                .unwrap()
+            ),
+            Err(Error::DuplicateId(_))
+        ));
+
+        // the same item can be added in more than one place
+        menu.append(&Submenu::with_items("Recent", true, &[&item]).unwrap())
+            .unwrap();
+
+        menu.set_strict_ids(false);
+        assert!(!menu.is_strict_ids());
+        submenu
+            .append(&MenuItem::with_id("save", "Save", true, None))
+            .unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn validate_reports_issues() {
+        let accelerator = Accelerator::new(Some(Modifiers::CONTROL), Code::KeyS);
+        let menu = Menu::with_items(&[
+            &Submenu::with_items(
+                "File",
+                true,
+                &[
+                    &MenuItem::with_id("save", "Save", true, Some(accelerator)),
+                    &MenuItem::with_id("save", "Save As", true, None),
+                    &MenuItem::with_id("save-all", "Save All", true, Some(accelerator)),
+                ],
+            )
+            .unwrap(),
+            &Submenu::with_id("empty", "Empty", true),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            menu.validate(),
+            vec![
+                MenuValidationIssue::DuplicateId(MenuId::new("save")),
+                MenuValidationIssue::EmptySubmenu(MenuId::new("empty")),
+                MenuValidationIssue::AcceleratorConflict {
+                    accelerator,
+                    key_sequence: accelerator.into(),
+                    items: vec![MenuId::new("save"), MenuId::new("save-all")],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn accelerator_conflicts_and_strict_accelerators() {
+        let save = MenuItem::with_id(
+            "save",
+            "Save",
+            true,
+            Some("CmdOrCtrl+KeyS".parse().unwrap()),
+        );
+        let spell = CheckMenuItem::with_id("spell", "Spell", true, false, None);
+        let submenu = Submenu::with_items("File", true, &[&save, &spell]).unwrap();
+        let menu = Menu::with_items(&[&submenu]).unwrap();
+        assert!(menu.accelerator_conflicts().is_empty());
+
+        // `CmdOrCtrl` is resolved to the platform modifier before comparing
+        #[cfg(target_os = "macos")]
+        let same = "super+KeyS";
+        #[cfg(not(target_os = "macos"))]
+        let same = "control+KeyS";
+        spell.set_accelerator(Some(same.parse().unwrap())).unwrap();
+
+        let conflicts = menu.accelerator_conflicts();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(
+            conflicts[0]
+                .items
+                .iter()
+                .map(|i| i.id().clone())
+                .collect::<Vec<_>>(),
+            vec![MenuId::new("save"), MenuId::new("spell")]
+        );
+        spell.set_accelerator(None).unwrap();
+
+        menu.set_strict_accelerators(true);
+        assert!(menu.is_strict_accelerators());
+        assert!(matches!(
+            spell.set_accelerator(Some(same.parse().unwrap())),
+            Err(Error::AcceleratorConflict(id)) if id == "save"
+        ));
+        assert!(matches!(
+            submenu.append(&MenuItem::new("Save 2", true, Some(same.parse().unwrap()))),
+            Err(Error::AcceleratorConflict(_))
+        ));
+        // setting the same accelerator again is not a conflict
+        save.set_accelerator(Some(same.parse().unwrap())).unwrap();
+        assert!(menu.accelerator_conflicts().is_empty());
+
+        // items removed from the menu tree are not checked anymore
+        menu.remove(&submenu).unwrap();
+        spell.set_accelerator(Some(same.parse().unwrap())).unwrap();
+    }
+
+    #[test]
+    #[cfg_attr(all(miri, not(target_os = "linux")), ignore)]
+    fn key_sequence_conflicts() {
+        let comment = MenuItem::with_id("comment", "Comment", true, None);
+        comment
+            .set_key_sequence(Some("Ctrl+KeyK Ctrl+KeyC".parse().unwrap()))
+            .unwrap();
+        let uncomment = MenuItem::with_id("uncomment", "Uncomment", true, None);
+        uncomment
+            .set_key_sequence(Some("Ctrl+KeyK Ctrl+KeyU".parse().unwrap()))
+            .unwrap();
+        let kill = MenuItem::with_id("kill", "Kill", true, None);
+        let menu = Menu::with_items(&[&comment, &uncomment, &kill]).unwrap();
+        assert!(menu.accelerator_conflicts().is_empty());
+
+        // the same chord twice
+        let duplicate = MenuItem::with_id("duplicate", "Duplicate", true, None);
+        duplicate
+            .set_key_sequence(Some("Ctrl+KeyK Ctrl+KeyC".parse().unwrap()))
+            .unwrap();
+        menu.append(&duplicate).unwrap();
+        // an accelerator that is the first stroke of the chords
+        let ctrl_k = Accelerator::new(Some(Modifiers::CONTROL), Code::KeyK);
+        kill.set_accelerator(Some(ctrl_k)).unwrap();
+
+        let conflicts = menu
+            .accelerator_conflicts()
+            .into_iter()
+            .map(|c| {
+                (
+                    c.key_sequence.to_string(),
+                    c.items.iter().map(|i| i.id().clone()).collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            conflicts,
+            vec![
+                (
+                    "control+KeyK control+KeyC".to_string(),
+                    vec![MenuId::new("comment"), MenuId::new("duplicate")]
+                ),
+                (
+                    "control+KeyK".to_string(),
+                    vec![
+                        MenuId::new("comment"),
+                        MenuId::new("uncomment"),
+                        MenuId::new("kill"),
+                        MenuId::new("duplicate")
+                    ]
+                ),
+            ]
+        );
+        menu.remove(&duplicate).unwrap();
+        kill.set_accelerator(None).unwrap();
+
+        menu.set_strict_accelerators(true);
+        assert!(matches!(
+            kill.set_accelerator(Some(ctrl_k)),
+            Err(Error::AcceleratorConflict(id)) if id == "comment"
+        ));
+        assert!(matches!(
+            kill.set_key_sequence(Some("Ctrl+KeyK Ctrl+KeyU".parse().unwrap())),
+            Err(Error::AcceleratorConflict(id)) if id == "uncomment"
+        ));
+        assert!(matches!(
+            kill.set_key_sequence(Some("Ctrl+KeyK Ctrl+KeyU Ctrl+KeyX".parse().unwrap())),
+            Err(Error::AcceleratorConflict(id)) if id == "uncomment"
+        ));
+        assert!(matches!(
+            menu.append(&duplicate),
+            Err(Error::AcceleratorConflict(id)) if id == "comment"
+        ));
+        kill.set_key_sequence(Some("Ctrl+KeyK Ctrl+KeyX".parse().unwrap()))
+            .unwrap();
+        // changing the key sequence of an item doesn't conflict with its own one
+        comment
+            .set_key_sequence(Some("Ctrl+KeyK Ctrl+KeyC".parse().unwrap()))
+            .unwrap();
+        assert!(menu.accelerator_conflicts().is_empty());
+    }
+}